@@ -1,4 +1,4 @@
 fn main() {
+    #[cfg(feature = "desktop")]
     tauri_build::build()
 }
-
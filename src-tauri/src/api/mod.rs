@@ -1,5 +1,7 @@
 pub mod arxiv;
+#[cfg(feature = "desktop")]
 pub mod groq;
 pub mod osv;
+pub mod osv_archive;
 pub mod rfc_editor;
 pub mod translate;
@@ -1,6 +1,8 @@
 //! OSV (Open Source Vulnerabilities) API クライアント
 //! https://osv.dev/docs/
 
+use crate::commands::vuln_commands::severity_order;
+use crate::cvss::{self, Cvss};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
@@ -9,12 +11,19 @@ const OSV_API_BASE: &str = "https://api.osv.dev/v1";
 /// OSV API クライアント
 pub struct OsvClient {
     client: Client,
+    base_url: String,
 }
 
 impl OsvClient {
     pub fn new() -> Self {
+        Self::with_base_url(OSV_API_BASE)
+    }
+
+    /// API のベース URL を指定して作成（テスト用のサーバーなど）
+    pub fn with_base_url(base_url: &str) -> Self {
         Self {
             client: Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
         }
     }
 
@@ -35,7 +44,7 @@ impl OsvClient {
 
         let response = self
             .client
-            .post(format!("{}/query", OSV_API_BASE))
+            .post(format!("{}/query", self.base_url))
             .json(&request)
             .send()
             .await
@@ -63,7 +72,7 @@ impl OsvClient {
 
         let response = self
             .client
-            .post(format!("{}/querybatch", OSV_API_BASE))
+            .post(format!("{}/querybatch", self.base_url))
             .json(&request)
             .send()
             .await
@@ -86,7 +95,7 @@ impl OsvClient {
     pub async fn get_vulnerability(&self, vuln_id: &str) -> Result<OsvVulnerability, OsvError> {
         let response = self
             .client
-            .get(format!("{}/vulns/{}", OSV_API_BASE, vuln_id))
+            .get(format!("{}/vulns/{}", self.base_url, vuln_id))
             .send()
             .await
             .map_err(|e| OsvError::Network(e.to_string()))?;
@@ -233,52 +242,161 @@ pub enum OsvError {
     Api(String),
     #[error("Parse error: {0}")]
     Parse(String),
+    #[error("IO error: {0}")]
+    Io(String),
+}
+
+/// 深刻度の算出元
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeveritySource {
+    /// severity[] の CVSS ベクター（またはスコア）
+    Cvss,
+    /// database_specific.severity（GitHub Advisory の CRITICAL / HIGH / MODERATE / LOW など）
+    DatabaseSpecific,
+    /// affected[].ecosystem_specific.severity
+    EcosystemSpecific,
+    /// いずれの情報もない
+    Unknown,
+}
+
+impl SeveritySource {
+    /// 保存・表示用の文字列
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SeveritySource::Cvss => "cvss",
+            SeveritySource::DatabaseSpecific => "database_specific",
+            SeveritySource::EcosystemSpecific => "ecosystem_specific",
+            SeveritySource::Unknown => "unknown",
+        }
+    }
+}
+
+/// データベース固有の深刻度表記を critical / high / medium / low に正規化
+fn normalize_severity_label(value: &serde_json::Value) -> Option<&'static str> {
+    match value.as_str()?.trim().to_lowercase().as_str() {
+        "critical" => Some("critical"),
+        "high" | "important" => Some("high"),
+        "moderate" | "medium" => Some("medium"),
+        "low" | "minor" | "negligible" => Some("low"),
+        _ => None,
+    }
 }
 
 // --- Helper functions ---
 
+impl OsvAffected {
+    /// 修正バージョンを取得
+    pub fn fixed_versions(&self) -> Vec<String> {
+        let mut fixed = Vec::new();
+        for range in &self.ranges {
+            for event in &range.events {
+                if let Some(ref v) = event.fixed {
+                    if !fixed.contains(v) {
+                        fixed.push(v.clone());
+                    }
+                }
+            }
+        }
+        fixed
+    }
+
+    /// 影響を受けるバージョンを区間ごとの文字列で取得
+    pub fn version_intervals(&self) -> Vec<String> {
+        // 明示的なバージョンリスト
+        let mut versions = self.versions.clone();
+        // 範囲指定（introduced ごとに 1 区間）
+        for range in &self.ranges {
+            let mut introduced: Option<&str> = None;
+
+            for event in &range.events {
+                if let Some(ref i) = event.introduced {
+                    if let Some(prev) = introduced.take() {
+                        versions.push(format!(">= {}", prev));
+                    }
+                    introduced = Some(i);
+                } else if let Some(ref f) = event.fixed {
+                    versions.push(format!(">= {}, < {}", introduced.take().unwrap_or("0"), f));
+                } else if let Some(ref l) = event.last_affected {
+                    versions.push(format!(">= {}, <= {}", introduced.take().unwrap_or("0"), l));
+                } else if let Some(ref l) = event.limit {
+                    versions.push(format!(">= {}, < {}", introduced.take().unwrap_or("0"), l));
+                }
+            }
+
+            if let Some(i) = introduced {
+                versions.push(format!(">= {}", i));
+            }
+        }
+        versions
+    }
+}
+
 impl OsvVulnerability {
+    /// CVSS ベクターを解析（複数ある場合は最も新しいバージョンを優先）
+    /// 解析できないベクターは読み飛ばし、数値のスコアやデータベース固有の深刻度で判定する
+    pub fn cvss(&self) -> Option<Cvss> {
+        self.severity
+            .iter()
+            .filter(|sev| sev.severity_type.starts_with("CVSS_"))
+            .filter_map(|sev| cvss::parse(&sev.score).ok())
+            .max_by_key(|parsed| parsed.version)
+    }
+
     /// CVSSスコアを取得（存在する場合）
     pub fn cvss_score(&self) -> Option<f64> {
-        for sev in &self.severity {
-            if sev.severity_type == "CVSS_V3" || sev.severity_type == "CVSS_V2" {
-                // CVSS vector stringからスコアを抽出
-                // 形式: "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H" または単純なスコア
-                if let Ok(score) = sev.score.parse::<f64>() {
-                    return Some(score);
-                }
-                // ベクター文字列の場合はスコアを計算しない（複雑なため）
-            }
+        if let Some(parsed) = self.cvss() {
+            return Some(parsed.score);
         }
-        None
+        // ベクターではなく数値だけが入っている場合
+        self.severity
+            .iter()
+            .filter(|sev| sev.severity_type.starts_with("CVSS_"))
+            .find_map(|sev| sev.score.parse::<f64>().ok())
     }
 
-    /// 深刻度を文字列で取得
-    pub fn severity_level(&self) -> String {
+    /// 深刻度とその算出元を判定
+    /// CVSS → database_specific.severity → affected[].ecosystem_specific.severity の順に参照し、
+    /// どれもなければ "unknown"
+    pub fn resolve_severity(&self) -> (String, SeveritySource) {
         if let Some(score) = self.cvss_score() {
-            match score {
-                s if s >= 9.0 => "critical".to_string(),
-                s if s >= 7.0 => "high".to_string(),
-                s if s >= 4.0 => "medium".to_string(),
-                _ => "low".to_string(),
-            }
-        } else {
-            // CVSSスコアがない場合はデフォルト
-            "medium".to_string()
+            let level = match cvss::severity(score) {
+                // 影響なし（0.0）は low として扱う
+                "none" => "low",
+                level => level,
+            };
+            return (level.to_string(), SeveritySource::Cvss);
+        }
+
+        if let Some(level) = self
+            .database_specific
+            .as_ref()
+            .and_then(|d| d.get("severity"))
+            .and_then(normalize_severity_label)
+        {
+            return (level.to_string(), SeveritySource::DatabaseSpecific);
         }
+
+        // 複数の affected エントリで異なる場合は最も深刻なものを採用
+        let ecosystem_level = self
+            .affected
+            .iter()
+            .filter_map(|a| a.ecosystem_specific.as_ref()?.get("severity"))
+            .filter_map(normalize_severity_label)
+            .max_by_key(|level| severity_order(level));
+        if let Some(level) = ecosystem_level {
+            return (level.to_string(), SeveritySource::EcosystemSpecific);
+        }
+
+        ("unknown".to_string(), SeveritySource::Unknown)
     }
 
     /// 修正バージョンを取得
     pub fn fixed_versions(&self) -> Vec<String> {
         let mut fixed = Vec::new();
         for affected in &self.affected {
-            for range in &affected.ranges {
-                for event in &range.events {
-                    if let Some(ref v) = event.fixed {
-                        if !fixed.contains(v) {
-                            fixed.push(v.clone());
-                        }
-                    }
+            for v in affected.fixed_versions() {
+                if !fixed.contains(&v) {
+                    fixed.push(v);
                 }
             }
         }
@@ -287,36 +405,11 @@ impl OsvVulnerability {
 
     /// 影響を受けるバージョン範囲を文字列で取得
     pub fn affected_versions_string(&self) -> String {
-        let mut versions = Vec::new();
-        for affected in &self.affected {
-            // 明示的なバージョンリスト
-            if !affected.versions.is_empty() {
-                versions.extend(affected.versions.iter().cloned());
-            }
-            // 範囲指定
-            for range in &affected.ranges {
-                let mut introduced = None;
-                let mut fixed = None;
-                
-                for event in &range.events {
-                    if event.introduced.is_some() {
-                        introduced = event.introduced.clone();
-                    }
-                    if event.fixed.is_some() {
-                        fixed = event.fixed.clone();
-                    }
-                }
-                
-                if let Some(i) = introduced {
-                    let range_str = if let Some(f) = fixed {
-                        format!(">= {}, < {}", i, f)
-                    } else {
-                        format!(">= {}", i)
-                    };
-                    versions.push(range_str);
-                }
-            }
-        }
+        let versions: Vec<String> = self
+            .affected
+            .iter()
+            .flat_map(|affected| affected.version_intervals())
+            .collect();
         if versions.is_empty() {
             "不明".to_string()
         } else {
@@ -345,6 +438,89 @@ impl OsvVulnerability {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_affected_versions_string() {
+        let vuln: OsvVulnerability = serde_json::from_str(
+            r#"{"id": "PYSEC-x", "affected": [{
+                "package": {"ecosystem": "PyPI", "name": "django"},
+                "ranges": [{"type": "ECOSYSTEM", "events": [
+                    {"introduced": "3.2"}, {"fixed": "3.2.19"},
+                    {"introduced": "4.0"}, {"last_affected": "4.1.7"},
+                    {"introduced": "4.2"}
+                ]}]
+            }]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vuln.affected_versions_string(),
+            ">= 3.2, < 3.2.19; >= 4.0, <= 4.1.7; >= 4.2"
+        );
+    }
+
+    #[test]
+    fn test_cvss_from_vectors() {
+        let vuln: OsvVulnerability = serde_json::from_str(
+            r#"{"id": "GHSA-x", "severity": [
+                {"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"},
+                {"type": "CVSS_V4", "score": "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N"}
+            ]}"#,
+        )
+        .unwrap();
+        let cvss = vuln.cvss().unwrap();
+        assert_eq!(cvss.version, cvss::CvssVersion::V4_0);
+        assert_eq!(vuln.cvss_score(), Some(9.3));
+        assert_eq!(vuln.resolve_severity().0, "critical");
+
+        let plain: OsvVulnerability = serde_json::from_str(
+            r#"{"id": "OSV-x", "severity": [{"type": "CVSS_V3", "score": "5.3"}]}"#,
+        )
+        .unwrap();
+        assert!(plain.cvss().is_none());
+        assert_eq!(plain.cvss_score(), Some(5.3));
+        assert_eq!(plain.resolve_severity().0, "medium");
+    }
+
+    #[test]
+    fn test_resolve_severity_fallbacks() {
+        let ghsa: OsvVulnerability = serde_json::from_str(
+            r#"{"id": "GHSA-x", "database_specific": {"severity": "MODERATE"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ghsa.resolve_severity(),
+            ("medium".to_string(), SeveritySource::DatabaseSpecific)
+        );
+
+        let ecosystem: OsvVulnerability = serde_json::from_str(
+            r#"{"id": "A-x", "affected": [
+                {"package": {"ecosystem": "Android", "name": "a"}, "ecosystem_specific": {"severity": "High"}},
+                {"package": {"ecosystem": "Android", "name": "b"}, "ecosystem_specific": {"severity": "Critical"}}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ecosystem.resolve_severity(),
+            ("critical".to_string(), SeveritySource::EcosystemSpecific)
+        );
+
+        // CVSS があればデータベース固有の表記より優先
+        let both: OsvVulnerability = serde_json::from_str(
+            r#"{"id": "GHSA-y", "database_specific": {"severity": "LOW"},
+                "severity": [{"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            both.resolve_severity(),
+            ("critical".to_string(), SeveritySource::Cvss)
+        );
+
+        let none: OsvVulnerability = serde_json::from_str(r#"{"id": "OSV-z"}"#).unwrap();
+        assert_eq!(
+            none.resolve_severity(),
+            ("unknown".to_string(), SeveritySource::Unknown)
+        );
+    }
+
     #[tokio::test]
     async fn test_query_package() {
         let client = OsvClient::new();
@@ -0,0 +1,88 @@
+//! OSV データベースのエクスポート（all.zip）の読み込み
+//! https://google.github.io/osv.dev/data/#data-dumps
+//! ネットワークに出られない環境向けに、ローカルのアーカイブから OSV エントリを読み出す
+
+use super::osv::{OsvError, OsvVulnerability};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// アーカイブ内の各 OSV エントリ（*.json）を順に読み出す
+/// 解析できなかったエントリ数を返す（コールバックがエラーを返した時点で中断）
+pub fn for_each_entry<E: From<OsvError>>(
+    path: &Path,
+    mut f: impl FnMut(OsvVulnerability) -> Result<(), E>,
+) -> Result<usize, E> {
+    let file = File::open(path).map_err(|e| OsvError::Io(e.to_string()))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| OsvError::Parse(e.to_string()))?;
+
+    let mut skipped = 0;
+    let mut buf = String::new();
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| OsvError::Parse(e.to_string()))?;
+        if !entry.is_file() || !entry.name().ends_with(".json") {
+            continue;
+        }
+
+        buf.clear();
+        if let Err(e) = entry.read_to_string(&mut buf) {
+            eprintln!("Warning: Failed to read {}: {}", entry.name(), e);
+            skipped += 1;
+            continue;
+        }
+        match serde_json::from_str::<OsvVulnerability>(&buf) {
+            Ok(vuln) => f(vuln)?,
+            Err(e) => {
+                eprintln!("Warning: Failed to parse {}: {}", entry.name(), e);
+                skipped += 1;
+            }
+        }
+    }
+
+    Ok(skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn test_for_each_entry() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        {
+            let mut zip = zip::ZipWriter::new(file.reopen().unwrap());
+            let options = SimpleFileOptions::default();
+            zip.start_file("GHSA-aaaa.json", options).unwrap();
+            zip.write_all(br#"{"id": "GHSA-aaaa", "affected": []}"#)
+                .unwrap();
+            zip.start_file("broken.json", options).unwrap();
+            zip.write_all(b"{").unwrap();
+            zip.start_file("README.txt", options).unwrap();
+            zip.write_all(b"not an entry").unwrap();
+            zip.finish().unwrap();
+        }
+
+        let mut ids = Vec::new();
+        let skipped = for_each_entry(file.path(), |v| {
+            ids.push(v.id);
+            Ok::<(), OsvError>(())
+        })
+        .unwrap();
+        assert_eq!(ids, vec!["GHSA-aaaa"]);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn test_not_a_zip() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"plain text").unwrap();
+        assert!(matches!(
+            for_each_entry::<OsvError>(file.path(), |_| Ok(())),
+            Err(OsvError::Parse(_))
+        ));
+    }
+}
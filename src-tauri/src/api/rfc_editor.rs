@@ -84,7 +84,7 @@ impl RfcEditorClient {
     
     /// Fetch RFC Index XML and parse all RFC entries
     pub async fn fetch_rfc_index(&self) -> Result<Vec<RfcEntry>, RfcEditorError> {
-        println!("Fetching RFC index from {}...", RFC_INDEX_URL);
+        eprintln!("Fetching RFC index from {}...", RFC_INDEX_URL);
         
         let response = self.client
             .get(RFC_INDEX_URL)
@@ -92,10 +92,10 @@ impl RfcEditorClient {
             .await?;
         
         let xml_text = response.text().await?;
-        println!("Downloaded {} bytes of XML", xml_text.len());
+        eprintln!("Downloaded {} bytes of XML", xml_text.len());
         
         let entries = parse_rfc_index(&xml_text)?;
-        println!("Parsed {} RFC entries", entries.len());
+        eprintln!("Parsed {} RFC entries", entries.len());
         
         Ok(entries)
     }
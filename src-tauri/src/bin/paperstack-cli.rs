@@ -0,0 +1,6 @@
+//! Headless CLI sharing the library crate with the desktop app
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    std::process::exit(paperstack_lib::cli::run(args))
+}
@@ -0,0 +1,814 @@
+//! ヘッドレス CLI
+//! Webview を起動せずに、デスクトップアプリと同じ db / api / scanner モジュールを利用する
+
+use crate::api::rfc_editor::RfcEditorClient;
+use crate::commands::{self, rfc_commands, vuln_commands};
+use crate::db::{self, models::ScanResult, rfc_queries};
+use crate::report::diff;
+use crate::report::gate::{self, GatePolicy};
+use crate::report::remediation;
+use crate::report::sarif;
+use crate::report::sbom::SbomFormat;
+use crate::scanner::{DevFindings, ScanOptions};
+use crate::version::VersionMatch;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// デスクトップアプリの識別子（tauri.conf.json の identifier と一致させる）
+const APP_IDENTIFIER: &str = "com.paperstack.app";
+
+const USAGE: &str = "Usage: paperstack-cli [--db <path>] <command>
+
+Commands:
+  scan <path> [--json]       Scan a project directory, or a CycloneDX/SPDX JSON SBOM, for vulnerable dependencies
+       [--fail-on <severity>] Exit with status 3 if a finding is at or above the severity
+       [--cvss-floor <score>] Exit with status 3 if a finding has a CVSS score at or above the floor
+       [--sarif <file>]       Also write the findings as a SARIF 2.1.0 log
+       [--depth <n>]          How deep to look for sub-projects (default: 4, 0 = top level only)
+       [--ignore <patterns>]  Comma-separated directories to skip, in addition to
+                              node_modules, target, vendor and .git (* and ? allowed)
+       [--offline]            Match against the imported OSV database instead of the OSV API
+       [--dev <mode>]         Findings in dev-only dependencies: include (default),
+                              deprioritize (listed last, still counted by the gate) or exclude
+  history [<path>] [--json]  List saved scan runs, newest first (optionally for one directory)
+       [--limit <n>]          Number of runs to show (default: 20)
+  diff [<base>] <target>     Compare the findings of two scan runs (new, fixed, still present);
+       [--json]               <base> defaults to the previous run of the same directory
+  sbom <dir>                 Write an SBOM of the dependencies found in a directory
+       [--format <format>]    cyclonedx (default) or spdx
+       [--output <file>]      Output file (default: stdout)
+  osv import <all.zip>       Import an OSV database export (e.g. npm/all.zip) for offline scans
+  osv prune [--days <n>]     Delete cached advisories fetched more than <n> days ago (default: 30)
+  rfc fetch                  Fetch the RFC index into the local database
+  rfc show <number> [--text] Show a cached RFC (--text downloads the full text)
+  papers fetch               Fetch the latest papers from arXiv
+  help                       Show this message
+
+Options:
+  --db <path>                Database file (default: $PAPERSTACK_DB or the desktop app's papers.db)";
+
+/// 終了コード
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_ERROR: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+pub const EXIT_GATE_FAILED: i32 = 3;
+
+/// 値を取るオプション
+const VALUE_OPTIONS: &[&str] = &[
+    "--db",
+    "--fail-on",
+    "--cvss-floor",
+    "--sarif",
+    "--format",
+    "--output",
+    "--depth",
+    "--ignore",
+    "--limit",
+    "--dev",
+    "--days",
+];
+
+/// CLI サブコマンド
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Scan {
+        dir: String,
+        json: bool,
+        gate: Option<GatePolicy>,
+        sarif: Option<String>,
+        options: ScanOptions,
+    },
+    Sbom {
+        dir: String,
+        format: SbomFormat,
+        output: Option<String>,
+    },
+    History {
+        dir: Option<String>,
+        limit: i32,
+        json: bool,
+    },
+    Diff {
+        base: Option<i64>,
+        target: i64,
+        json: bool,
+    },
+    OsvImport {
+        archive: String,
+    },
+    OsvPrune {
+        days: i32,
+    },
+    RfcFetch,
+    RfcShow {
+        number: i32,
+        text: bool,
+    },
+    PapersFetch,
+    Help,
+}
+
+/// パース済みの CLI 引数
+#[derive(Debug, Clone, PartialEq)]
+struct CliArgs {
+    db_path: Option<String>,
+    command: Command,
+}
+
+/// CLI エントリーポイント（終了コードを返す）
+pub fn run(args: Vec<String>) -> i32 {
+    let cli_args = match parse_args(&args) {
+        Ok(a) => a,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return EXIT_USAGE;
+        }
+    };
+
+    if cli_args.command == Command::Help {
+        println!("{}", USAGE);
+        return EXIT_OK;
+    }
+
+    let db_path = match resolve_db_path(cli_args.db_path) {
+        Ok(p) => p,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return EXIT_ERROR;
+        }
+    };
+
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("error: failed to start async runtime: {}", e);
+            return EXIT_ERROR;
+        }
+    };
+
+    match runtime.block_on(execute(&db_path, cli_args.command)) {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            EXIT_ERROR
+        }
+    }
+}
+
+/// サブコマンドを実行
+async fn execute(db_path: &str, command: Command) -> Result<i32, String> {
+    match command {
+        Command::Scan {
+            dir,
+            json,
+            gate,
+            sarif,
+            options,
+        } => {
+            let result = vuln_commands::run_scan(db_path, dir, &options)
+                .await
+                .map_err(|e| e.message)?;
+            if let Some(sarif_path) = sarif {
+                let log = sarif::to_sarif_json(&result).map_err(|e| e.to_string())?;
+                std::fs::write(&sarif_path, log)
+                    .map_err(|e| format!("failed to write {}: {}", sarif_path, e))?;
+            }
+            if json {
+                let out = serde_json::to_string_pretty(&result).map_err(|e| e.to_string())?;
+                println!("{}", out);
+            } else {
+                print_scan_result(&result);
+            }
+
+            let Some(policy) = gate else {
+                return Ok(EXIT_OK);
+            };
+            let gate_result = gate::evaluate(&result, &policy);
+            // JSON 出力を壊さないようにサマリーは stderr へ
+            let summary = gate::format_summary(&gate_result, &policy);
+            if json {
+                eprintln!("{}", summary);
+            } else {
+                println!("\n{}", summary);
+            }
+            Ok(if gate_result.passed {
+                EXIT_OK
+            } else {
+                EXIT_GATE_FAILED
+            })
+        }
+        Command::Sbom {
+            dir,
+            format,
+            output,
+        } => {
+            let json = vuln_commands::build_sbom(&dir, format).map_err(|e| e.message)?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, json)
+                        .map_err(|e| format!("failed to write {}: {}", path, e))?;
+                }
+                None => println!("{}", json),
+            }
+            Ok(EXIT_OK)
+        }
+        Command::History { dir, limit, json } => {
+            let conn = db::get_connection(db_path).map_err(|e| e.to_string())?;
+            let dir = dir.map(|d| vuln_commands::normalize_run_directory(&d));
+            let runs = db::vuln_queries::get_scan_runs(&conn, dir.as_deref(), limit)
+                .map_err(|e| e.to_string())?;
+            if json {
+                let out = serde_json::to_string_pretty(&runs).map_err(|e| e.to_string())?;
+                println!("{}", out);
+            } else if runs.is_empty() {
+                println!("No scan runs saved yet.");
+            } else {
+                for run in &runs {
+                    println!(
+                        "  #{:<5} {}  {} packages, {} vulnerabilities  {}",
+                        run.id, run.scanned_at, run.total_packages, run.vuln_count, run.directory
+                    );
+                }
+            }
+            Ok(EXIT_OK)
+        }
+        Command::Diff { base, target, json } => {
+            let conn = db::get_connection(db_path).map_err(|e| e.to_string())?;
+            let diff = vuln_commands::load_scan_diff(&conn, base, target).map_err(|e| e.message)?;
+            if json {
+                let out = serde_json::to_string_pretty(&diff).map_err(|e| e.to_string())?;
+                println!("{}", out);
+            } else {
+                println!("{}", diff::format_diff(&diff));
+            }
+            Ok(EXIT_OK)
+        }
+        Command::OsvImport { archive } => {
+            let summary = vuln_commands::import_osv_archive_file(db_path, Path::new(&archive))
+                .map_err(|e| e.message)?;
+            println!(
+                "{} advisories imported into {} ({})",
+                summary.imported,
+                db_path,
+                summary.ecosystems.join(", ")
+            );
+            if summary.skipped > 0 {
+                eprintln!("warning: {} entries could not be parsed", summary.skipped);
+            }
+            Ok(EXIT_OK)
+        }
+        Command::OsvPrune { days } => {
+            let conn = db::get_connection(db_path).map_err(|e| e.to_string())?;
+            let deleted = db::vuln_queries::delete_old_vulnerabilities(&conn, days)
+                .map_err(|e| e.to_string())?;
+            println!(
+                "{} advisories older than {} days deleted from {}",
+                deleted, days, db_path
+            );
+            Ok(EXIT_OK)
+        }
+        Command::RfcFetch => {
+            let count = rfc_commands::fetch_and_store_rfcs(db_path)
+                .await
+                .map_err(|e| e.message)?;
+            println!("{} RFCs stored in {}", count, db_path);
+            Ok(EXIT_OK)
+        }
+        Command::RfcShow { number, text } => {
+            let conn = db::get_connection(db_path).map_err(|e| e.to_string())?;
+            let rfc_id = format!("RFC{}", number);
+            let rfc = rfc_queries::get_rfc_by_id(&conn, &rfc_id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("{} is not cached; run `rfc fetch` first", rfc_id))?;
+
+            println!("{}: {}", rfc.id, rfc.title);
+            if !rfc.status.is_empty() {
+                println!("Status:    {}", rfc.status);
+            }
+            if let Some(ref date) = rfc.published_date {
+                println!("Published: {}", date);
+            }
+            if !rfc.authors.is_empty() {
+                println!("Authors:   {}", rfc.authors.join(", "));
+            }
+            if let Some(ref abstract_text) = rfc.r#abstract {
+                println!("\n{}", abstract_text);
+            }
+            if text {
+                let content = RfcEditorClient::new()
+                    .fetch_rfc_text(number)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                println!("\n{}", content);
+            }
+            Ok(EXIT_OK)
+        }
+        Command::PapersFetch => {
+            let count = commands::fetch_and_store_papers(db_path)
+                .await
+                .map_err(|e| e.message)?;
+            println!("{} new papers stored in {}", count, db_path);
+            Ok(EXIT_OK)
+        }
+        Command::Help => Ok(EXIT_OK),
+    }
+}
+
+/// スキャン結果を人間向けに出力
+fn print_scan_result(result: &ScanResult) {
+    println!(
+        "Scanned {} ({} packages, ecosystems: {})",
+        result.directory,
+        result.total_packages,
+        result.ecosystems.join(", ")
+    );
+    if result.projects.len() > 1 {
+        for project in &result.projects {
+            println!(
+                "  {}: {} packages, {} vulnerabilities ({})",
+                project.path,
+                project.total_packages,
+                project.vuln_count,
+                project.ecosystems.join(", ")
+            );
+        }
+    }
+    for warning in &result.warnings {
+        println!("  skipped: {}", warning);
+    }
+    if let Some(run_id) = result.run_id {
+        println!(
+            "Saved as scan run #{} (compare with `diff {}`)",
+            run_id, run_id
+        );
+    }
+
+    if result.vulnerabilities.is_empty() {
+        println!("No known vulnerabilities found.");
+        return;
+    }
+
+    for m in &result.vulnerabilities {
+        let fixed = m
+            .vulnerability
+            .fixed_versions
+            .as_deref()
+            .map(|f| format!(" (fixed in {})", f))
+            .unwrap_or_default();
+        let range_note = match m.version_match {
+            VersionMatch::NotAffected => " [outside affected range?]",
+            // バージョンが固定されておらず、指定範囲のどこかが影響を受ける
+            VersionMatch::Unpinned => " [unpinned]",
+            _ => "",
+        };
+        let aliases = if m.vulnerability.aliases.is_empty() {
+            String::new()
+        } else {
+            format!(" (aka {})", m.vulnerability.aliases.join(", "))
+        };
+        let suppressed = m
+            .suppression
+            .as_ref()
+            .map(|s| format!(" [suppressed: {}]", s.reason))
+            .unwrap_or_default();
+        let dev = if m.dev { " [dev]" } else { "" };
+        let project = if m.project.is_empty() || m.project == "." {
+            String::new()
+        } else {
+            format!("{}: ", m.project)
+        };
+        println!(
+            "  [{:<8}] {}{}{} {}@{}{}{}{}{} - {}",
+            m.vulnerability.severity,
+            project,
+            m.vulnerability.id,
+            aliases,
+            m.package_name,
+            m.installed_version,
+            fixed,
+            range_note,
+            dev,
+            suppressed,
+            m.vulnerability.title
+        );
+        // 推移的依存は直接依存からの経路を示す
+        if m.dependency_path.len() > 1 {
+            println!("             via {}", m.dependency_path.join(" > "));
+        }
+    }
+    let suppressed = result
+        .vulnerabilities
+        .iter()
+        .filter(|m| m.suppression.is_some())
+        .count();
+    if suppressed > 0 {
+        println!(
+            "{} vulnerabilities found ({} suppressed).",
+            result.vulnerabilities.len(),
+            suppressed
+        );
+    } else {
+        println!("{} vulnerabilities found.", result.vulnerabilities.len());
+    }
+
+    if !result.remediation.is_empty() {
+        println!("\nUpgrade plan:");
+        println!("{}", remediation::format_plan(&result.remediation));
+    }
+}
+
+/// 引数をパース
+fn parse_args(args: &[String]) -> Result<CliArgs, String> {
+    let mut positional: Vec<&str> = Vec::new();
+    let mut flags: Vec<&str> = Vec::new();
+    let mut options: HashMap<&str, &str> = HashMap::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            a if VALUE_OPTIONS.contains(&a) => {
+                let value = iter.next().ok_or(format!("{} requires a value", a))?;
+                options.insert(a, value.as_str());
+            }
+            "-h" | "--help" => flags.push("--help"),
+            a if a.starts_with("--") => flags.push(a),
+            a => positional.push(a),
+        }
+    }
+
+    let command = if flags.contains(&"--help") {
+        Command::Help
+    } else {
+        parse_command(&positional, &flags, &options)?
+    };
+
+    Ok(CliArgs {
+        db_path: options.get("--db").map(|p| p.to_string()),
+        command,
+    })
+}
+
+fn parse_command(
+    positional: &[&str],
+    flags: &[&str],
+    options: &HashMap<&str, &str>,
+) -> Result<Command, String> {
+    let allow = |allowed: &[&str]| -> Result<(), String> {
+        let unknown = flags
+            .iter()
+            .chain(options.keys())
+            .find(|f| **f != "--db" && !allowed.contains(f));
+        match unknown {
+            Some(f) => Err(format!("unknown option: {}", f)),
+            None => Ok(()),
+        }
+    };
+
+    match positional {
+        [] | ["help"] => Ok(Command::Help),
+        ["scan", dir] => {
+            allow(&[
+                "--json",
+                "--fail-on",
+                "--cvss-floor",
+                "--sarif",
+                "--depth",
+                "--ignore",
+                "--offline",
+                "--dev",
+            ])?;
+            let min_cvss = options
+                .get("--cvss-floor")
+                .map(|v| {
+                    v.parse::<f64>()
+                        .map_err(|_| format!("invalid CVSS score: {}", v))
+                })
+                .transpose()?;
+            let min_severity = options.get("--fail-on").copied();
+            let gate = if min_severity.is_some() || min_cvss.is_some() {
+                Some(GatePolicy::new(min_severity, min_cvss)?)
+            } else {
+                None
+            };
+            let max_depth = options
+                .get("--depth")
+                .map(|v| {
+                    v.parse::<usize>()
+                        .map_err(|_| format!("invalid depth: {}", v))
+                })
+                .transpose()?;
+            let ignore: Vec<String> = options
+                .get("--ignore")
+                .map(|v| v.split(',').map(|p| p.to_string()).collect())
+                .unwrap_or_default();
+            let mut scan_options = ScanOptions::new(max_depth, &ignore);
+            scan_options.offline = flags.contains(&"--offline");
+            // 照会に失敗したまま検出なしとしてゲートを通さない
+            scan_options.strict = gate.is_some();
+            if let Some(mode) = options.get("--dev") {
+                scan_options.dev_findings = DevFindings::parse(mode).ok_or(format!(
+                    "invalid --dev mode: {} (expected include, deprioritize or exclude)",
+                    mode
+                ))?;
+            }
+            Ok(Command::Scan {
+                dir: dir.to_string(),
+                json: flags.contains(&"--json"),
+                gate,
+                sarif: options.get("--sarif").map(|p| p.to_string()),
+                options: scan_options,
+            })
+        }
+        ["scan"] => Err("scan requires a directory or an SBOM file".to_string()),
+        ["sbom", dir] => {
+            allow(&["--format", "--output"])?;
+            let format = match options.get("--format") {
+                Some(f) => SbomFormat::parse(f).ok_or(format!("unknown SBOM format: {}", f))?,
+                None => SbomFormat::CycloneDx,
+            };
+            Ok(Command::Sbom {
+                dir: dir.to_string(),
+                format,
+                output: options.get("--output").map(|p| p.to_string()),
+            })
+        }
+        ["sbom"] => Err("sbom requires a directory".to_string()),
+        ["history"] | ["history", _] => {
+            allow(&["--json", "--limit"])?;
+            let limit = options
+                .get("--limit")
+                .map(|v| {
+                    v.parse::<i32>()
+                        .map_err(|_| format!("invalid limit: {}", v))
+                })
+                .transpose()?;
+            Ok(Command::History {
+                dir: positional.get(1).map(|d| d.to_string()),
+                limit: limit.unwrap_or(20),
+                json: flags.contains(&"--json"),
+            })
+        }
+        ["diff", runs @ ..] if !runs.is_empty() && runs.len() <= 2 => {
+            allow(&["--json"])?;
+            let ids = runs
+                .iter()
+                .map(|r| {
+                    r.trim_start_matches('#')
+                        .parse::<i64>()
+                        .map_err(|_| format!("invalid scan run: {}", r))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Command::Diff {
+                base: if ids.len() == 2 { Some(ids[0]) } else { None },
+                target: ids[ids.len() - 1],
+                json: flags.contains(&"--json"),
+            })
+        }
+        ["diff"] => Err("diff requires a scan run".to_string()),
+        ["osv", "import", archive] => {
+            allow(&[])?;
+            Ok(Command::OsvImport {
+                archive: archive.to_string(),
+            })
+        }
+        ["osv", "import"] => Err("osv import requires an archive file".to_string()),
+        ["osv", "prune"] => {
+            allow(&["--days"])?;
+            let days = options
+                .get("--days")
+                .map(|v| {
+                    v.parse::<i32>()
+                        .ok()
+                        .filter(|d| *d >= 0)
+                        .ok_or(format!("invalid number of days: {}", v))
+                })
+                .transpose()?;
+            Ok(Command::OsvPrune {
+                days: days.unwrap_or(30),
+            })
+        }
+        ["rfc", "fetch"] => {
+            allow(&[])?;
+            Ok(Command::RfcFetch)
+        }
+        ["rfc", "show", number] => {
+            allow(&["--text"])?;
+            let number = number
+                .trim_start_matches("RFC")
+                .trim_start_matches("rfc")
+                .parse::<i32>()
+                .map_err(|_| format!("invalid RFC number: {}", number))?;
+            Ok(Command::RfcShow {
+                number,
+                text: flags.contains(&"--text"),
+            })
+        }
+        ["papers", "fetch"] => {
+            allow(&[])?;
+            Ok(Command::PapersFetch)
+        }
+        other => Err(format!("unknown command: {}", other.join(" "))),
+    }
+}
+
+/// データベースのパスを決定し、テーブルを初期化
+/// 優先順位: --db > PAPERSTACK_DB > デスクトップアプリのデータディレクトリ
+fn resolve_db_path(explicit: Option<String>) -> Result<String, String> {
+    let path = match explicit.or_else(|| std::env::var("PAPERSTACK_DB").ok()) {
+        Some(p) => PathBuf::from(p),
+        None => {
+            // Tauri の app_data_dir() と同じ場所（<data_dir>/<identifier>/papers.db）
+            let data_dir = dirs::data_dir()
+                .ok_or("could not determine the application data directory; use --db")?;
+            data_dir.join(APP_IDENTIFIER).join("papers.db")
+        }
+    };
+
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+    }
+
+    let path_str = path.to_string_lossy().to_string();
+    db::init_db(&path_str).map_err(|e| e.to_string())?;
+    Ok(path_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_scan() {
+        let parsed = parse_args(&args("--db /tmp/x.db scan ./project --json")).unwrap();
+        assert_eq!(parsed.db_path, Some("/tmp/x.db".to_string()));
+        assert_eq!(
+            parsed.command,
+            Command::Scan {
+                dir: "./project".to_string(),
+                json: true,
+                gate: None,
+                sarif: None,
+                options: ScanOptions::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_scan_walk_options() {
+        let parsed = parse_args(&args("scan . --depth 2 --ignore dist,examples/*")).unwrap();
+        match parsed.command {
+            Command::Scan { options, .. } => {
+                assert_eq!(options.max_depth, 2);
+                assert!(options.ignore.contains(&"node_modules".to_string()));
+                assert!(options.ignore.contains(&"dist".to_string()));
+                assert!(options.ignore.contains(&"examples/*".to_string()));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert!(parse_args(&args("scan . --depth -1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_offline() {
+        match parse_args(&args("scan . --offline")).unwrap().command {
+            Command::Scan { options, .. } => assert!(options.offline),
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert_eq!(
+            parse_args(&args("osv import npm-all.zip")).unwrap().command,
+            Command::OsvImport {
+                archive: "npm-all.zip".to_string()
+            }
+        );
+        assert!(parse_args(&args("osv import")).is_err());
+        assert_eq!(
+            parse_args(&args("osv prune")).unwrap().command,
+            Command::OsvPrune { days: 30 }
+        );
+        assert_eq!(
+            parse_args(&args("osv prune --days 7")).unwrap().command,
+            Command::OsvPrune { days: 7 }
+        );
+        assert!(parse_args(&args("osv prune --days -1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_dev_mode() {
+        match parse_args(&args("scan . --dev deprioritize"))
+            .unwrap()
+            .command
+        {
+            Command::Scan { options, .. } => {
+                assert_eq!(options.dev_findings, DevFindings::Deprioritize)
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        match parse_args(&args("scan .")).unwrap().command {
+            Command::Scan { options, .. } => assert_eq!(options.dev_findings, DevFindings::Include),
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert!(parse_args(&args("scan . --dev skip")).is_err());
+    }
+
+    #[test]
+    fn test_parse_scan_gate() {
+        let parsed = parse_args(&args("scan . --fail-on HIGH --cvss-floor 7.5")).unwrap();
+        match parsed.command {
+            Command::Scan {
+                gate: Some(policy),
+                options,
+                ..
+            } => {
+                assert_eq!(policy.min_severity, Some("high".to_string()));
+                assert_eq!(policy.min_cvss, Some(7.5));
+                assert!(options.strict);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert!(parse_args(&args("scan . --fail-on severe")).is_err());
+        assert!(parse_args(&args("scan . --cvss-floor abc")).is_err());
+        assert!(parse_args(&args("rfc fetch --fail-on high")).is_err());
+    }
+
+    #[test]
+    fn test_parse_sbom() {
+        assert_eq!(
+            parse_args(&args("sbom . --format spdx --output bom.json"))
+                .unwrap()
+                .command,
+            Command::Sbom {
+                dir: ".".to_string(),
+                format: SbomFormat::Spdx,
+                output: Some("bom.json".to_string()),
+            }
+        );
+        assert!(parse_args(&args("sbom . --format xml")).is_err());
+    }
+
+    #[test]
+    fn test_parse_history_and_diff() {
+        assert_eq!(
+            parse_args(&args("history . --limit 5")).unwrap().command,
+            Command::History {
+                dir: Some(".".to_string()),
+                limit: 5,
+                json: false,
+            }
+        );
+        assert_eq!(
+            parse_args(&args("diff 12 --json")).unwrap().command,
+            Command::Diff {
+                base: None,
+                target: 12,
+                json: true,
+            }
+        );
+        assert_eq!(
+            parse_args(&args("diff #3 #7")).unwrap().command,
+            Command::Diff {
+                base: Some(3),
+                target: 7,
+                json: false,
+            }
+        );
+        assert!(parse_args(&args("diff")).is_err());
+        assert!(parse_args(&args("diff a b")).is_err());
+        assert!(parse_args(&args("diff 1 2 3")).is_err());
+    }
+
+    #[test]
+    fn test_parse_rfc_commands() {
+        assert_eq!(
+            parse_args(&args("rfc fetch")).unwrap().command,
+            Command::RfcFetch
+        );
+        assert_eq!(
+            parse_args(&args("rfc show RFC9114 --text"))
+                .unwrap()
+                .command,
+            Command::RfcShow {
+                number: 9114,
+                text: true
+            }
+        );
+        assert!(parse_args(&args("rfc show abc")).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_args(&args("scan")).is_err());
+        assert!(parse_args(&args("papers fetch --json")).is_err());
+        assert!(parse_args(&args("unknown")).is_err());
+        assert!(parse_args(&args("--db")).is_err());
+        assert_eq!(parse_args(&args("")).unwrap().command, Command::Help);
+    }
+}
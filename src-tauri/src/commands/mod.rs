@@ -3,10 +3,18 @@
 pub mod rfc_commands;
 pub mod vuln_commands;
 
-use crate::api::{arxiv::ArxivClient, groq::GroqClient, translate::TranslateClient};
-use crate::db::{self, models::{Category, Paper}};
+#[cfg(feature = "desktop")]
+use crate::api::groq::GroqClient;
+use crate::api::{arxiv::ArxivClient, translate::TranslateClient};
+#[cfg(feature = "desktop")]
+use crate::db::models::Category;
+use crate::db::{self, models::Paper};
+#[cfg(feature = "desktop")]
 use crate::AppState;
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "desktop")]
+use serde::Deserialize;
+use serde::Serialize;
+#[cfg(feature = "desktop")]
 use tauri::State;
 
 #[derive(Debug, Serialize)]
@@ -30,6 +38,7 @@ impl From<crate::api::arxiv::ArxivError> for CommandError {
     }
 }
 
+#[cfg(feature = "desktop")]
 impl From<crate::api::groq::GroqError> for CommandError {
     fn from(e: crate::api::groq::GroqError) -> Self {
         CommandError {
@@ -47,6 +56,7 @@ impl From<crate::api::translate::TranslateError> for CommandError {
 }
 
 /// Get papers from database, optionally filtered by category
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_papers(
     state: State<'_, AppState>,
@@ -60,12 +70,23 @@ pub async fn get_papers(
 
 /// Fetch papers from arXiv API and save to database
 /// Only fetches new papers that don't already exist in the database
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn fetch_papers(
     state: State<'_, AppState>,
     _tasks: Vec<String>,
 ) -> Result<Vec<Paper>, CommandError> {
     let db_path = &state.db_path;
+    fetch_and_store_papers(db_path).await?;
+
+    // Return updated papers from database
+    let papers = db::get_papers_from_db(db_path, None, Some(100))?;
+    Ok(papers)
+}
+
+/// Fetch new papers from arXiv, save them and translate their titles
+/// Returns the number of newly added papers (shared by the Tauri command and the CLI)
+pub async fn fetch_and_store_papers(db_path: &str) -> Result<usize, CommandError> {
     let arxiv_client = ArxivClient::new();
     let translate_client = TranslateClient::new();
     
@@ -110,9 +131,12 @@ pub async fn fetch_papers(
         papers_to_translate.push((arxiv_paper.id.clone(), arxiv_paper.title.clone()));
         new_paper_count += 1;
     }
-    
-    println!("Fetch complete: {} new papers, {} skipped (already exist)", new_paper_count, skipped_count);
-    
+
+    eprintln!(
+        "Fetch complete: {} new papers, {} skipped (already exist)",
+        new_paper_count, skipped_count
+    );
+
     // Translate titles for NEW papers only (with rate limiting built into the client)
     for (paper_id, title) in &papers_to_translate {
         match translate_client.translate_to_japanese(title).await {
@@ -126,12 +150,11 @@ pub async fn fetch_papers(
         }
     }
     
-    // Return updated papers from database
-    let papers = db::get_papers_from_db(db_path, None, Some(100))?;
-    Ok(papers)
+    Ok(new_paper_count)
 }
 
 /// Generate Japanese summary for a paper using Groq API
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn generate_summary(
     state: State<'_, AppState>,
@@ -184,12 +207,14 @@ pub async fn generate_summary(
 }
 
 /// Get all available categories
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub fn get_categories() -> Vec<Category> {
     Category::all_categories()
 }
 
 /// Settings response for frontend
+#[cfg(feature = "desktop")]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SettingsResponse {
     pub groq_api_key: Option<String>,
@@ -197,6 +222,7 @@ pub struct SettingsResponse {
 }
 
 /// Get current settings
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub fn get_settings(state: State<'_, AppState>) -> Result<SettingsResponse, CommandError> {
     let settings = state.settings.read().map_err(|e| CommandError {
@@ -220,12 +246,14 @@ pub fn get_settings(state: State<'_, AppState>) -> Result<SettingsResponse, Comm
 }
 
 /// Settings input from frontend
+#[cfg(feature = "desktop")]
 #[derive(Debug, Deserialize)]
 pub struct SettingsInput {
     pub groq_api_key: Option<String>,
 }
 
 /// Save settings
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub fn save_settings(
     state: State<'_, AppState>,
@@ -1,11 +1,19 @@
 //! RFC Tauri Commands
 
+#[cfg(feature = "desktop")]
 use crate::api::groq::GroqClient;
 use crate::api::rfc_editor::RfcEditorClient;
-use crate::db::{self, models::{Rfc, RfcFilter, RfcListResponse, RfcBookmark, RfcHistory, RfcCategory, SummaryLevel}};
+#[cfg(feature = "desktop")]
+use crate::db::models::{RfcBookmark, RfcFilter, RfcHistory, RfcListResponse, SummaryLevel};
 use crate::db::rfc_queries;
+use crate::db::{
+    self,
+    models::{Rfc, RfcCategory},
+};
+#[cfg(feature = "desktop")]
 use crate::AppState;
 use serde::Serialize;
+#[cfg(feature = "desktop")]
 use tauri::State;
 
 #[derive(Debug, Serialize)]
@@ -25,6 +33,7 @@ impl From<crate::api::rfc_editor::RfcEditorError> for RfcCommandError {
     }
 }
 
+#[cfg(feature = "desktop")]
 impl From<crate::api::groq::GroqError> for RfcCommandError {
     fn from(e: crate::api::groq::GroqError) -> Self {
         RfcCommandError { message: e.to_string() }
@@ -36,6 +45,7 @@ impl From<crate::api::groq::GroqError> for RfcCommandError {
 // ============================================================================
 
 /// Get RFCs with filtering and pagination
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_rfcs(
     state: State<'_, AppState>,
@@ -54,12 +64,17 @@ pub async fn get_rfcs(
 }
 
 /// Fetch RFCs from RFC Editor and save to database
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn fetch_rfcs(state: State<'_, AppState>) -> Result<i64, RfcCommandError> {
-    let db_path = &state.db_path;
+    fetch_and_store_rfcs(&state.db_path).await
+}
+
+/// Fetch the RFC index and store it (shared by the Tauri command and the CLI)
+pub async fn fetch_and_store_rfcs(db_path: &str) -> Result<i64, RfcCommandError> {
     let client = RfcEditorClient::new();
     
-    println!("Fetching RFC index...");
+    eprintln!("Fetching RFC index...");
     let entries = client.fetch_rfc_index().await?;
     
     let conn = db::get_connection(db_path)?;
@@ -101,7 +116,7 @@ pub async fn fetch_rfcs(state: State<'_, AppState>) -> Result<i64, RfcCommandErr
         count += 1;
     }
     
-    println!("Saved {} RFCs to database", count);
+    eprintln!("Saved {} RFCs to database", count);
     Ok(count)
 }
 
@@ -110,6 +125,7 @@ pub async fn fetch_rfcs(state: State<'_, AppState>) -> Result<i64, RfcCommandErr
 // ============================================================================
 
 /// Get a single RFC by ID
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_rfc_by_id(
     state: State<'_, AppState>,
@@ -123,6 +139,7 @@ pub async fn get_rfc_by_id(
 }
 
 /// Get RFC full text
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_rfc_content(rfc_number: i32) -> Result<String, RfcCommandError> {
     let client = RfcEditorClient::new();
@@ -135,6 +152,7 @@ pub async fn get_rfc_content(rfc_number: i32) -> Result<String, RfcCommandError>
 // ============================================================================
 
 /// Generate RFC summary at specified level
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn generate_rfc_summary(
     state: State<'_, AppState>,
@@ -199,6 +217,7 @@ pub async fn generate_rfc_summary(
 }
 
 /// Generate implementation guide
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn generate_rfc_implementation_guide(
     state: State<'_, AppState>,
@@ -251,6 +270,7 @@ pub async fn generate_rfc_implementation_guide(
 // ============================================================================
 
 /// Translate RFC section
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn translate_rfc_section(
     state: State<'_, AppState>,
@@ -275,6 +295,7 @@ pub async fn translate_rfc_section(
 }
 
 /// Translate RFC abstract and save to database
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn translate_rfc_abstract(
     state: State<'_, AppState>,
@@ -326,6 +347,7 @@ pub async fn translate_rfc_abstract(
 }
 
 /// Translate RFC title and save to database
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn translate_rfc_title(
     state: State<'_, AppState>,
@@ -377,6 +399,7 @@ pub async fn translate_rfc_title(
 // ============================================================================
 
 /// Add RFC bookmark
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn add_rfc_bookmark(
     state: State<'_, AppState>,
@@ -391,6 +414,7 @@ pub async fn add_rfc_bookmark(
 }
 
 /// Remove RFC bookmark
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn remove_rfc_bookmark(
     state: State<'_, AppState>,
@@ -404,6 +428,7 @@ pub async fn remove_rfc_bookmark(
 }
 
 /// Get all RFC bookmarks
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_rfc_bookmarks(
     state: State<'_, AppState>,
@@ -420,6 +445,7 @@ pub async fn get_rfc_bookmarks(
 // ============================================================================
 
 /// Add RFC to history
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn add_rfc_history(
     state: State<'_, AppState>,
@@ -433,6 +459,7 @@ pub async fn add_rfc_history(
 }
 
 /// Get RFC history
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_rfc_history(
     state: State<'_, AppState>,
@@ -450,6 +477,7 @@ pub async fn get_rfc_history(
 // ============================================================================
 
 /// Get all RFC categories
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_rfc_categories(
     state: State<'_, AppState>,
@@ -462,6 +490,7 @@ pub async fn get_rfc_categories(
 }
 
 /// Get RFC count
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_rfc_count(
     state: State<'_, AppState>,
@@ -1,13 +1,34 @@
 //! 脆弱性スキャナー Tauri Commands
 
-use crate::api::osv::{OsvClient, OsvPackage, OsvQueryRequest, OsvVulnerability};
-use crate::db::{self, models::{ScanHistory, ScanResult, VulnFilter, VulnListResponse, VulnMatch, Vulnerability}};
-use crate::scanner::{self, Dependency};
+use crate::api::osv::{OsvClient, OsvError, OsvPackage, OsvQueryRequest, OsvVulnerability};
+use crate::api::osv_archive;
+#[cfg(feature = "desktop")]
+use crate::db::models::{
+    ScanHistory, ScanRun, Suppression, VulnFilter, VulnListResponse, WatchedProject,
+};
+use crate::db::{
+    self,
+    models::{
+        AffectedPackage, OsvImportSummary, ScanDiff, ScanFinding, ScanProject, ScanResult,
+        VulnMatch, Vulnerability,
+    },
+};
+use crate::report::{self, diff, sbom::SbomFormat, suppression};
+use crate::scanner::{self, Dependency, DevFindings, ProjectScan, ScanDependencies, ScanOptions};
+#[cfg(feature = "desktop")]
+use crate::scheduler;
+use crate::version::{self, VersionMatch};
+#[cfg(feature = "desktop")]
 use crate::AppState;
 use super::CommandError;
+use std::collections::HashMap;
 use std::path::Path;
+#[cfg(feature = "desktop")]
 use tauri::State;
 
+/// 1 回の /querybatch で照会する依存関係の数（OSV の上限は 1000 件）
+const BATCH_SIZE: usize = 1000;
+
 impl From<crate::api::osv::OsvError> for CommandError {
     fn from(e: crate::api::osv::OsvError) -> Self {
         CommandError {
@@ -25,6 +46,7 @@ impl From<crate::scanner::ScanError> for CommandError {
 }
 
 /// 脆弱性一覧を取得（キャッシュから）
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_vulnerabilities(
     state: State<'_, AppState>,
@@ -43,6 +65,7 @@ pub async fn get_vulnerabilities(
 }
 
 /// 最新の脆弱性をAPIから取得してキャッシュに保存
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn fetch_vulnerabilities(
     state: State<'_, AppState>,
@@ -76,11 +99,14 @@ pub async fn fetch_vulnerabilities(
             match osv_client.query_package(ecosystem, package, None).await {
                 Ok(response) => {
                     for osv_vuln in response.vulns {
-                        let vuln = convert_osv_vulnerability(&osv_vuln, ecosystem, package);
-                        if let Err(e) = db::vuln_queries::upsert_vulnerability(&conn, &vuln) {
-                            eprintln!("Failed to save vulnerability {}: {}", vuln.id, e);
-                        } else {
-                            total_fetched += 1;
+                        let vuln = convert_osv_vulnerability(&osv_vuln);
+                        match db::vuln_queries::upsert_vulnerability(&conn, &vuln) {
+                            Ok(true) => {
+                                db::vuln_queries::replace_affected(&conn, &osv_vuln)?;
+                                total_fetched += 1;
+                            }
+                            Ok(false) => {}
+                            Err(e) => eprintln!("Failed to save vulnerability {}: {}", vuln.id, e),
                         }
                     }
                 }
@@ -95,12 +121,45 @@ pub async fn fetch_vulnerabilities(
 }
 
 /// ディレクトリをスキャンして脆弱性を検出
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn scan_directory(
     state: State<'_, AppState>,
     path: String,
+    max_depth: Option<usize>,
+    ignore: Option<Vec<String>>,
+    offline: Option<bool>,
+    dev_findings: Option<String>,
+) -> Result<ScanResult, CommandError> {
+    let mut options = ScanOptions::new(max_depth, &ignore.unwrap_or_default());
+    options.offline = offline.unwrap_or(false);
+    if let Some(value) = dev_findings {
+        options.dev_findings = DevFindings::parse(&value).ok_or_else(|| CommandError {
+            message: format!(
+                "開発用の依存関係の扱いが不正です: {}（include / deprioritize / exclude）",
+                value
+            ),
+        })?;
+    }
+    run_scan(&state.db_path, path, &options).await
+}
+
+/// スキャン本体（Tauri コマンドと CLI で共有）
+pub async fn run_scan(
+    db_path: &str,
+    path: String,
+    options: &ScanOptions,
+) -> Result<ScanResult, CommandError> {
+    scan_with_client(&OsvClient::new(), db_path, path, options).await
+}
+
+/// 指定した OSV API クライアントでスキャン
+async fn scan_with_client(
+    osv_client: &OsvClient,
+    db_path: &str,
+    path: String,
+    options: &ScanOptions,
 ) -> Result<ScanResult, CommandError> {
-    let db_path = &state.db_path;
     let dir_path = Path::new(&path);
     
     if !dir_path.exists() {
@@ -109,101 +168,544 @@ pub async fn scan_directory(
         });
     }
     
-    if !dir_path.is_dir() {
-        return Err(CommandError {
-            message: format!("ディレクトリではありません: {}", path),
-        });
-    }
-    
-    // 依存関係をスキャン
-    let scan_results = scanner::scan_directory(dir_path)?;
+    // 依存関係をスキャン（ファイルが指定された場合は CycloneDX / SPDX の SBOM として取り込む）
+    let (projects, mut warnings) = if dir_path.is_file() {
+        let import = scanner::sbom::parse_sbom(dir_path)?;
+        let project = ProjectScan {
+            path: ".".to_string(),
+            results: import.scans,
+        };
+        (vec![project], import.skipped)
+    } else {
+        (scanner::scan_directory(dir_path, options)?, Vec::new())
+    };
+    // パーサーが照会の対象から外したもの（git / path の依存関係など）
+    warnings.extend(
+        projects
+            .iter()
+            .flat_map(|p| &p.results)
+            .flat_map(|r| r.warnings.iter().cloned()),
+    );
     
-    let osv_client = OsvClient::new();
     let conn = db::get_connection(db_path)?;
     
+    // 抑制ルール（プロジェクトの .paperstack-ignore.toml とデータベース）
+    let ignore_dir = if dir_path.is_file() {
+        dir_path.parent().unwrap_or(dir_path)
+    } else {
+        dir_path
+    };
+    let (mut suppressions, ignore_warnings) = suppression::load_file(ignore_dir);
+    warnings.extend(ignore_warnings);
+    suppressions.extend(db::vuln_queries::get_suppressions(&conn, None)?);
+    let today = chrono_now();
+    let (suppressions, expired) = suppression::split_expired(suppressions, &path, &today);
+    warnings.extend(expired);
+
     let mut all_vulnerabilities: Vec<VulnMatch> = Vec::new();
     let mut ecosystems_found: Vec<String> = Vec::new();
     let mut total_packages = 0;
+    let mut project_summaries: Vec<ScanProject> = Vec::new();
+    // 詳細を取得済みの脆弱性（同じ ID はスキャン全体で 1 回だけ取得する）
+    let mut details: HashMap<String, OsvVulnerability> = HashMap::new();
+    // サブプロジェクト × エコシステムごとの検出数（スキャンを終えてから履歴に保存する）
+    let mut history: Vec<(String, String, i32)> = Vec::new();
+    // ここから後の警告は OSV への照会の失敗だけ
+    let warnings_before_queries = warnings.len();
     
-    for scan in &scan_results {
-        if !ecosystems_found.contains(&scan.ecosystem) {
-            ecosystems_found.push(scan.ecosystem.clone());
-        }
+    for project in &projects {
+        let mut summary = ScanProject {
+            path: project.path.clone(),
+            ecosystems: Vec::new(),
+            total_packages: 0,
+            vuln_count: 0,
+        };
+        let first_match = all_vulnerabilities.len();
         
-        total_packages += scan.dependencies.len() as i32;
-        
-        // バッチクエリを構築（最大1000件ずつ）
-        let chunks: Vec<&[Dependency]> = scan.dependencies.chunks(100).collect();
-        
-        for chunk in chunks {
-            let queries: Vec<OsvQueryRequest> = chunk
+        for scan in &project.results {
+            if !ecosystems_found.contains(&scan.ecosystem) {
+                ecosystems_found.push(scan.ecosystem.clone());
+            }
+            if !summary.ecosystems.contains(&scan.ecosystem) {
+                summary.ecosystems.push(scan.ecosystem.clone());
+            }
+
+            // 開発時のみの依存関係を除外する設定なら照会しない（経路の算出にはグラフ全体を使う）
+            let dependencies: Vec<&Dependency> = scan
+                .dependencies
                 .iter()
-                .map(|dep| OsvQueryRequest {
-                    package: OsvPackage {
-                        name: dep.name.clone(),
-                        ecosystem: dep.ecosystem.clone(),
-                    },
-                    version: Some(dep.version.clone()),
-                })
+                .filter(|dep| !(dep.dev && options.dev_findings == DevFindings::Exclude))
                 .collect();
+            summary.total_packages += dependencies.len() as i32;
+
+            // オフライン: 取り込み済みの影響範囲と照合
+            if options.offline {
+                for &dep in &dependencies {
+                    for (vuln, version_match) in match_local(&conn, dep)? {
+                        all_vulnerabilities.push(VulnMatch {
+                            package_name: dep.name.clone(),
+                            installed_version: dep.version.clone(),
+                            source_file: scan.source_file.clone(),
+                            project: project.path.clone(),
+                            version_match,
+                            vulnerability: vuln,
+                            suppression: None,
+                            dependency_path: scanner::dependency_path(&scan.dependencies, dep),
+                            dev: dep.dev,
+                            optional: dep.optional,
+                        });
+                    }
+                }
+                continue;
+            }
+
+            // バッチクエリを構築（BATCH_SIZE 件ずつ）
+            // バージョンを固定していない依存（requirements.txt の ">=2.0" など）は別に照会する
+            let (unpinned, pinned): (Vec<&Dependency>, Vec<&Dependency>) = dependencies
+                .iter()
+                .partition(|dep| version::is_unpinned(&dep.ecosystem, &dep.version));
+            let chunks: Vec<&[&Dependency]> = pinned.chunks(BATCH_SIZE).collect();
             
-            match osv_client.query_batch(queries).await {
-                Ok(batch_response) => {
-                    for (i, result) in batch_response.results.iter().enumerate() {
-                        if let Some(dep) = chunk.get(i) {
-                            for osv_vuln in &result.vulns {
-                                let vuln = convert_osv_vulnerability(
-                                    osv_vuln,
-                                    &dep.ecosystem,
-                                    &dep.name,
-                                );
-                                
-                                // キャッシュに保存
-                                let _ = db::vuln_queries::upsert_vulnerability(&conn, &vuln);
-                                
-                                all_vulnerabilities.push(VulnMatch {
-                                    package_name: dep.name.clone(),
-                                    installed_version: dep.version.clone(),
-                                    vulnerability: vuln,
-                                });
+            for chunk in chunks {
+                let queries: Vec<OsvQueryRequest> = chunk
+                    .iter()
+                    .map(|dep| OsvQueryRequest {
+                        package: OsvPackage {
+                            name: dep.name.clone(),
+                            ecosystem: dep.ecosystem.clone(),
+                        },
+                        version: Some(dep.version.clone()),
+                    })
+                    .collect();
+
+                match osv_client.query_batch(queries).await {
+                    Ok(batch_response) => {
+                        for (i, result) in batch_response.results.iter().enumerate() {
+                            let Some(&dep) = chunk.get(i) else {
+                                continue;
+                            };
+                            // querybatch は id と modified しか返さないため、詳細を取得してから変換する
+                            for found in &result.vulns {
+                                match vulnerability_details(osv_client, &mut details, &found.id)
+                                    .await
+                                {
+                                    Ok(osv_vuln) => {
+                                        all_vulnerabilities.push(osv_match(
+                                            &conn,
+                                            osv_vuln,
+                                            dep,
+                                            scan,
+                                            &project.path,
+                                        )?);
+                                    }
+                                    Err(e) => query_failed(
+                                        options,
+                                        &mut warnings,
+                                        format!(
+                                            "{}: failed to fetch {} for {}: {}",
+                                            scan.source_file, found.id, dep.name, e
+                                        ),
+                                    )?,
+                                }
                             }
                         }
                     }
+                    Err(e) => query_failed(
+                        options,
+                        &mut warnings,
+                        format!(
+                            "{}: OSV query failed for {} dependencies: {}",
+                            scan.source_file,
+                            chunk.len(),
+                            e
+                        ),
+                    )?,
                 }
-                Err(e) => {
-                    eprintln!("Batch query error: {}", e);
+            }
+
+            // 全バージョンの脆弱性を取得し、指定範囲と影響範囲が重なるものだけを残す
+            for dep in unpinned {
+                match osv_client
+                    .query_package(&dep.ecosystem, &dep.name, None)
+                    .await
+                {
+                    Ok(response) => {
+                        for osv_vuln in &response.vulns {
+                            let m = osv_match(&conn, osv_vuln, dep, scan, &project.path)?;
+                            if m.version_match != VersionMatch::NotAffected {
+                                all_vulnerabilities.push(m);
+                            }
+                        }
+                    }
+                    Err(e) => query_failed(
+                        options,
+                        &mut warnings,
+                        format!(
+                            "{}: OSV query failed for {}: {}",
+                            scan.source_file, dep.name, e
+                        ),
+                    )?,
                 }
             }
         }
+
+        // 同じ問題を指す別 ID の検出をまとめてから件数を数える
+        let project_matches = all_vulnerabilities.split_off(first_match);
+        all_vulnerabilities.extend(merge_aliases(&conn, project_matches));
+
+        // 抑制ルールに該当する検出に印を付ける
+        suppression::mark(
+            &mut all_vulnerabilities[first_match..],
+            &suppressions,
+            &path,
+        );
+
+        // スキャン履歴の件数（サブプロジェクト × エコシステムごと、抑制済みは除く）
+        let project_matches = &all_vulnerabilities[first_match..];
+        for ecosystem in &summary.ecosystems {
+            let vuln_count = project_matches
+                .iter()
+                .filter(|v| v.suppression.is_none())
+                .filter(|v| v.vulnerability.affected_ecosystem == *ecosystem)
+                .count() as i32;
+            history.push((project.path.clone(), ecosystem.clone(), vuln_count));
+        }
+
+        summary.vuln_count = project_matches
+            .iter()
+            .filter(|v| v.suppression.is_none())
+            .count() as i32;
+        total_packages += summary.total_packages;
+        project_summaries.push(summary);
     }
     
-    // 深刻度でソート（critical -> high -> medium -> low）
+    // 深刻度でソート（critical -> high -> medium -> low、後回しにする開発用の検出と抑制済みは後ろ）
     all_vulnerabilities.sort_by(|a, b| {
-        severity_order(&b.vulnerability.severity).cmp(&severity_order(&a.vulnerability.severity))
+        a.suppression
+            .is_some()
+            .cmp(&b.suppression.is_some())
+            .then_with(|| {
+                is_deprioritized(a, options.dev_findings)
+                    .cmp(&is_deprioritized(b, options.dev_findings))
+            })
+            .then_with(|| {
+                severity_order(&b.vulnerability.severity)
+                    .cmp(&severity_order(&a.vulnerability.severity))
+            })
     });
     
-    // スキャン履歴を保存
-    for ecosystem in &ecosystems_found {
-        let vuln_count = all_vulnerabilities
-            .iter()
-            .filter(|v| v.vulnerability.affected_ecosystem == *ecosystem)
-            .count() as i32;
-        let _ = db::vuln_queries::add_scan_history(&conn, &path, ecosystem, vuln_count);
-    }
-    
     // 現在時刻を取得
-    let scanned_at = chrono_now();
+    let scanned_at = today;
     
+    // 照会に失敗した依存関係の検出は欠けているため、履歴にも差分の比較対象にも残さない
+    // （残すと次回の差分で、欠けた検出が「修正済み」から「新規」に戻って見える）
+    let query_failures = warnings.len() - warnings_before_queries;
+    let run_id = if query_failures > 0 {
+        warnings.push(format!(
+            "scan run not saved: {} OSV queries failed, so findings may be missing",
+            query_failures
+        ));
+        None
+    } else {
+        for (project, ecosystem, vuln_count) in &history {
+            let _ =
+                db::vuln_queries::add_scan_history(&conn, &path, project, ecosystem, *vuln_count);
+        }
+        // 次回との差分のため、検出の一覧を実行ごとに保存（ディレクトリは正規化して記録）
+        let findings: Vec<ScanFinding> = all_vulnerabilities
+            .iter()
+            .map(|m| diff::to_finding(m, canonical_id(&conn, &m.vulnerability)))
+            .collect();
+        let run_directory = normalize_run_directory(&path);
+        db::vuln_queries::save_scan_run(&conn, &run_directory, total_packages, &findings).ok()
+    };
+
+    // 依存関係ごとのアップグレード計画（保存済みの影響範囲で候補を判定）
+    let remediation = report::remediation::plan(&all_vulnerabilities, |ecosystem, name| {
+        db::vuln_queries::get_local_affected(&conn, ecosystem, name).unwrap_or_default()
+    });
+
     Ok(ScanResult {
         directory: path,
         ecosystems: ecosystems_found,
         vulnerabilities: all_vulnerabilities,
         scanned_at,
         total_packages,
+        projects: project_summaries,
+        warnings,
+        run_id,
+        remediation,
+        dev_findings: options.dev_findings,
+    })
+}
+
+/// 後回しにする検出か（開発時のみの依存関係で、後回しにする設定のとき）
+pub fn is_deprioritized(m: &VulnMatch, dev_findings: DevFindings) -> bool {
+    m.dev && dev_findings == DevFindings::Deprioritize
+}
+
+/// 検出を実行間で照合するための正規 ID（データベースの別名グループ、なければ検出の別名から選ぶ）
+fn canonical_id(conn: &rusqlite::Connection, vuln: &Vulnerability) -> String {
+    if let Ok(Some(id)) = db::vuln_queries::get_canonical_id(conn, &vuln.id) {
+        return id;
+    }
+    let ids = std::iter::once(vuln.id.as_str()).chain(vuln.aliases.iter().map(String::as_str));
+    db::vuln_queries::preferred_id(ids)
+        .unwrap_or(&vuln.id)
+        .to_string()
+}
+
+/// OSV への照会の失敗を扱う（strict ならスキャンを失敗させ、そうでなければ警告に残す）
+/// 失敗を握りつぶすと、検出なしの結果としてゲートを通ってしまう
+fn query_failed(
+    options: &ScanOptions,
+    warnings: &mut Vec<String>,
+    message: String,
+) -> Result<(), CommandError> {
+    if options.strict {
+        return Err(CommandError {
+            message: format!("OSV への照会に失敗しました: {}", message),
+        });
+    }
+    warnings.push(message);
+    Ok(())
+}
+
+/// 脆弱性の詳細（深刻度・別名・影響範囲を含む完全なレコード）を取得
+async fn vulnerability_details<'a>(
+    osv_client: &OsvClient,
+    details: &'a mut HashMap<String, OsvVulnerability>,
+    vuln_id: &str,
+) -> Result<&'a OsvVulnerability, OsvError> {
+    if !details.contains_key(vuln_id) {
+        let osv_vuln = osv_client.get_vulnerability(vuln_id).await?;
+        details.insert(vuln_id.to_string(), osv_vuln);
+    }
+    Ok(&details[vuln_id])
+}
+
+/// OSV の応答をキャッシュに保存し、依存関係の検出に変換
+fn osv_match(
+    conn: &rusqlite::Connection,
+    osv_vuln: &OsvVulnerability,
+    dep: &Dependency,
+    scan: &ScanDependencies,
+    project: &str,
+) -> Result<VulnMatch, CommandError> {
+    let mut vuln = convert_osv_vulnerability(osv_vuln);
+
+    // キャッシュに保存（affected を含まない応答は検出したパッケージを代表とする）
+    if osv_vuln.affected.is_empty() {
+        vuln = for_package(vuln, &dep.ecosystem, &dep.name);
+    }
+    if db::vuln_queries::upsert_vulnerability(conn, &vuln)? && !osv_vuln.affected.is_empty() {
+        db::vuln_queries::replace_affected(conn, osv_vuln)?;
+    }
+    let vuln = for_package(vuln, &dep.ecosystem, &dep.name);
+
+    Ok(VulnMatch {
+        package_name: dep.name.clone(),
+        installed_version: dep.version.clone(),
+        source_file: scan.source_file.clone(),
+        project: project.to_string(),
+        version_match: version::range::evaluate(osv_vuln, dep),
+        vulnerability: vuln,
+        suppression: None,
+        dependency_path: scanner::dependency_path(&scan.dependencies, dep),
+        dev: dep.dev,
+        optional: dep.optional,
+    })
+}
+
+/// ローカルに保存された影響範囲から、依存関係のバージョンに該当する脆弱性を検索
+/// バージョンを固定していない依存は、指定範囲と影響範囲が重なるものを未固定として返す
+fn match_local(
+    conn: &rusqlite::Connection,
+    dep: &Dependency,
+) -> Result<Vec<(Vulnerability, VersionMatch)>, CommandError> {
+    let mut matched: Vec<(String, VersionMatch)> = Vec::new();
+    for (vuln_id, affected) in
+        db::vuln_queries::get_local_affected(conn, &dep.ecosystem, &dep.name)?
+    {
+        if matched.iter().any(|(id, _)| *id == vuln_id) {
+            continue;
+        }
+        match version::range::evaluate_affected(&affected, &dep.ecosystem, &dep.version) {
+            m @ (VersionMatch::Affected | VersionMatch::Unpinned) => matched.push((vuln_id, m)),
+            VersionMatch::NotAffected | VersionMatch::Unknown => {}
+        }
+    }
+
+    let mut vulns = Vec::new();
+    for (vuln_id, version_match) in matched {
+        if let Some(vuln) = db::vuln_queries::get_vulnerability_by_id(conn, &vuln_id)? {
+            vulns.push((for_package(vuln, &dep.ecosystem, &dep.name), version_match));
+        }
+    }
+    Ok(vulns)
+}
+
+/// GHSA / CVE / RUSTSEC など同じ問題を指す検出を 1 件にまとめる
+/// 同じファイルの同じパッケージ・バージョンで正規 ID が一致するものを統合し、
+/// 最も深刻なものを残して他の ID は aliases に列挙する
+fn merge_aliases(conn: &rusqlite::Connection, matches: Vec<VulnMatch>) -> Vec<VulnMatch> {
+    let mut merged: Vec<(String, VulnMatch)> = Vec::new();
+    for mut found in matches {
+        let vuln = &mut found.vulnerability;
+        let mut ids = db::vuln_queries::get_aliases(conn, &vuln.id).unwrap_or_default();
+        for alias in &vuln.aliases {
+            if *alias != vuln.id && !ids.contains(alias) {
+                ids.push(alias.clone());
+            }
+        }
+        // 保存に失敗していても、自身の別名から正規 ID を決める
+        let canonical = db::vuln_queries::get_canonical_id(conn, &vuln.id)
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| {
+                db::vuln_queries::preferred_id(
+                    std::iter::once(vuln.id.as_str()).chain(ids.iter().map(String::as_str)),
+                )
+                .unwrap_or(&vuln.id)
+                .to_string()
+            });
+        vuln.aliases = ids;
+
+        let existing = merged.iter_mut().find(|(id, m)| {
+            *id == canonical
+                && m.package_name == found.package_name
+                && m.installed_version == found.installed_version
+                && m.source_file == found.source_file
+                && m.project == found.project
+        });
+        let Some((_, existing)) = existing else {
+            merged.push((canonical, found));
+            continue;
+        };
+
+        let mut ids: Vec<String> = std::iter::once(existing.vulnerability.id.clone())
+            .chain(existing.vulnerability.aliases.drain(..))
+            .collect();
+        for id in std::iter::once(&found.vulnerability.id).chain(&found.vulnerability.aliases) {
+            if !ids.contains(id) {
+                ids.push(id.clone());
+            }
+        }
+        if severity_order(&found.vulnerability.severity)
+            > severity_order(&existing.vulnerability.severity)
+        {
+            *existing = found;
+        }
+        let kept = existing.vulnerability.id.clone();
+        ids.retain(|id| *id != kept);
+        existing.vulnerability.aliases = ids;
+    }
+    merged.into_iter().map(|(_, found)| found).collect()
+}
+
+/// OSV のデータベースエクスポート（all.zip）を取り込む
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn import_osv_archive(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<OsvImportSummary, CommandError> {
+    import_osv_archive_file(&state.db_path, Path::new(&path))
+}
+
+/// all.zip の各エントリを脆弱性と影響範囲として保存（Tauri コマンドと CLI で共有）
+pub fn import_osv_archive_file(
+    db_path: &str,
+    path: &Path,
+) -> Result<OsvImportSummary, CommandError> {
+    let mut conn = db::get_connection(db_path)?;
+    let tx = conn.transaction()?;
+
+    let mut imported = 0;
+    let mut ecosystems: Vec<String> = Vec::new();
+    let skipped = osv_archive::for_each_entry(path, |osv_vuln| -> Result<(), CommandError> {
+        let vuln = convert_osv_vulnerability(&osv_vuln);
+        for package in &vuln.affected_packages {
+            if !ecosystems.contains(&package.ecosystem) {
+                ecosystems.push(package.ecosystem.clone());
+            }
+        }
+
+        // 保存済みの方が新しければ残す
+        if db::vuln_queries::upsert_vulnerability(&tx, &vuln)? {
+            db::vuln_queries::replace_affected(&tx, &osv_vuln)?;
+            imported += 1;
+        }
+        Ok(())
+    })?;
+
+    tx.commit()?;
+    ecosystems.sort();
+
+    Ok(OsvImportSummary {
+        imported,
+        skipped: skipped as i64,
+        ecosystems,
+    })
+}
+
+/// スキャン結果を SARIF 2.1.0 形式でファイルに出力
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn export_scan_sarif(result: ScanResult, path: String) -> Result<(), CommandError> {
+    let json = report::sarif::to_sarif_json(&result).map_err(|e| CommandError {
+        message: e.to_string(),
+    })?;
+    std::fs::write(&path, json).map_err(|e| CommandError {
+        message: format!("SARIF の書き込みに失敗しました: {}", e),
+    })?;
+    Ok(())
+}
+
+/// ディレクトリの依存関係から SBOM（CycloneDX / SPDX）を生成してファイルに出力
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn export_sbom(
+    path: String,
+    format: String,
+    output_path: String,
+) -> Result<(), CommandError> {
+    let format = SbomFormat::parse(&format).ok_or_else(|| CommandError {
+        message: format!("未対応の SBOM 形式です: {}", format),
+    })?;
+    let json = build_sbom(&path, format)?;
+    std::fs::write(&output_path, json).map_err(|e| CommandError {
+        message: format!("SBOM の書き込みに失敗しました: {}", e),
+    })?;
+    Ok(())
+}
+
+/// SBOM 生成本体（Tauri コマンドと CLI で共有）
+pub fn build_sbom(path: &str, format: SbomFormat) -> Result<String, CommandError> {
+    let dir_path = Path::new(path);
+    if !dir_path.is_dir() {
+        return Err(CommandError {
+            message: format!("ディレクトリではありません: {}", path),
+        });
+    }
+
+    let scan_results: Vec<_> = scanner::scan_directory(dir_path, &ScanOptions::default())?
+        .into_iter()
+        .flat_map(|project| project.results)
+        .collect();
+    let project_name = dir_path
+        .canonicalize()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| path.to_string());
+
+    report::sbom::to_sbom_json(&scan_results, &project_name, format).map_err(|e| CommandError {
+        message: e.to_string(),
     })
 }
 
 /// 脆弱性の詳細を取得
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_vulnerability_detail(
     state: State<'_, AppState>,
@@ -221,21 +723,12 @@ pub async fn get_vulnerability_detail(
     let osv_client = OsvClient::new();
     match osv_client.get_vulnerability(&vuln_id).await {
         Ok(osv_vuln) => {
-            // パッケージ情報を取得
-            let (ecosystem, package) = if let Some(affected) = osv_vuln.affected.first() {
-                if let Some(ref pkg) = affected.package {
-                    (pkg.ecosystem.clone(), pkg.name.clone())
-                } else {
-                    ("unknown".to_string(), "unknown".to_string())
-                }
-            } else {
-                ("unknown".to_string(), "unknown".to_string())
-            };
-            
-            let vuln = convert_osv_vulnerability(&osv_vuln, &ecosystem, &package);
+            let vuln = convert_osv_vulnerability(&osv_vuln);
             
-            // キャッシュに保存
-            let _ = db::vuln_queries::upsert_vulnerability(&conn, &vuln);
+            // キャッシュに保存（影響パッケージもすべて記録する）
+            if db::vuln_queries::upsert_vulnerability(&conn, &vuln)? {
+                db::vuln_queries::replace_affected(&conn, &osv_vuln)?;
+            }
             
             Ok(Some(vuln))
         }
@@ -244,6 +737,7 @@ pub async fn get_vulnerability_detail(
 }
 
 /// スキャン履歴を取得
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_scan_history(
     state: State<'_, AppState>,
@@ -258,7 +752,179 @@ pub async fn get_scan_history(
     Ok(history)
 }
 
+/// 抑制ルールを取得（directory を指定するとそのディレクトリに適用されるもの）
+/// プロジェクトの .paperstack-ignore.toml のルールも含める
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn get_suppressions(
+    state: State<'_, AppState>,
+    directory: Option<String>,
+) -> Result<Vec<Suppression>, CommandError> {
+    let conn = db::get_connection(&state.db_path)?;
+    let mut suppressions = match directory.as_deref() {
+        Some(dir) => suppression::load_file(Path::new(dir)).0,
+        None => Vec::new(),
+    };
+    suppressions.extend(db::vuln_queries::get_suppressions(
+        &conn,
+        directory.as_deref(),
+    )?);
+    Ok(suppressions)
+}
+
+/// 抑制ルールを追加
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn add_suppression(
+    state: State<'_, AppState>,
+    suppression: Suppression,
+) -> Result<i64, CommandError> {
+    suppression::validate(&suppression).map_err(|message| CommandError { message })?;
+    let conn = db::get_connection(&state.db_path)?;
+    Ok(db::vuln_queries::add_suppression(&conn, &suppression)?)
+}
+
+/// 抑制ルールを削除
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn delete_suppression(state: State<'_, AppState>, id: i64) -> Result<(), CommandError> {
+    let conn = db::get_connection(&state.db_path)?;
+    db::vuln_queries::delete_suppression(&conn, id)?;
+    Ok(())
+}
+
+/// 監視対象プロジェクトの一覧を取得
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn get_watched_projects(
+    state: State<'_, AppState>,
+) -> Result<Vec<WatchedProject>, CommandError> {
+    let conn = db::get_connection(&state.db_path)?;
+    Ok(db::vuln_queries::get_watched_projects(&conn)?)
+}
+
+/// 監視対象プロジェクトを追加（バックグラウンドで interval_hours ごとに再スキャンする）
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn add_watched_project(
+    state: State<'_, AppState>,
+    directory: String,
+    interval_hours: Option<i64>,
+) -> Result<i64, CommandError> {
+    if !Path::new(&directory).is_dir() {
+        return Err(CommandError {
+            message: format!("ディレクトリが存在しません: {}", directory),
+        });
+    }
+    let interval_hours = interval_hours.unwrap_or(scheduler::DEFAULT_INTERVAL_HOURS);
+    if interval_hours < 1 {
+        return Err(CommandError {
+            message: format!(
+                "再スキャンの間隔は 1 時間以上にしてください: {}",
+                interval_hours
+            ),
+        });
+    }
+    let conn = db::get_connection(&state.db_path)?;
+    let directory = normalize_run_directory(&directory);
+    Ok(db::vuln_queries::add_watched_project(
+        &conn,
+        &directory,
+        interval_hours,
+    )?)
+}
+
+/// 監視対象プロジェクトの有効・無効を切り替え
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn set_watched_project_enabled(
+    state: State<'_, AppState>,
+    id: i64,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    let conn = db::get_connection(&state.db_path)?;
+    db::vuln_queries::set_watched_project_enabled(&conn, id, enabled)?;
+    Ok(())
+}
+
+/// 監視対象プロジェクトを削除
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn delete_watched_project(
+    state: State<'_, AppState>,
+    id: i64,
+) -> Result<(), CommandError> {
+    let conn = db::get_connection(&state.db_path)?;
+    db::vuln_queries::delete_watched_project(&conn, id)?;
+    Ok(())
+}
+
+/// 検出を保存したスキャン実行の一覧を取得
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn get_scan_runs(
+    state: State<'_, AppState>,
+    directory: Option<String>,
+    limit: Option<i32>,
+) -> Result<Vec<ScanRun>, CommandError> {
+    let conn = db::get_connection(&state.db_path)?;
+    let directory = directory.map(|dir| normalize_run_directory(&dir));
+    Ok(db::vuln_queries::get_scan_runs(
+        &conn,
+        directory.as_deref(),
+        limit.unwrap_or(20),
+    )?)
+}
+
+/// 2 回のスキャンの検出を比較（base を省略すると同じディレクトリの直前の実行）
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub async fn diff_scan_runs(
+    state: State<'_, AppState>,
+    base_run_id: Option<i64>,
+    target_run_id: i64,
+) -> Result<ScanDiff, CommandError> {
+    let conn = db::get_connection(&state.db_path)?;
+    load_scan_diff(&conn, base_run_id, target_run_id)
+}
+
+/// スキャン実行の記録に使うディレクトリ表記（存在すれば正規化したパス）
+pub fn normalize_run_directory(directory: &str) -> String {
+    Path::new(directory)
+        .canonicalize()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| directory.to_string())
+}
+
+/// 保存済みの 2 回のスキャンを読み込んで比較（Tauri コマンドと CLI で共有）
+pub fn load_scan_diff(
+    conn: &rusqlite::Connection,
+    base_run_id: Option<i64>,
+    target_run_id: i64,
+) -> Result<ScanDiff, CommandError> {
+    let not_found = |id: i64| CommandError {
+        message: format!("スキャン実行が見つかりません: #{}", id),
+    };
+    let target = db::vuln_queries::get_scan_run(conn, target_run_id)?
+        .ok_or_else(|| not_found(target_run_id))?;
+    let base = match base_run_id {
+        Some(id) => db::vuln_queries::get_scan_run(conn, id)?.ok_or_else(|| not_found(id))?,
+        None => {
+            db::vuln_queries::get_previous_scan_run(conn, &target)?.ok_or_else(|| CommandError {
+                message: format!(
+                    "スキャン実行 #{} より前の実行がありません: {}",
+                    target.id, target.directory
+                ),
+            })?
+        }
+    };
+    let base_findings = db::vuln_queries::get_scan_findings(conn, base.id)?;
+    let target_findings = db::vuln_queries::get_scan_findings(conn, target.id)?;
+    Ok(diff::diff(base, base_findings, target, target_findings))
+}
+
 /// 脆弱性の総数を取得
+#[cfg(feature = "desktop")]
 #[tauri::command]
 pub async fn get_vulnerability_count(
     state: State<'_, AppState>,
@@ -274,20 +940,31 @@ pub async fn get_vulnerability_count(
 // --- Helper Functions ---
 
 /// OSV脆弱性をアプリ内モデルに変換
-fn convert_osv_vulnerability(
-    osv_vuln: &OsvVulnerability,
-    ecosystem: &str,
-    package_name: &str,
-) -> Vulnerability {
+/// 一覧表示用の行は最初の affected パッケージで代表させ、すべての影響パッケージを affected_packages に持つ
+fn convert_osv_vulnerability(osv_vuln: &OsvVulnerability) -> Vulnerability {
+    let cvss = osv_vuln.cvss();
+    let (severity, severity_source) = osv_vuln.resolve_severity();
+    let affected_packages: Vec<AffectedPackage> = osv_vuln
+        .affected
+        .iter()
+        .filter_map(db::vuln_queries::affected_package)
+        .collect();
+    let (ecosystem, package) = affected_packages
+        .first()
+        .map(|p| (p.ecosystem.clone(), p.name.clone()))
+        .unwrap_or_else(|| ("unknown".to_string(), "unknown".to_string()));
     Vulnerability {
         id: osv_vuln.id.clone(),
         source: "osv".to_string(),
-        severity: osv_vuln.severity_level(),
+        severity,
+        severity_source: Some(severity_source.as_str().to_string()),
         cvss_score: osv_vuln.cvss_score(),
+        cvss_vector: cvss.as_ref().map(|c| c.vector.clone()),
+        cvss_version: cvss.as_ref().map(|c| c.version.as_str().to_string()),
         title: osv_vuln.summary.clone().unwrap_or_else(|| osv_vuln.id.clone()),
         description: osv_vuln.details.clone(),
-        affected_package: package_name.to_string(),
-        affected_ecosystem: ecosystem.to_string(),
+        affected_package: package,
+        affected_ecosystem: ecosystem,
         affected_versions: Some(osv_vuln.affected_versions_string()),
         fixed_versions: {
             let fixed = osv_vuln.fixed_versions();
@@ -298,13 +975,31 @@ fn convert_osv_vulnerability(
             }
         },
         published_at: osv_vuln.published.clone(),
+        modified: osv_vuln.modified.clone(),
         references: osv_vuln.reference_urls(),
         fetched_at: None,
+        affected_packages,
+        aliases: osv_vuln.aliases.clone(),
     }
 }
 
+/// 検出したパッケージから見た脆弱性（影響範囲・修正バージョンをそのパッケージのものに絞る）
+fn for_package(mut vuln: Vulnerability, ecosystem: &str, package_name: &str) -> Vulnerability {
+    let key = db::vuln_queries::normalize_package(ecosystem, package_name);
+    let matched = vuln.affected_packages.iter().find(|p| {
+        p.ecosystem == ecosystem && db::vuln_queries::normalize_package(ecosystem, &p.name) == key
+    });
+    if let Some(package) = matched {
+        vuln.affected_versions = package.affected_versions.clone();
+        vuln.fixed_versions = package.fixed_versions.clone();
+    }
+    vuln.affected_package = package_name.to_string();
+    vuln.affected_ecosystem = ecosystem.to_string();
+    vuln
+}
+
 /// 深刻度の順序（ソート用）
-fn severity_order(severity: &str) -> i32 {
+pub fn severity_order(severity: &str) -> i32 {
     match severity.to_lowercase().as_str() {
         "critical" => 4,
         "high" => 3,
@@ -315,7 +1010,7 @@ fn severity_order(severity: &str) -> i32 {
 }
 
 /// 現在時刻をISO 8601形式で取得（簡易版）
-fn chrono_now() -> String {
+pub fn chrono_now() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
     
     let now = SystemTime::now()
@@ -372,3 +1067,287 @@ fn days_to_ymd(days: u64) -> (i32, u32, u32) {
 fn is_leap_year(year: i32) -> bool {
     (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::gate::{self, GatePolicy};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    const PACKAGE_LOCK: &str = r#"{
+        "lockfileVersion": 3,
+        "packages": {
+            "": {"dependencies": {"lodash": "^4.17.20"}, "devDependencies": {"minimist": "^1.2.5"}},
+            "node_modules/lodash": {"version": "4.17.20"},
+            "node_modules/minimist": {"version": "1.2.5", "dev": true}
+        }
+    }"#;
+
+    const LODASH_GHSA: &str = r#"{
+        "id": "GHSA-35jh-r3h4-6jhm",
+        "modified": "2024-02-01T00:00:00Z",
+        "summary": "Command Injection in lodash",
+        "aliases": ["CVE-2021-23337"],
+        "severity": [{"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:H/UI:N/S:U/C:H/I:H/A:H"}],
+        "affected": [{
+            "package": {"ecosystem": "npm", "name": "lodash"},
+            "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "4.17.21"}]}]
+        }],
+        "database_specific": {"severity": "HIGH"}
+    }"#;
+
+    /// HTTP リクエストごとに (メソッドとパス, 本文) から (ステータス, 本文) を返すサーバーを起動し、ベース URL を返す
+    fn serve(handler: impl Fn(&str, &str) -> (u16, String) + Send + 'static) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let route = request_line
+                    .split_whitespace()
+                    .take(2)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let (status, response) = handler(&route, &String::from_utf8_lossy(&body));
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    response.len(),
+                    response
+                );
+            }
+        });
+        url
+    }
+
+    /// OSV API の代わり（querybatch は本物と同じく id と modified だけを返し、詳細は /vulns/{id} で返す）
+    /// hits はパッケージ名と該当する脆弱性の ID
+    fn osv_server(hits: &[(&str, &[&str])], records: &[&str]) -> String {
+        let hits: HashMap<String, Vec<String>> = hits
+            .iter()
+            .map(|(name, ids)| {
+                (
+                    name.to_string(),
+                    ids.iter().map(|id| id.to_string()).collect(),
+                )
+            })
+            .collect();
+        let records: HashMap<String, serde_json::Value> = records
+            .iter()
+            .map(|record| {
+                let value: serde_json::Value = serde_json::from_str(record).unwrap();
+                (value["id"].as_str().unwrap().to_string(), value)
+            })
+            .collect();
+        serve(move |route, body| {
+            if route == "POST /v1/querybatch" {
+                let request: serde_json::Value = serde_json::from_str(body).unwrap();
+                let results: Vec<serde_json::Value> = request["queries"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|query| {
+                        let name = query["package"]["name"].as_str().unwrap();
+                        let vulns: Vec<serde_json::Value> = hits
+                            .get(name)
+                            .into_iter()
+                            .flatten()
+                            .map(|id| serde_json::json!({"id": id, "modified": records.get(id).map(|r| &r["modified"])}))
+                            .collect();
+                        serde_json::json!({ "vulns": vulns })
+                    })
+                    .collect();
+                return (200, serde_json::json!({ "results": results }).to_string());
+            }
+            match route
+                .strip_prefix("GET /v1/vulns/")
+                .and_then(|id| records.get(id))
+            {
+                Some(record) => (200, record.to_string()),
+                None => (404, "{}".to_string()),
+            }
+        })
+    }
+
+    /// 指定したロックファイルだけのプロジェクトとデータベースを用意
+    fn project(file: &str, content: &str) -> (tempfile::TempDir, String) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("app")).unwrap();
+        std::fs::write(dir.path().join("app").join(file), content).unwrap();
+        let db_path = dir.path().join("vuln.db").to_string_lossy().to_string();
+        db::init_db(&db_path).unwrap();
+        (dir, db_path)
+    }
+
+    async fn scan(
+        url: &str,
+        dir: &tempfile::TempDir,
+        db_path: &str,
+        options: &ScanOptions,
+    ) -> Result<ScanResult, CommandError> {
+        let path = dir.path().join("app").to_string_lossy().to_string();
+        scan_with_client(&OsvClient::with_base_url(url), db_path, path, options).await
+    }
+
+    #[tokio::test]
+    async fn test_online_scan_hydrates_batch_results() {
+        let (dir, db_path) = project("package-lock.json", PACKAGE_LOCK);
+        let url = osv_server(&[("lodash", &["GHSA-35jh-r3h4-6jhm"])], &[LODASH_GHSA]);
+        let result = scan(&url, &dir, &db_path, &ScanOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(result.vulnerabilities.len(), 1);
+        let found = &result.vulnerabilities[0];
+        assert_eq!(found.package_name, "lodash");
+        assert_eq!(found.version_match, VersionMatch::Affected);
+        assert_eq!(found.vulnerability.severity, "high");
+        assert_eq!(found.vulnerability.cvss_score, Some(7.2));
+        assert_eq!(found.vulnerability.aliases, vec!["CVE-2021-23337"]);
+        assert_eq!(
+            found.vulnerability.fixed_versions.as_deref(),
+            Some("4.17.21")
+        );
+
+        for policy in [
+            GatePolicy::new(Some("high"), None).unwrap(),
+            GatePolicy::new(None, Some(7.0)).unwrap(),
+        ] {
+            assert!(!gate::evaluate(&result, &policy).passed);
+        }
+        assert!(gate::evaluate(&result, &GatePolicy::new(Some("critical"), None).unwrap()).passed);
+    }
+
+    #[tokio::test]
+    async fn test_query_failure() {
+        let (dir, db_path) = project("package-lock.json", PACKAGE_LOCK);
+        let url = serve(|_, _| (503, "{}".to_string()));
+
+        // ゲートなし: 結果に警告として残す
+        let result = scan(&url, &dir, &db_path, &ScanOptions::default())
+            .await
+            .unwrap();
+        assert!(result.vulnerabilities.is_empty());
+        assert_eq!(result.warnings.len(), 2);
+        assert!(result.warnings[0].contains("OSV query failed for 2 dependencies"));
+        // 検出の欠けた実行は保存せず、次回の差分の比較対象にしない
+        assert!(result.warnings[1].contains("scan run not saved"));
+        assert_eq!(result.run_id, None);
+        let conn = db::get_connection(&db_path).unwrap();
+        assert!(db::vuln_queries::get_scan_runs(&conn, None, 10)
+            .unwrap()
+            .is_empty());
+
+        // ゲートあり: 検出なしとして通さずにスキャンを失敗させる
+        let strict = ScanOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let err = scan(&url, &dir, &db_path, &strict).await.unwrap_err();
+        assert!(err.message.contains("503"));
+
+        // 詳細の取得に失敗した場合も同じ
+        let url = osv_server(&[("lodash", &["GHSA-35jh-r3h4-6jhm"])], &[]);
+        assert!(scan(&url, &dir, &db_path, &strict).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_online_scan_merges_aliases() {
+        // PYSEC は GHSA と CVE を、GHSA は CVE だけを別名に持つ（querybatch は両方の ID を返す）
+        const PYSEC: &str = r#"{
+            "id": "PYSEC-2021-66",
+            "modified": "2024-01-01T00:00:00Z",
+            "aliases": ["CVE-2020-28493", "GHSA-g3rq-g295-4j3m"],
+            "affected": [{
+                "package": {"ecosystem": "PyPI", "name": "jinja2"},
+                "ranges": [{"type": "ECOSYSTEM", "events": [{"introduced": "0"}, {"fixed": "2.11.3"}]}]
+            }]
+        }"#;
+        const GHSA: &str = r#"{
+            "id": "GHSA-g3rq-g295-4j3m",
+            "modified": "2024-01-02T00:00:00Z",
+            "summary": "Regular Expression Denial of Service (ReDoS) in Jinja2",
+            "aliases": ["CVE-2020-28493"],
+            "severity": [{"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:L"}],
+            "affected": [{
+                "package": {"ecosystem": "PyPI", "name": "jinja2"},
+                "ranges": [{"type": "ECOSYSTEM", "events": [{"introduced": "0"}, {"fixed": "2.11.3"}]}]
+            }],
+            "database_specific": {"severity": "MODERATE"}
+        }"#;
+        let (dir, db_path) = project("requirements.txt", "Jinja2==2.11.2\n");
+        let url = osv_server(
+            &[("jinja2", &["PYSEC-2021-66", "GHSA-g3rq-g295-4j3m"])],
+            &[PYSEC, GHSA],
+        );
+        let result = scan(&url, &dir, &db_path, &ScanOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(result.vulnerabilities.len(), 1);
+        let found = &result.vulnerabilities[0].vulnerability;
+        assert_eq!(found.id, "GHSA-g3rq-g295-4j3m");
+        assert_eq!(found.severity, "medium");
+        assert!(found.aliases.contains(&"PYSEC-2021-66".to_string()));
+        assert!(found.aliases.contains(&"CVE-2020-28493".to_string()));
+        assert_eq!(result.projects[0].vuln_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_online_scan_remediation() {
+        const LODASH_REDOS: &str = r#"{
+            "id": "GHSA-29mw-wpgm-hmr9",
+            "modified": "2024-02-01T00:00:00Z",
+            "summary": "Regular Expression Denial of Service (ReDoS) in lodash",
+            "aliases": ["CVE-2020-28500"],
+            "severity": [{"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:L"}],
+            "affected": [{
+                "package": {"ecosystem": "npm", "name": "lodash"},
+                "ranges": [{"type": "SEMVER", "events": [{"introduced": "4.0.0"}, {"fixed": "4.17.21"}]}]
+            }]
+        }"#;
+        let (dir, db_path) = project("package-lock.json", PACKAGE_LOCK);
+        let url = osv_server(
+            &[("lodash", &["GHSA-35jh-r3h4-6jhm", "GHSA-29mw-wpgm-hmr9"])],
+            &[LODASH_GHSA, LODASH_REDOS],
+        );
+        let result = scan(&url, &dir, &db_path, &ScanOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(result.vulnerabilities.len(), 2);
+        assert!(result
+            .vulnerabilities
+            .iter()
+            .all(|m| m.version_match == VersionMatch::Affected));
+        assert_eq!(result.remediation.len(), 1);
+        let plan = &result.remediation[0];
+        assert_eq!(plan.package_name, "lodash");
+        assert_eq!(plan.severity, "high");
+        assert_eq!(plan.target_version.as_deref(), Some("4.17.21"));
+        assert!(!plan.major_bump);
+        assert_eq!(plan.fixes.len(), 2);
+        assert!(plan.unfixed.is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+//! CVSS ベクターの解析と基本スコアの計算
+//! OSV の severity[].score はベクター文字列で提供されることが多いため、ここで基本値を求める
+
+pub mod v2;
+pub mod v3;
+pub mod v4;
+
+use std::collections::HashMap;
+
+/// CVSS のバージョン
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CvssVersion {
+    V2,
+    V3_0,
+    V3_1,
+    V4_0,
+}
+
+impl CvssVersion {
+    /// 表示・保存用のバージョン文字列
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CvssVersion::V2 => "2.0",
+            CvssVersion::V3_0 => "3.0",
+            CvssVersion::V3_1 => "3.1",
+            CvssVersion::V4_0 => "4.0",
+        }
+    }
+}
+
+/// 計算済みの CVSS
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cvss {
+    pub version: CvssVersion,
+    /// 元のベクター文字列
+    pub vector: String,
+    /// 基本値（0.0 - 10.0）
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CvssError {
+    #[error("Unsupported CVSS vector: {0}")]
+    Unsupported(String),
+    #[error("Malformed CVSS metric: {0}")]
+    Malformed(String),
+    #[error("Missing CVSS metric: {0}")]
+    MissingMetric(&'static str),
+    #[error("Invalid value for CVSS metric {metric}: {value}")]
+    InvalidValue { metric: String, value: String },
+}
+
+/// メトリクス名と値の組
+pub type Metrics = HashMap<String, String>;
+
+/// ベクター文字列から基本値を計算
+/// "CVSS:3.x/..." / "CVSS:4.0/..." と、接頭辞のない v2 ベクター（"AV:N/AC:L/Au:N/..."）を受け付ける
+pub fn parse(vector: &str) -> Result<Cvss, CvssError> {
+    let vector = vector.trim();
+    let (version, body) = if let Some(body) = vector.strip_prefix("CVSS:3.1/") {
+        (CvssVersion::V3_1, body)
+    } else if let Some(body) = vector.strip_prefix("CVSS:3.0/") {
+        (CvssVersion::V3_0, body)
+    } else if let Some(body) = vector.strip_prefix("CVSS:4.0/") {
+        (CvssVersion::V4_0, body)
+    } else if vector.starts_with("CVSS:") {
+        return Err(CvssError::Unsupported(vector.to_string()));
+    } else {
+        // NVD の v2 ベクターは括弧で囲まれていることがある
+        let body = vector.trim_start_matches('(').trim_end_matches(')');
+        (CvssVersion::V2, body)
+    };
+
+    let metrics = parse_metrics(body)?;
+    let score = match version {
+        CvssVersion::V2 => v2::base_score(&metrics)?,
+        CvssVersion::V3_0 | CvssVersion::V3_1 => v3::base_score(&metrics)?,
+        CvssVersion::V4_0 => v4::base_score(&metrics)?,
+    };
+
+    Ok(Cvss {
+        version,
+        vector: vector.to_string(),
+        score,
+    })
+}
+
+/// "AV:N/AC:L/..." をメトリクスに分解（同じメトリクスが 2 回現れたらエラー）
+fn parse_metrics(body: &str) -> Result<Metrics, CvssError> {
+    let mut metrics = Metrics::new();
+    for part in body.split('/') {
+        let Some((name, value)) = part.split_once(':') else {
+            return Err(CvssError::Malformed(part.to_string()));
+        };
+        if name.is_empty() || value.is_empty() {
+            return Err(CvssError::Malformed(part.to_string()));
+        }
+        if metrics
+            .insert(name.to_string(), value.to_string())
+            .is_some()
+        {
+            return Err(CvssError::Malformed(part.to_string()));
+        }
+    }
+    Ok(metrics)
+}
+
+/// メトリクスの値を取得し、許可された値か検証
+fn metric<'a>(
+    metrics: &'a Metrics,
+    name: &'static str,
+    allowed: &[&str],
+) -> Result<&'a str, CvssError> {
+    let value = metrics
+        .get(name)
+        .map(String::as_str)
+        .ok_or(CvssError::MissingMetric(name))?;
+    if !allowed.contains(&value) {
+        return Err(CvssError::InvalidValue {
+            metric: name.to_string(),
+            value: value.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+/// 省略可能なメトリクスの値を取得（未指定なら "X"）
+fn optional_metric<'a>(
+    metrics: &'a Metrics,
+    name: &str,
+    allowed: &[&str],
+) -> Result<&'a str, CvssError> {
+    match metrics.get(name) {
+        None => Ok("X"),
+        Some(value) if value == "X" || allowed.contains(&value.as_str()) => Ok(value),
+        Some(value) => Err(CvssError::InvalidValue {
+            metric: name.to_string(),
+            value: value.clone(),
+        }),
+    }
+}
+
+/// スコアから深刻度を判定（CVSS v3 / v4 の定性的評価。0.0 は "none"）
+pub fn severity(score: f64) -> &'static str {
+    match score {
+        s if s >= 9.0 => "critical",
+        s if s >= 7.0 => "high",
+        s if s >= 4.0 => "medium",
+        s if s > 0.0 => "low",
+        _ => "none",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_versions() {
+        let v3 = parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H").unwrap();
+        assert_eq!(v3.version, CvssVersion::V3_1);
+        assert_eq!(v3.score, 9.8);
+
+        let v4 = parse("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N").unwrap();
+        assert_eq!(v4.version, CvssVersion::V4_0);
+        assert_eq!(v4.score, 9.3);
+
+        let v2 = parse("(AV:N/AC:L/Au:N/C:P/I:P/A:P)").unwrap();
+        assert_eq!(v2.version, CvssVersion::V2);
+        assert_eq!(v2.score, 7.5);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            parse("CVSS:5.0/AV:N"),
+            Err(CvssError::Unsupported(_))
+        ));
+        assert!(matches!(
+            parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H"),
+            Err(CvssError::MissingMetric("A"))
+        ));
+        assert!(matches!(
+            parse("CVSS:3.1/AV:X/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            Err(CvssError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            parse("CVSS:3.1/AV:N/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            Err(CvssError::Malformed(_))
+        ));
+        assert!(parse("9.8").is_err());
+    }
+
+    #[test]
+    fn test_severity() {
+        assert_eq!(severity(10.0), "critical");
+        assert_eq!(severity(9.0), "critical");
+        assert_eq!(severity(8.9), "high");
+        assert_eq!(severity(4.0), "medium");
+        assert_eq!(severity(0.1), "low");
+        assert_eq!(severity(0.0), "none");
+    }
+}
@@ -0,0 +1,58 @@
+//! CVSS v2 の基本値
+//! https://www.first.org/cvss/v2/guide#3-2-1-Base-Equation
+
+use super::{metric, CvssError, Metrics};
+
+/// 基本値を計算
+pub fn base_score(metrics: &Metrics) -> Result<f64, CvssError> {
+    let av = match metric(metrics, "AV", &["L", "A", "N"])? {
+        "L" => 0.395,
+        "A" => 0.646,
+        _ => 1.0,
+    };
+    let ac = match metric(metrics, "AC", &["H", "M", "L"])? {
+        "H" => 0.35,
+        "M" => 0.61,
+        _ => 0.71,
+    };
+    let au = match metric(metrics, "Au", &["M", "S", "N"])? {
+        "M" => 0.45,
+        "S" => 0.56,
+        _ => 0.704,
+    };
+    let impact_value = |name: &'static str| -> Result<f64, CvssError> {
+        Ok(match metric(metrics, name, &["N", "P", "C"])? {
+            "N" => 0.0,
+            "P" => 0.275,
+            _ => 0.660,
+        })
+    };
+    let c = impact_value("C")?;
+    let i = impact_value("I")?;
+    let a = impact_value("A")?;
+
+    let impact = 10.41 * (1.0 - (1.0 - c) * (1.0 - i) * (1.0 - a));
+    let exploitability = 20.0 * av * ac * au;
+    let f_impact = if impact == 0.0 { 0.0 } else { 1.176 };
+    let score = (0.6 * impact + 0.4 * exploitability - 1.5) * f_impact;
+
+    Ok((score * 10.0).round() / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse;
+
+    fn score(vector: &str) -> f64 {
+        parse(vector).unwrap().score
+    }
+
+    #[test]
+    fn test_v2_base_scores() {
+        assert_eq!(score("AV:N/AC:L/Au:N/C:C/I:C/A:C"), 10.0);
+        assert_eq!(score("AV:N/AC:L/Au:N/C:P/I:P/A:P"), 7.5);
+        assert_eq!(score("AV:N/AC:M/Au:N/C:N/I:P/A:N"), 4.3);
+        assert_eq!(score("AV:L/AC:L/Au:N/C:C/I:C/A:C"), 7.2);
+        assert_eq!(score("AV:N/AC:L/Au:N/C:N/I:N/A:N"), 0.0);
+    }
+}
@@ -0,0 +1,99 @@
+//! CVSS v3.0 / v3.1 の基本値
+//! https://www.first.org/cvss/v3.1/specification-document#7-1-Base-Metrics-Equations
+
+use super::{metric, CvssError, Metrics};
+
+/// 基本値を計算
+pub fn base_score(metrics: &Metrics) -> Result<f64, CvssError> {
+    let scope_changed = metric(metrics, "S", &["U", "C"])? == "C";
+
+    let av = match metric(metrics, "AV", &["N", "A", "L", "P"])? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        _ => 0.2,
+    };
+    let ac = match metric(metrics, "AC", &["L", "H"])? {
+        "L" => 0.77,
+        _ => 0.44,
+    };
+    // スコープが変更される場合は必要な権限の重みが変わる
+    let pr = match metric(metrics, "PR", &["N", "L", "H"])? {
+        "N" => 0.85,
+        "L" if scope_changed => 0.68,
+        "L" => 0.62,
+        _ if scope_changed => 0.5,
+        _ => 0.27,
+    };
+    let ui = match metric(metrics, "UI", &["N", "R"])? {
+        "N" => 0.85,
+        _ => 0.62,
+    };
+    let cia = |name: &'static str| -> Result<f64, CvssError> {
+        Ok(match metric(metrics, name, &["H", "L", "N"])? {
+            "H" => 0.56,
+            "L" => 0.22,
+            _ => 0.0,
+        })
+    };
+    let c = cia("C")?;
+    let i = cia("I")?;
+    let a = cia("A")?;
+
+    let iss = 1.0 - (1.0 - c) * (1.0 - i) * (1.0 - a);
+    let impact = if scope_changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powi(15)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return Ok(0.0);
+    }
+
+    let exploitability = 8.22 * av * ac * pr * ui;
+    let score = if scope_changed {
+        roundup((1.08 * (impact + exploitability)).min(10.0))
+    } else {
+        roundup((impact + exploitability).min(10.0))
+    };
+    Ok(score)
+}
+
+/// 小数第 1 位への切り上げ（v3.1 付録 A の浮動小数点誤差を避ける実装）
+fn roundup(value: f64) -> f64 {
+    let int_input = (value * 100_000.0).round() as i64;
+    if int_input % 10_000 == 0 {
+        int_input as f64 / 100_000.0
+    } else {
+        (int_input / 10_000 + 1) as f64 / 10.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse;
+    use super::*;
+
+    fn score(vector: &str) -> f64 {
+        parse(vector).unwrap().score
+    }
+
+    #[test]
+    fn test_v3_base_scores() {
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"), 9.8);
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H"), 10.0);
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"), 6.1);
+        assert_eq!(score("CVSS:3.1/AV:L/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:H"), 7.8);
+        assert_eq!(score("CVSS:3.0/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:N/A:N"), 5.9);
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:C/C:L/I:N/A:N"), 5.0);
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N"), 0.0);
+    }
+
+    #[test]
+    fn test_roundup() {
+        assert_eq!(roundup(4.02), 4.1);
+        assert_eq!(roundup(4.0), 4.0);
+        // 浮動小数点誤差で 4.000000000000001 になっても 4.1 にしない
+        assert_eq!(roundup(4.000_000_000_000_001), 4.0);
+    }
+}
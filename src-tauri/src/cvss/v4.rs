@@ -0,0 +1,443 @@
+//! CVSS v4.0 のスコア
+//! https://www.first.org/cvss/v4.0/specification-document#CVSS-v4-0-Scoring
+//! FIRST の参照実装（cvss_lookup.js / max_composed.js / max_severity.js）と同じ手順で、
+//! MacroVector のスコアから重大度距離に応じて補間する
+
+use super::{metric, optional_metric, CvssError, Metrics};
+
+const IMPACT: &[&str] = &["H", "L", "N"];
+
+/// 評価に使う値（環境メトリクス M* が指定されていれば基本メトリクスを上書き）
+struct Vector<'a> {
+    metrics: &'a Metrics,
+}
+
+impl<'a> Vector<'a> {
+    /// 基本メトリクスと脅威・環境メトリクスを検証
+    fn new(metrics: &'a Metrics) -> Result<Self, CvssError> {
+        metric(metrics, "AV", &["N", "A", "L", "P"])?;
+        metric(metrics, "AC", &["L", "H"])?;
+        metric(metrics, "AT", &["N", "P"])?;
+        metric(metrics, "PR", &["N", "L", "H"])?;
+        metric(metrics, "UI", &["N", "P", "A"])?;
+        for name in ["VC", "VI", "VA", "SC", "SI", "SA"] {
+            metric(metrics, name, IMPACT)?;
+        }
+
+        optional_metric(metrics, "E", &["A", "P", "U"])?;
+        for name in ["CR", "IR", "AR"] {
+            optional_metric(metrics, name, &["H", "M", "L"])?;
+        }
+        optional_metric(metrics, "MAV", &["N", "A", "L", "P"])?;
+        optional_metric(metrics, "MAC", &["L", "H"])?;
+        optional_metric(metrics, "MAT", &["N", "P"])?;
+        optional_metric(metrics, "MPR", &["N", "L", "H"])?;
+        optional_metric(metrics, "MUI", &["N", "P", "A"])?;
+        for name in ["MVC", "MVI", "MVA", "MSC"] {
+            optional_metric(metrics, name, IMPACT)?;
+        }
+        // 後続システムの完全性・可用性は "S"（Safety）も取りうる
+        for name in ["MSI", "MSA"] {
+            optional_metric(metrics, name, &["S", "H", "L", "N"])?;
+        }
+
+        Ok(Self { metrics })
+    }
+
+    /// 評価に使う値
+    fn m(&self, name: &str) -> &str {
+        let get = |key: &str| {
+            self.metrics
+                .get(key)
+                .map(String::as_str)
+                .filter(|v| *v != "X")
+        };
+        match name {
+            // 未指定の脅威・環境要求は最悪値として扱う
+            "E" => get("E").unwrap_or("A"),
+            "CR" | "IR" | "AR" => get(name).unwrap_or("H"),
+            _ => get(&format!("M{}", name))
+                .or_else(|| get(name))
+                .unwrap_or("N"),
+        }
+    }
+
+    /// MacroVector（EQ1 - EQ6）
+    fn macro_vector(&self) -> [u8; 6] {
+        let (av, pr, ui) = (self.m("AV"), self.m("PR"), self.m("UI"));
+        let eq1 = if av == "N" && pr == "N" && ui == "N" {
+            0
+        } else if (av == "N" || pr == "N" || ui == "N") && av != "P" {
+            1
+        } else {
+            2
+        };
+
+        let eq2 = if self.m("AC") == "L" && self.m("AT") == "N" {
+            0
+        } else {
+            1
+        };
+
+        let (vc, vi, va) = (self.m("VC"), self.m("VI"), self.m("VA"));
+        let eq3 = if vc == "H" && vi == "H" {
+            0
+        } else if vc == "H" || vi == "H" || va == "H" {
+            1
+        } else {
+            2
+        };
+
+        let eq4 = if self.m("SI") == "S" || self.m("SA") == "S" {
+            0
+        } else if self.m("SC") == "H" || self.m("SI") == "H" || self.m("SA") == "H" {
+            1
+        } else {
+            2
+        };
+
+        let eq5 = match self.m("E") {
+            "A" => 0,
+            "P" => 1,
+            _ => 2,
+        };
+
+        let eq6 = if (self.m("CR") == "H" && vc == "H")
+            || (self.m("IR") == "H" && vi == "H")
+            || (self.m("AR") == "H" && va == "H")
+        {
+            0
+        } else {
+            1
+        };
+
+        [eq1, eq2, eq3, eq4, eq5, eq6]
+    }
+}
+
+/// メトリクス値の重大度レベル（0.1 刻みを 10 倍した整数）
+fn level(name: &str, value: &str) -> i32 {
+    match (name, value) {
+        ("AV", "N") | ("PR", "N") | ("UI", "N") | ("AC", "L") | ("AT", "N") => 0,
+        ("AV", "A") | ("PR", "L") | ("UI", "P") | ("AC", "H") | ("AT", "P") => 1,
+        ("AV", "L") | ("PR", "H") | ("UI", "A") => 2,
+        ("AV", "P") => 3,
+        ("VC" | "VI" | "VA", "H") => 0,
+        ("VC" | "VI" | "VA", "L") => 1,
+        ("VC" | "VI" | "VA", "N") => 2,
+        ("SC" | "SI" | "SA", "S") => 0,
+        ("SC" | "SI" | "SA", "H") => 1,
+        ("SC" | "SI" | "SA", "L") => 2,
+        ("SC" | "SI" | "SA", "N") => 3,
+        ("CR" | "IR" | "AR", "H") => 0,
+        ("CR" | "IR" | "AR", "M") => 1,
+        ("CR" | "IR" | "AR", "L") => 2,
+        _ => 0,
+    }
+}
+
+/// 各 EQ の最高重大度ベクター（max_composed.js）
+fn eq1_maxes(eq1: u8) -> &'static [&'static str] {
+    match eq1 {
+        0 => &["AV:N/PR:N/UI:N"],
+        1 => &["AV:A/PR:N/UI:N", "AV:N/PR:L/UI:N", "AV:N/PR:N/UI:P"],
+        _ => &["AV:P/PR:N/UI:N", "AV:A/PR:L/UI:P"],
+    }
+}
+
+fn eq2_maxes(eq2: u8) -> &'static [&'static str] {
+    match eq2 {
+        0 => &["AC:L/AT:N"],
+        _ => &["AC:H/AT:N", "AC:L/AT:P"],
+    }
+}
+
+fn eq3_eq6_maxes(eq3: u8, eq6: u8) -> &'static [&'static str] {
+    match (eq3, eq6) {
+        (0, 0) => &["VC:H/VI:H/VA:H/CR:H/IR:H/AR:H"],
+        (0, _) => &[
+            "VC:H/VI:H/VA:L/CR:M/IR:M/AR:H",
+            "VC:H/VI:H/VA:H/CR:M/IR:M/AR:M",
+        ],
+        (1, 0) => &[
+            "VC:L/VI:H/VA:H/CR:H/IR:H/AR:H",
+            "VC:H/VI:L/VA:H/CR:H/IR:H/AR:H",
+        ],
+        (1, _) => &[
+            "VC:L/VI:H/VA:L/CR:H/IR:M/AR:H",
+            "VC:L/VI:H/VA:H/CR:H/IR:M/AR:M",
+            "VC:H/VI:L/VA:H/CR:M/IR:H/AR:M",
+            "VC:H/VI:L/VA:L/CR:M/IR:H/AR:H",
+            "VC:L/VI:L/VA:H/CR:H/IR:H/AR:M",
+        ],
+        _ => &["VC:L/VI:L/VA:L/CR:H/IR:H/AR:H"],
+    }
+}
+
+fn eq4_maxes(eq4: u8) -> &'static [&'static str] {
+    match eq4 {
+        0 => &["SC:H/SI:S/SA:S"],
+        1 => &["SC:H/SI:H/SA:H"],
+        _ => &["SC:L/SI:L/SA:L"],
+    }
+}
+
+/// 各 EQ の MacroVector 内での最大の重大度距離（max_severity.js）
+fn max_severity_eq1(eq1: u8) -> i32 {
+    [1, 4, 5][eq1 as usize]
+}
+
+fn max_severity_eq2(eq2: u8) -> i32 {
+    [1, 2][eq2 as usize]
+}
+
+fn max_severity_eq3_eq6(eq3: u8, eq6: u8) -> i32 {
+    match (eq3, eq6) {
+        (0, 0) => 7,
+        (0, _) => 6,
+        (1, _) => 8,
+        _ => 10,
+    }
+}
+
+fn max_severity_eq4(eq4: u8) -> i32 {
+    [6, 5, 4][eq4 as usize]
+}
+
+/// スコアを計算（脅威・環境メトリクスが指定されていれば反映する）
+pub fn base_score(metrics: &Metrics) -> Result<f64, CvssError> {
+    let vector = Vector::new(metrics)?;
+
+    // 脆弱なシステムにも後続システムにも影響がなければ 0
+    if ["VC", "VI", "VA", "SC", "SI", "SA"]
+        .iter()
+        .all(|name| vector.m(name) == "N")
+    {
+        return Ok(0.0);
+    }
+
+    let mv = vector.macro_vector();
+    let [eq1, eq2, eq3, eq4, _, eq6] = mv;
+    let value = lookup(mv).ok_or_else(|| CvssError::Malformed(format_macro(mv)))?;
+
+    // 1 段階重大度の低い MacroVector のスコア（存在しない場合は None）
+    let lower = |index: usize| {
+        let mut next = mv;
+        next[index] += 1;
+        lookup(next)
+    };
+    let lower_eq1 = lower(0);
+    let lower_eq2 = lower(1);
+    // EQ3 と EQ6 は組み合わせて扱う
+    let lower_eq3_eq6 = match (eq3, eq6) {
+        (0, 0) => {
+            let left = lower(5);
+            let right = lower(2);
+            match (left, right) {
+                (Some(l), Some(r)) => Some(l.max(r)),
+                (l, r) => l.or(r),
+            }
+        }
+        (1, 0) => lower(5),
+        (0, 1) | (1, 1) => lower(2),
+        _ => None,
+    };
+    let lower_eq4 = lower(3);
+    let lower_eq5 = lower(4);
+
+    // 評価対象以上の重大度を持つ最高重大度ベクターを探す
+    let mut candidates = Vec::new();
+    for a in eq1_maxes(eq1) {
+        for b in eq2_maxes(eq2) {
+            for c in eq3_eq6_maxes(eq3, eq6) {
+                for d in eq4_maxes(eq4) {
+                    candidates.push(format!("{}/{}/{}/{}", a, b, c, d));
+                }
+            }
+        }
+    }
+    let distances = |max_vector: &str| -> Vec<(String, i32)> {
+        max_vector
+            .split('/')
+            .filter_map(|part| part.split_once(':'))
+            .map(|(name, max_value)| {
+                let distance = level(name, vector.m(name)) - level(name, max_value);
+                (name.to_string(), distance)
+            })
+            .collect()
+    };
+    let mut selected = Vec::new();
+    for candidate in &candidates {
+        selected = distances(candidate);
+        if selected.iter().all(|(_, d)| *d >= 0) {
+            break;
+        }
+    }
+    let distance_of = |names: &[&str]| -> i32 {
+        selected
+            .iter()
+            .filter(|(name, _)| names.contains(&name.as_str()))
+            .map(|(_, d)| d)
+            .sum()
+    };
+
+    let distance_eq1 = distance_of(&["AV", "PR", "UI"]);
+    let distance_eq2 = distance_of(&["AC", "AT"]);
+    let distance_eq3_eq6 = distance_of(&["VC", "VI", "VA", "CR", "IR", "AR"]);
+    let distance_eq4 = distance_of(&["SC", "SI", "SA"]);
+
+    // 次の MacroVector までのスコア差に、MacroVector 内での距離の割合を掛ける
+    let parts = [
+        (lower_eq1, distance_eq1, max_severity_eq1(eq1)),
+        (lower_eq2, distance_eq2, max_severity_eq2(eq2)),
+        (
+            lower_eq3_eq6,
+            distance_eq3_eq6,
+            max_severity_eq3_eq6(eq3, eq6),
+        ),
+        (lower_eq4, distance_eq4, max_severity_eq4(eq4)),
+        // EQ5 は MacroVector 内で距離を持たない
+        (lower_eq5, 0, 1),
+    ];
+    let mut existing = 0;
+    let mut total = 0.0;
+    for (lower_score, distance, max_severity) in parts {
+        if let Some(lower_score) = lower_score {
+            existing += 1;
+            total += (value - lower_score) * distance as f64 / max_severity as f64;
+        }
+    }
+    let mean = if existing == 0 {
+        0.0
+    } else {
+        total / existing as f64
+    };
+
+    let score = (value - mean).clamp(0.0, 10.0);
+    Ok(((score + 1e-6) * 10.0).round() / 10.0)
+}
+
+fn format_macro(mv: [u8; 6]) -> String {
+    mv.iter().map(|d| d.to_string()).collect()
+}
+
+/// MacroVector のスコア（cvss_lookup.js）
+fn lookup(mv: [u8; 6]) -> Option<f64> {
+    let key = format_macro(mv);
+    LOOKUP
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, score)| *score)
+}
+
+#[rustfmt::skip]
+const LOOKUP: &[(&str, f64)] = &[
+    ("000000", 10.0), ("000001", 9.9), ("000010", 9.8), ("000011", 9.5), ("000020", 9.5), ("000021", 9.2),
+    ("000100", 10.0), ("000101", 9.6), ("000110", 9.3), ("000111", 8.7), ("000120", 9.1), ("000121", 8.1),
+    ("000200", 9.3), ("000201", 9.0), ("000210", 8.9), ("000211", 8.0), ("000220", 8.1), ("000221", 6.8),
+    ("001000", 9.8), ("001001", 9.5), ("001010", 9.5), ("001011", 9.2), ("001020", 9.0), ("001021", 8.4),
+    ("001100", 9.3), ("001101", 9.2), ("001110", 8.9), ("001111", 8.1), ("001120", 8.1), ("001121", 6.5),
+    ("001200", 8.8), ("001201", 8.0), ("001210", 7.8), ("001211", 7.0), ("001220", 6.9), ("001221", 4.8),
+    ("002001", 9.2), ("002011", 8.2), ("002021", 7.2), ("002101", 7.9), ("002111", 6.9), ("002121", 5.0),
+    ("002201", 6.9), ("002211", 5.5), ("002221", 2.7),
+    ("010000", 9.9), ("010001", 9.7), ("010010", 9.5), ("010011", 9.2), ("010020", 9.2), ("010021", 8.5),
+    ("010100", 9.5), ("010101", 9.1), ("010110", 9.0), ("010111", 8.3), ("010120", 8.4), ("010121", 7.1),
+    ("010200", 9.2), ("010201", 8.1), ("010210", 8.2), ("010211", 7.1), ("010220", 7.2), ("010221", 5.3),
+    ("011000", 9.5), ("011001", 9.3), ("011010", 9.2), ("011011", 8.5), ("011020", 8.5), ("011021", 7.3),
+    ("011100", 9.2), ("011101", 8.2), ("011110", 8.0), ("011111", 7.2), ("011120", 7.0), ("011121", 5.9),
+    ("011200", 8.4), ("011201", 7.0), ("011210", 7.1), ("011211", 5.2), ("011220", 5.0), ("011221", 3.0),
+    ("012001", 8.6), ("012011", 7.5), ("012021", 5.2), ("012101", 7.1), ("012111", 5.2), ("012121", 2.9),
+    ("012201", 6.3), ("012211", 2.9), ("012221", 1.7),
+    ("100000", 9.8), ("100001", 9.5), ("100010", 9.4), ("100011", 8.7), ("100020", 9.1), ("100021", 8.1),
+    ("100100", 9.4), ("100101", 8.9), ("100110", 8.6), ("100111", 7.4), ("100120", 7.7), ("100121", 6.4),
+    ("100200", 8.7), ("100201", 7.5), ("100210", 7.4), ("100211", 6.3), ("100220", 6.3), ("100221", 4.9),
+    ("101000", 9.4), ("101001", 8.9), ("101010", 8.8), ("101011", 7.7), ("101020", 7.6), ("101021", 6.7),
+    ("101100", 8.6), ("101101", 7.6), ("101110", 7.4), ("101111", 5.8), ("101120", 5.9), ("101121", 5.0),
+    ("101200", 7.2), ("101201", 5.7), ("101210", 5.7), ("101211", 5.2), ("101220", 5.2), ("101221", 2.5),
+    ("102001", 8.3), ("102011", 7.0), ("102021", 5.4), ("102101", 6.5), ("102111", 5.8), ("102121", 2.6),
+    ("102201", 5.3), ("102211", 2.1), ("102221", 1.3),
+    ("110000", 9.5), ("110001", 9.0), ("110010", 8.8), ("110011", 7.6), ("110020", 7.6), ("110021", 7.0),
+    ("110100", 9.0), ("110101", 7.7), ("110110", 7.5), ("110111", 6.2), ("110120", 6.1), ("110121", 5.3),
+    ("110200", 7.7), ("110201", 6.6), ("110210", 6.8), ("110211", 5.9), ("110220", 5.2), ("110221", 3.0),
+    ("111000", 8.9), ("111001", 7.8), ("111010", 7.6), ("111011", 6.7), ("111020", 6.2), ("111021", 5.8),
+    ("111100", 7.4), ("111101", 5.9), ("111110", 5.7), ("111111", 5.7), ("111120", 4.7), ("111121", 2.3),
+    ("111200", 6.1), ("111201", 5.2), ("111210", 5.7), ("111211", 2.9), ("111220", 2.4), ("111221", 1.6),
+    ("112001", 7.1), ("112011", 5.9), ("112021", 3.0), ("112101", 5.8), ("112111", 2.6), ("112121", 1.5),
+    ("112201", 2.3), ("112211", 1.3), ("112221", 0.6),
+    ("200000", 9.3), ("200001", 8.7), ("200010", 8.6), ("200011", 7.2), ("200020", 7.5), ("200021", 5.8),
+    ("200100", 8.6), ("200101", 7.4), ("200110", 7.4), ("200111", 6.1), ("200120", 5.6), ("200121", 3.4),
+    ("200200", 7.0), ("200201", 5.4), ("200210", 5.2), ("200211", 4.0), ("200220", 4.0), ("200221", 2.2),
+    ("201000", 8.5), ("201001", 7.5), ("201010", 7.4), ("201011", 5.5), ("201020", 6.2), ("201021", 5.1),
+    ("201100", 7.2), ("201101", 5.7), ("201110", 5.5), ("201111", 4.1), ("201120", 4.6), ("201121", 1.9),
+    ("201200", 5.3), ("201201", 3.6), ("201210", 3.4), ("201211", 1.9), ("201220", 1.9), ("201221", 0.8),
+    ("202001", 6.4), ("202011", 5.1), ("202021", 2.0), ("202101", 4.7), ("202111", 2.1), ("202121", 1.1),
+    ("202201", 2.4), ("202211", 0.9), ("202221", 0.4),
+    ("210000", 8.8), ("210001", 7.5), ("210010", 7.3), ("210011", 5.3), ("210020", 6.0), ("210021", 5.0),
+    ("210100", 7.3), ("210101", 5.5), ("210110", 5.9), ("210111", 4.0), ("210120", 4.1), ("210121", 2.0),
+    ("210200", 5.4), ("210201", 4.3), ("210210", 4.5), ("210211", 2.2), ("210220", 2.0), ("210221", 1.1),
+    ("211000", 7.5), ("211001", 5.5), ("211010", 5.8), ("211011", 4.5), ("211020", 4.0), ("211021", 2.1),
+    ("211100", 6.1), ("211101", 5.1), ("211110", 4.8), ("211111", 1.8), ("211120", 2.0), ("211121", 0.9),
+    ("211200", 4.6), ("211201", 1.8), ("211210", 1.7), ("211211", 0.7), ("211220", 0.8), ("211221", 0.2),
+    ("212001", 5.3), ("212011", 2.4), ("212021", 1.4), ("212101", 2.4), ("212111", 1.2), ("212121", 0.5),
+    ("212201", 1.0), ("212211", 0.3), ("212221", 0.1),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse;
+    use super::*;
+
+    fn score(vector: &str) -> f64 {
+        parse(vector).unwrap().score
+    }
+
+    #[test]
+    fn test_lookup_table_complete() {
+        // EQ3 = 2 かつ EQ6 = 0 の組み合わせは存在しない
+        assert_eq!(LOOKUP.len(), 270);
+        assert_eq!(lookup([0, 0, 2, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn test_v4_base_scores() {
+        assert_eq!(
+            score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:H/SI:H/SA:H"),
+            10.0
+        );
+        assert_eq!(
+            score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N"),
+            9.3
+        );
+        assert_eq!(
+            score("CVSS:4.0/AV:L/AC:L/AT:N/PR:L/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N"),
+            8.5
+        );
+        assert_eq!(
+            score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:L/VI:L/VA:N/SC:N/SI:N/SA:N"),
+            6.9
+        );
+        assert_eq!(
+            score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:A/VC:N/VI:N/VA:N/SC:L/SI:L/SA:N"),
+            5.1
+        );
+        assert_eq!(
+            score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:N/VI:N/VA:N/SC:N/SI:N/SA:N"),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_v4_threat_and_environmental() {
+        // 攻撃コードが未報告（E:U）なら MacroVector が下がる
+        assert_eq!(
+            score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N/E:U"),
+            8.1
+        );
+        // E:X は未指定と同じ
+        assert_eq!(
+            score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N/E:X"),
+            9.3
+        );
+        assert!(parse("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:S/SA:N").is_err());
+    }
+}
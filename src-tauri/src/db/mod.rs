@@ -2,7 +2,7 @@ pub mod models;
 pub mod rfc_queries;
 pub mod vuln_queries;
 
-use rusqlite::{Connection, Result};
+use rusqlite::{Connection, OptionalExtension, Result};
 
 /// Initialize the database with required tables
 pub fn init_db(db_path: &str) -> Result<()> {
@@ -143,16 +143,187 @@ pub fn init_db(db_path: &str) -> Result<()> {
         CREATE INDEX IF NOT EXISTS idx_scan_history_time ON scan_history(scanned_at DESC);
         "
     )?;
+
+    // Structured OSV affected ranges (used for offline matching)
+    conn.execute_batch(
+        "
+        -- 影響パッケージテーブル（OSV の affected[] を 1 パッケージ 1 行で保持）
+        -- package は照合用に正規化した名前、name はアドバイザリ上の表記
+        CREATE TABLE IF NOT EXISTS vulnerability_affected (
+            vuln_id TEXT NOT NULL,
+            affected_index INTEGER NOT NULL,
+            ecosystem TEXT NOT NULL,
+            package TEXT NOT NULL,
+            name TEXT NOT NULL,
+            purl TEXT,
+            PRIMARY KEY (vuln_id, affected_index),
+            FOREIGN KEY (vuln_id) REFERENCES vulnerabilities(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_vuln_affected_package ON vulnerability_affected(ecosystem, package);
+
+        -- 影響範囲テーブル（OSV の affected[].ranges[].events を 1 イベント 1 行で保持）
+        CREATE TABLE IF NOT EXISTS vulnerability_ranges (
+            vuln_id TEXT NOT NULL,
+            ecosystem TEXT NOT NULL,
+            package TEXT NOT NULL,
+            affected_index INTEGER NOT NULL,
+            range_index INTEGER NOT NULL,
+            range_type TEXT NOT NULL,
+            event_index INTEGER NOT NULL,
+            event_type TEXT NOT NULL,
+            version TEXT NOT NULL,
+            PRIMARY KEY (vuln_id, affected_index, range_index, event_index),
+            FOREIGN KEY (vuln_id) REFERENCES vulnerabilities(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_vuln_ranges_package ON vulnerability_ranges(ecosystem, package);
+
+        -- 影響バージョン一覧テーブル（OSV の affected[].versions）
+        CREATE TABLE IF NOT EXISTS vulnerability_versions (
+            vuln_id TEXT NOT NULL,
+            ecosystem TEXT NOT NULL,
+            package TEXT NOT NULL,
+            affected_index INTEGER NOT NULL,
+            version TEXT NOT NULL,
+            PRIMARY KEY (vuln_id, affected_index, version),
+            FOREIGN KEY (vuln_id) REFERENCES vulnerabilities(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_vuln_versions_package ON vulnerability_versions(ecosystem, package);
+
+        -- 別名テーブル（OSV の aliases。CVE / GHSA / RUSTSEC などの同一の問題を指す ID）
+        CREATE TABLE IF NOT EXISTS vulnerability_aliases (
+            vuln_id TEXT NOT NULL,
+            alias TEXT NOT NULL,
+            PRIMARY KEY (vuln_id, alias),
+            FOREIGN KEY (vuln_id) REFERENCES vulnerabilities(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_vuln_aliases_alias ON vulnerability_aliases(alias);
+
+        -- 抑制ルールテーブル（既知のリスクの受容。プロジェクト内の .paperstack-ignore.toml と併用）
+        CREATE TABLE IF NOT EXISTS suppressions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            vuln_id TEXT,
+            package TEXT,
+            ecosystem TEXT,
+            path TEXT,
+            directory TEXT,
+            expires TEXT,
+            reason TEXT NOT NULL,
+            created_at TEXT DEFAULT (datetime('now'))
+        );
+
+        -- スキャン実行テーブル（実行ごとの検出結果を scan_findings に保持し、差分の比較に使う）
+        CREATE TABLE IF NOT EXISTS scan_runs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            directory TEXT NOT NULL,
+            total_packages INTEGER NOT NULL DEFAULT 0,
+            vuln_count INTEGER NOT NULL DEFAULT 0,
+            scanned_at TEXT DEFAULT (datetime('now'))
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_scan_runs_dir ON scan_runs(directory, id DESC);
+
+        CREATE TABLE IF NOT EXISTS scan_findings (
+            run_id INTEGER NOT NULL,
+            project TEXT NOT NULL,
+            source_file TEXT NOT NULL,
+            ecosystem TEXT NOT NULL,
+            package TEXT NOT NULL,
+            version TEXT NOT NULL,
+            vuln_id TEXT NOT NULL,
+            canonical_id TEXT NOT NULL,
+            severity TEXT NOT NULL,
+            suppressed INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (run_id) REFERENCES scan_runs(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_scan_findings_run ON scan_findings(run_id);
+
+        -- 監視対象プロジェクト（バックグラウンドで定期的に再スキャンする）
+        CREATE TABLE IF NOT EXISTS watched_projects (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            directory TEXT NOT NULL UNIQUE,
+            interval_hours INTEGER NOT NULL DEFAULT 24,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            last_scanned_at TEXT,
+            last_run_id INTEGER,
+            last_error TEXT,
+            created_at TEXT DEFAULT (datetime('now'))
+        );
+        "
+    )?;
+
+    // Migration: Add CVSS vector / version columns to vulnerabilities
+    let _ = conn.execute(
+        "ALTER TABLE vulnerabilities ADD COLUMN cvss_vector TEXT",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE vulnerabilities ADD COLUMN cvss_version TEXT",
+        [],
+    );
+
+    // Migration: Add severity_source column to vulnerabilities
+    let _ = conn.execute(
+        "ALTER TABLE vulnerabilities ADD COLUMN severity_source TEXT",
+        [],
+    );
+
+    // Migration: Add modified column to vulnerabilities (keep the newest OSV record)
+    let _ = conn.execute("ALTER TABLE vulnerabilities ADD COLUMN modified TEXT", []);
+
+    // Migration: Add canonical_id column to vulnerabilities (alias deduplication)
+    // 別名のグループを索引で引けるよう、別名のない脆弱性も自身の ID を持つ NOT NULL の列にする
+    let canonical_not_null: Option<bool> = conn
+        .query_row(
+            "SELECT \"notnull\" FROM pragma_table_info('vulnerabilities') WHERE name = 'canonical_id'",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    match canonical_not_null {
+        None => conn.execute_batch(
+            "ALTER TABLE vulnerabilities ADD COLUMN canonical_id TEXT NOT NULL DEFAULT ''",
+        )?,
+        // NULL を許す列で作成済みのデータベースは、値を移して作り直す
+        Some(false) => conn.execute_batch(
+            "BEGIN;
+             DROP INDEX IF EXISTS idx_vulnerabilities_canonical;
+             ALTER TABLE vulnerabilities RENAME COLUMN canonical_id TO canonical_id_nullable;
+             ALTER TABLE vulnerabilities ADD COLUMN canonical_id TEXT NOT NULL DEFAULT '';
+             UPDATE vulnerabilities SET canonical_id = COALESCE(canonical_id_nullable, '');
+             ALTER TABLE vulnerabilities DROP COLUMN canonical_id_nullable;
+             COMMIT;",
+        )?,
+        Some(true) => {}
+    }
+    conn.execute_batch(
+        "UPDATE vulnerabilities SET canonical_id = id WHERE canonical_id = '';
+         CREATE INDEX IF NOT EXISTS idx_vulnerabilities_canonical ON vulnerabilities(canonical_id);",
+    )?;
+
+    // Migration: Add project column to scan_history (sub-project path for monorepo scans)
+    let _ = conn.execute(
+        "ALTER TABLE scan_history ADD COLUMN project TEXT NOT NULL DEFAULT '.'",
+        [],
+    );
     
     Ok(())
 }
 
 /// Get a database connection
+/// SQLite は接続ごとに外部キー制約が無効なので、子テーブルの ON DELETE CASCADE が効くように有効にする
 pub fn get_connection(db_path: &str) -> Result<Connection> {
-    Connection::open(db_path)
+    let conn = Connection::open(db_path)?;
+    conn.execute_batch("PRAGMA foreign_keys = ON")?;
+    Ok(conn)
 }
 
 /// Get papers from database, optionally filtered by category
+#[cfg(feature = "desktop")]
 pub fn get_papers_from_db(
     db_path: &str,
     category: Option<&str>,
@@ -223,6 +394,7 @@ pub fn get_papers_from_db(
     Ok(papers_with_tasks)
 }
 
+#[cfg(feature = "desktop")]
 fn get_tasks_for_paper(conn: &Connection, paper_id: &str) -> Result<Vec<String>> {
     let mut stmt = conn.prepare(
         "SELECT task_slug FROM paper_tasks WHERE paper_id = ?1"
@@ -273,6 +445,7 @@ pub fn paper_exists(conn: &Connection, paper_id: &str) -> Result<bool> {
 }
 
 /// Update paper summary
+#[cfg(feature = "desktop")]
 pub fn update_paper_summary(db_path: &str, paper_id: &str, summary: &str) -> Result<()> {
     let conn = get_connection(db_path)?;
     conn.execute(
@@ -292,6 +465,7 @@ pub fn update_paper_title_ja(conn: &Connection, paper_id: &str, title_ja: &str)
 }
 
 /// Get a single paper by ID
+#[cfg(feature = "desktop")]
 pub fn get_paper_by_id(db_path: &str, paper_id: &str) -> Result<Option<models::Paper>> {
     let conn = get_connection(db_path)?;
     
@@ -351,7 +525,57 @@ mod tests {
         // If we got here, init_db succeeded
         cleanup_test_db(&db_path);
     }
+
+    #[test]
+    fn test_canonical_id_migration_backfills_nullable_column() {
+        let counter = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let db_path = format!("/tmp/test_paper_news_canonical_{}.db", counter);
+        let _ = fs::remove_file(&db_path);
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE vulnerabilities (
+                     id TEXT PRIMARY KEY, source TEXT NOT NULL, severity TEXT NOT NULL,
+                     cvss_score REAL, title TEXT NOT NULL, description TEXT,
+                     affected_package TEXT NOT NULL, affected_ecosystem TEXT NOT NULL,
+                     affected_versions TEXT, fixed_versions TEXT, published_at TEXT,
+                     reference_urls TEXT DEFAULT '[]', fetched_at TEXT, canonical_id TEXT
+                 );
+                 CREATE INDEX idx_vulnerabilities_canonical ON vulnerabilities(canonical_id);
+                 INSERT INTO vulnerabilities (id, source, severity, title, affected_package, affected_ecosystem, canonical_id)
+                 VALUES ('GHSA-1', 'osv', 'high', 't', 'p', 'npm', 'CVE-1'),
+                        ('PYSEC-1', 'osv', 'high', 't', 'p', 'PyPI', NULL);",
+            )
+            .unwrap();
+        }
+        init_db(&db_path).unwrap();
+        // 2 回目の初期化では何もしない
+        init_db(&db_path).unwrap();
+
+        let conn = get_connection(&db_path).unwrap();
+        let not_null: bool = conn
+            .query_row(
+                "SELECT \"notnull\" FROM pragma_table_info('vulnerabilities') WHERE name = 'canonical_id'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(not_null);
+        let canonical = |id: &str| -> String {
+            conn.query_row(
+                "SELECT canonical_id FROM vulnerabilities WHERE id = ?1",
+                [id],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(canonical("GHSA-1"), "CVE-1");
+        assert_eq!(canonical("PYSEC-1"), "PYSEC-1");
+
+        cleanup_test_db(&db_path);
+    }
     
+    #[cfg(feature = "desktop")]
     #[test]
     fn test_upsert_and_get_paper() {
         let db_path = create_test_db("upsert");
@@ -382,6 +606,7 @@ mod tests {
         cleanup_test_db(&db_path);
     }
     
+    #[cfg(feature = "desktop")]
     #[test]
     fn test_paper_tasks() {
         let db_path = create_test_db("tasks");
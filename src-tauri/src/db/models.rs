@@ -1,3 +1,5 @@
+use crate::scanner::DevFindings;
+use crate::version::VersionMatch;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -24,6 +26,7 @@ pub struct Paper {
     pub tasks: Vec<String>,
 }
 
+#[cfg(feature = "desktop")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Category {
     pub id: String,
@@ -31,6 +34,7 @@ pub struct Category {
     pub tasks: Vec<String>,
 }
 
+#[cfg(feature = "desktop")]
 impl Category {
     pub fn all_categories() -> Vec<Category> {
         vec![
@@ -107,6 +111,7 @@ pub struct Rfc {
 }
 
 /// RFCフィルター条件
+#[cfg(feature = "desktop")]
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RfcFilter {
     pub search: Option<String>,
@@ -121,6 +126,7 @@ pub struct RfcFilter {
 }
 
 /// RFC一覧レスポンス
+#[cfg(feature = "desktop")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RfcListResponse {
     pub rfcs: Vec<Rfc>,
@@ -130,6 +136,7 @@ pub struct RfcListResponse {
 }
 
 /// RFCブックマーク
+#[cfg(feature = "desktop")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RfcBookmark {
     #[serde(rename = "rfcId")]
@@ -140,6 +147,7 @@ pub struct RfcBookmark {
 }
 
 /// RFC閲覧履歴
+#[cfg(feature = "desktop")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RfcHistory {
     #[serde(rename = "rfcId")]
@@ -149,6 +157,7 @@ pub struct RfcHistory {
 }
 
 /// 要約レベル
+#[cfg(feature = "desktop")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SummaryLevel {
@@ -248,13 +257,22 @@ impl RfcCategory {
 // ============================================================================
 
 /// 脆弱性情報
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Vulnerability {
-    pub id: String,                              // CVE-2024-XXXXX or GHSA-XXXX
-    pub source: String,                          // "osv", "nvd", "github"
-    pub severity: String,                        // "critical", "high", "medium", "low"
+    pub id: String,       // CVE-2024-XXXXX or GHSA-XXXX
+    pub source: String,   // "osv", "nvd", "github"
+    pub severity: String, // "critical", "high", "medium", "low", "unknown"
+    /// 深刻度の算出元（"cvss" / "database_specific" / "ecosystem_specific" / "unknown"）
+    #[serde(rename = "severitySource", default)]
+    pub severity_source: Option<String>,
     #[serde(rename = "cvssScore")]
     pub cvss_score: Option<f64>,
+    /// スコアの算出元の CVSS ベクター
+    #[serde(rename = "cvssVector", default)]
+    pub cvss_vector: Option<String>,
+    /// CVSS のバージョン（"2.0" / "3.0" / "3.1" / "4.0"）
+    #[serde(rename = "cvssVersion", default)]
+    pub cvss_version: Option<String>,
     pub title: String,
     pub description: Option<String>,
     #[serde(rename = "affectedPackage")]
@@ -267,12 +285,34 @@ pub struct Vulnerability {
     pub fixed_versions: Option<String>,
     #[serde(rename = "publishedAt")]
     pub published_at: Option<String>,
+    /// OSV レコードの最終更新日時（古いレコードで上書きしないために使う）
+    #[serde(default)]
+    pub modified: Option<String>,
     pub references: Vec<String>,
     #[serde(rename = "fetchedAt")]
     pub fetched_at: Option<String>,
+    /// アドバイザリが影響するすべてのパッケージ（affected_package はその代表）
+    #[serde(rename = "affectedPackages", default)]
+    pub affected_packages: Vec<AffectedPackage>,
+    /// 同じ問題を指す別の ID（CVE / GHSA / RUSTSEC など）
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+/// 脆弱性が影響するパッケージと、そのパッケージでの影響範囲
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AffectedPackage {
+    pub ecosystem: String,
+    pub name: String,
+    pub purl: Option<String>,
+    #[serde(rename = "affectedVersions")]
+    pub affected_versions: Option<String>,
+    #[serde(rename = "fixedVersions")]
+    pub fixed_versions: Option<String>,
 }
 
 /// 脆弱性フィルター条件
+#[cfg(feature = "desktop")]
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct VulnFilter {
     pub ecosystem: Option<String>,
@@ -281,17 +321,120 @@ pub struct VulnFilter {
 }
 
 /// 脆弱性マッチ結果（スキャン時）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct VulnMatch {
     #[serde(rename = "packageName")]
     pub package_name: String,
     #[serde(rename = "installedVersion")]
     pub installed_version: String,
+    /// 依存関係を検出したロックファイル
+    #[serde(rename = "sourceFile")]
+    pub source_file: String,
+    /// 検出したサブプロジェクト（スキャン対象からの相対パス、トップレベルは "."）
+    #[serde(default)]
+    pub project: String,
+    /// インストール済みバージョンが OSV の影響範囲に含まれるか（not_affected は誤検知の可能性、unpinned は範囲指定で未固定）
+    #[serde(rename = "versionMatch", default)]
+    pub version_match: VersionMatch,
     pub vulnerability: Vulnerability,
+    /// 適用された抑制（既知のリスクとして受容済み。結果からは削除せず印を付ける）
+    #[serde(default)]
+    pub suppression: Option<Suppression>,
+    /// マニフェストの直接依存から脆弱なパッケージまでの経路（"name@version"、分からなければ空）
+    #[serde(rename = "dependencyPath", default)]
+    pub dependency_path: Vec<String>,
+    /// 開発時のみの依存関係で見つかった検出
+    #[serde(default)]
+    pub dev: bool,
+    /// 省略可能な依存関係で見つかった検出
+    #[serde(default)]
+    pub optional: bool,
+}
+
+/// テスト用の検出を組み立てる
+#[cfg(test)]
+impl VulnMatch {
+    /// /work/app/package-lock.json の npm パッケージが影響範囲に含まれる検出
+    pub fn fixture(id: &str, package: &str, version: &str) -> Self {
+        VulnMatch {
+            package_name: package.to_string(),
+            installed_version: version.to_string(),
+            source_file: "/work/app/package-lock.json".to_string(),
+            project: ".".to_string(),
+            version_match: VersionMatch::Affected,
+            vulnerability: Vulnerability {
+                id: id.to_string(),
+                title: format!("{} title", id),
+                affected_package: package.to_string(),
+                affected_ecosystem: "npm".to_string(),
+                references: vec![format!("https://osv.dev/vulnerability/{}", id)],
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    pub fn with_severity(mut self, severity: &str, cvss: Option<f64>) -> Self {
+        self.vulnerability.severity = severity.to_string();
+        self.vulnerability.cvss_score = cvss;
+        self
+    }
+
+    pub fn with_fixed(mut self, fixed_versions: &str) -> Self {
+        self.vulnerability.fixed_versions = Some(fixed_versions.to_string());
+        self
+    }
+
+    pub fn with_aliases(mut self, aliases: &[&str]) -> Self {
+        self.vulnerability.aliases = aliases.iter().map(|a| a.to_string()).collect();
+        self
+    }
+
+    pub fn with_ecosystem(mut self, ecosystem: &str) -> Self {
+        self.vulnerability.affected_ecosystem = ecosystem.to_string();
+        self
+    }
+
+    /// 検出したロックファイルとサブプロジェクト
+    pub fn with_source(mut self, source_file: &str, project: &str) -> Self {
+        self.source_file = source_file.to_string();
+        self.project = project.to_string();
+        self
+    }
+}
+
+/// 抑制ルール（.paperstack-ignore.toml またはデータベース）
+/// 指定した条件（ID / パッケージ / エコシステム / パス）をすべて満たす検出に適用する
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Suppression {
+    /// データベースの行 ID（ファイル由来は None）
+    #[serde(default)]
+    pub id: Option<i64>,
+    /// 脆弱性 ID（別名でも一致）
+    #[serde(rename = "vulnId", default)]
+    pub vuln_id: Option<String>,
+    #[serde(default)]
+    pub package: Option<String>,
+    #[serde(default)]
+    pub ecosystem: Option<String>,
+    /// スキャン対象からの相対パス（サブプロジェクトまたはロックファイル）
+    #[serde(default)]
+    pub path: Option<String>,
+    /// 適用するスキャン対象ディレクトリ（データベース由来のみ。None はすべて）
+    #[serde(default)]
+    pub directory: Option<String>,
+    /// 有効期限（YYYY-MM-DD、この日まで有効）
+    #[serde(default)]
+    pub expires: Option<String>,
+    /// 受容した理由
+    pub reason: String,
+    /// "file" / "database"
+    #[serde(default)]
+    pub source: String,
 }
 
 /// スキャン結果
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ScanResult {
     pub directory: String,
     pub ecosystems: Vec<String>,
@@ -300,9 +443,72 @@ pub struct ScanResult {
     pub scanned_at: String,
     #[serde(rename = "totalPackages")]
     pub total_packages: i32,
+    /// サブプロジェクトごとの集計
+    #[serde(default)]
+    pub projects: Vec<ScanProject>,
+    /// スキャン対象に含まれていたが照会できなかったもの（SBOM の未対応 purl など）
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// 保存したスキャン実行の ID（差分の比較に使う）
+    #[serde(rename = "runId", default)]
+    pub run_id: Option<i64>,
+    /// 依存関係ごとのアップグレード計画
+    #[serde(default)]
+    pub remediation: Vec<Remediation>,
+    /// 開発時のみの依存関係の検出の扱い
+    #[serde(rename = "devFindings", default)]
+    pub dev_findings: DevFindings,
+}
+
+/// 依存関係ごとのアップグレード計画
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Remediation {
+    pub project: String,
+    #[serde(rename = "sourceFile")]
+    pub source_file: String,
+    pub ecosystem: String,
+    #[serde(rename = "packageName")]
+    pub package_name: String,
+    #[serde(rename = "installedVersion")]
+    pub installed_version: String,
+    /// 該当する脆弱性のうち最も高い深刻度
+    pub severity: String,
+    /// 解消できる脆弱性をすべて解消する最小のバージョン（修正がなければ None）
+    #[serde(rename = "targetVersion")]
+    pub target_version: Option<String>,
+    /// メジャーバージョンをまたぐ（互換性のない変更を含む可能性がある）
+    #[serde(rename = "majorBump")]
+    pub major_bump: bool,
+    /// target_version で解消される脆弱性
+    pub fixes: Vec<String>,
+    /// target_version では解消されない脆弱性
+    pub unfixed: Vec<String>,
+}
+
+/// サブプロジェクトごとのスキャン集計
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanProject {
+    /// スキャン対象からの相対パス（トップレベルは "."）
+    pub path: String,
+    pub ecosystems: Vec<String>,
+    #[serde(rename = "totalPackages")]
+    pub total_packages: i32,
+    #[serde(rename = "vulnCount")]
+    pub vuln_count: i32,
+}
+
+/// OSV データベースエクスポートの取り込み結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OsvImportSummary {
+    /// 保存したエントリ数
+    pub imported: i64,
+    /// 解析できずに読み飛ばしたエントリ数
+    pub skipped: i64,
+    pub ecosystems: Vec<String>,
 }
 
 /// 脆弱性一覧レスポンス
+#[cfg(feature = "desktop")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VulnListResponse {
     pub vulnerabilities: Vec<Vulnerability>,
@@ -312,13 +518,85 @@ pub struct VulnListResponse {
 }
 
 /// スキャン履歴
+#[cfg(feature = "desktop")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanHistory {
     pub id: i64,
     pub directory: String,
+    /// サブプロジェクト（スキャン対象からの相対パス、トップレベルは "."）
+    pub project: String,
     pub ecosystem: String,
     #[serde(rename = "vulnCount")]
     pub vuln_count: i32,
     #[serde(rename = "scannedAt")]
     pub scanned_at: String,
 }
+
+/// 検出結果を保存したスキャンの実行
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanRun {
+    pub id: i64,
+    /// スキャン対象（正規化した絶対パス）
+    pub directory: String,
+    #[serde(rename = "totalPackages")]
+    pub total_packages: i32,
+    /// 抑制済みを除いた検出数
+    #[serde(rename = "vulnCount")]
+    pub vuln_count: i32,
+    #[serde(rename = "scannedAt")]
+    pub scanned_at: String,
+}
+
+/// スキャンの実行ごとに保存する検出
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanFinding {
+    pub project: String,
+    #[serde(rename = "sourceFile")]
+    pub source_file: String,
+    pub ecosystem: String,
+    #[serde(rename = "packageName")]
+    pub package_name: String,
+    #[serde(rename = "installedVersion")]
+    pub installed_version: String,
+    #[serde(rename = "vulnId")]
+    pub vuln_id: String,
+    /// 別名をまとめた正規 ID（差分の照合に使う）
+    #[serde(rename = "canonicalId")]
+    pub canonical_id: String,
+    pub severity: String,
+    pub suppressed: bool,
+}
+
+/// 2 回のスキャンの差分
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanDiff {
+    pub base: ScanRun,
+    pub target: ScanRun,
+    /// target で新たに検出されたもの
+    pub new: Vec<ScanFinding>,
+    /// base にあって target でなくなったもの
+    pub fixed: Vec<ScanFinding>,
+    /// 両方に残っているもの（target 側の内容）
+    #[serde(rename = "stillPresent")]
+    pub still_present: Vec<ScanFinding>,
+}
+
+/// 監視対象プロジェクト（バックグラウンドで定期的に再スキャンする）
+#[cfg(feature = "desktop")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchedProject {
+    pub id: i64,
+    pub directory: String,
+    /// 再スキャンの間隔（時間）
+    #[serde(rename = "intervalHours")]
+    pub interval_hours: i64,
+    pub enabled: bool,
+    #[serde(rename = "lastScannedAt")]
+    pub last_scanned_at: Option<String>,
+    /// 最後に保存したスキャン実行の ID
+    #[serde(rename = "lastRunId")]
+    pub last_run_id: Option<i64>,
+    /// 最後の再スキャンが失敗した場合のエラー
+    #[serde(rename = "lastError")]
+    pub last_error: Option<String>,
+}
@@ -1,7 +1,9 @@
 //! RFC Database Queries
 
 use rusqlite::{Connection, Result, params};
-use super::models::{Rfc, RfcFilter, RfcBookmark, RfcHistory, RfcListResponse};
+use super::models::Rfc;
+#[cfg(feature = "desktop")]
+use super::models::{RfcFilter, RfcBookmark, RfcHistory, RfcListResponse};
 
 /// Insert or update an RFC
 pub fn upsert_rfc(conn: &Connection, rfc: &Rfc) -> Result<()> {
@@ -43,6 +45,7 @@ pub fn insert_rfc_category(conn: &Connection, rfc_id: &str, category: &str) -> R
 }
 
 /// Get RFCs with filtering and pagination
+#[cfg(feature = "desktop")]
 pub fn get_rfcs(
     conn: &Connection,
     filter: Option<&RfcFilter>,
@@ -237,6 +240,7 @@ fn is_rfc_bookmarked(conn: &Connection, rfc_id: &str) -> Result<bool> {
 }
 
 /// Update RFC summary (easy/normal/technical)
+#[cfg(feature = "desktop")]
 pub fn update_rfc_summary(
     conn: &Connection,
     rfc_id: &str,
@@ -258,6 +262,7 @@ pub fn update_rfc_summary(
 }
 
 /// Update RFC implementation guide
+#[cfg(feature = "desktop")]
 pub fn update_rfc_implementation_guide(conn: &Connection, rfc_id: &str, guide: &str) -> Result<()> {
     conn.execute(
         "UPDATE rfcs SET implementation_guide = ?1, updated_at = datetime('now') WHERE id = ?2",
@@ -267,6 +272,7 @@ pub fn update_rfc_implementation_guide(conn: &Connection, rfc_id: &str, guide: &
 }
 
 /// Update RFC Japanese abstract
+#[cfg(feature = "desktop")]
 pub fn update_rfc_abstract_ja(conn: &Connection, rfc_id: &str, abstract_ja: &str) -> Result<()> {
     conn.execute(
         "UPDATE rfcs SET abstract_ja = ?1, updated_at = datetime('now') WHERE id = ?2",
@@ -276,6 +282,7 @@ pub fn update_rfc_abstract_ja(conn: &Connection, rfc_id: &str, abstract_ja: &str
 }
 
 /// Update RFC Japanese title
+#[cfg(feature = "desktop")]
 pub fn update_rfc_title_ja(conn: &Connection, rfc_id: &str, title_ja: &str) -> Result<()> {
     conn.execute(
         "UPDATE rfcs SET title_ja = ?1, updated_at = datetime('now') WHERE id = ?2",
@@ -290,6 +297,7 @@ pub fn update_rfc_title_ja(conn: &Connection, rfc_id: &str, title_ja: &str) -> R
 // ============================================================================
 
 /// Add RFC bookmark
+#[cfg(feature = "desktop")]
 pub fn add_rfc_bookmark(conn: &Connection, rfc_id: &str, memo: Option<&str>) -> Result<()> {
     conn.execute(
         "INSERT OR REPLACE INTO rfc_bookmarks (rfc_id, memo, created_at) VALUES (?1, ?2, datetime('now'))",
@@ -299,12 +307,14 @@ pub fn add_rfc_bookmark(conn: &Connection, rfc_id: &str, memo: Option<&str>) ->
 }
 
 /// Remove RFC bookmark
+#[cfg(feature = "desktop")]
 pub fn remove_rfc_bookmark(conn: &Connection, rfc_id: &str) -> Result<()> {
     conn.execute("DELETE FROM rfc_bookmarks WHERE rfc_id = ?1", [rfc_id])?;
     Ok(())
 }
 
 /// Get all RFC bookmarks
+#[cfg(feature = "desktop")]
 pub fn get_rfc_bookmarks(conn: &Connection) -> Result<Vec<RfcBookmark>> {
     let mut stmt = conn.prepare(
         "SELECT rfc_id, memo, created_at FROM rfc_bookmarks ORDER BY created_at DESC"
@@ -326,6 +336,7 @@ pub fn get_rfc_bookmarks(conn: &Connection) -> Result<Vec<RfcBookmark>> {
 // ============================================================================
 
 /// Add RFC to history
+#[cfg(feature = "desktop")]
 pub fn add_rfc_history(conn: &Connection, rfc_id: &str) -> Result<()> {
     conn.execute(
         "INSERT INTO rfc_history (rfc_id, viewed_at) VALUES (?1, datetime('now'))",
@@ -335,6 +346,7 @@ pub fn add_rfc_history(conn: &Connection, rfc_id: &str) -> Result<()> {
 }
 
 /// Get RFC history
+#[cfg(feature = "desktop")]
 pub fn get_rfc_history(conn: &Connection, limit: Option<i32>) -> Result<Vec<RfcHistory>> {
     let limit_val = limit.unwrap_or(50);
     
@@ -361,6 +373,7 @@ pub fn get_rfc_history(conn: &Connection, limit: Option<i32>) -> Result<Vec<RfcH
 // ============================================================================
 
 /// Get all used categories
+#[cfg(feature = "desktop")]
 pub fn get_all_rfc_categories(conn: &Connection) -> Result<Vec<String>> {
     let mut stmt = conn.prepare(
         "SELECT DISTINCT category FROM rfc_categories ORDER BY category"
@@ -373,6 +386,7 @@ pub fn get_all_rfc_categories(conn: &Connection) -> Result<Vec<String>> {
 }
 
 /// Get RFC count
+#[cfg(feature = "desktop")]
 pub fn get_rfc_count(conn: &Connection) -> Result<i64> {
     let count: i64 = conn.query_row(
         "SELECT COUNT(*) FROM rfcs",
@@ -441,6 +455,7 @@ mod tests {
         cleanup_test_db(&db_path);
     }
     
+    #[cfg(feature = "desktop")]
     #[test]
     fn test_bookmark_operations() {
         let (db_path, conn) = create_test_db("bookmark");
@@ -1,17 +1,46 @@
 //! 脆弱性データベースクエリ
 
-use super::models::{ScanHistory, Vulnerability, VulnFilter, VulnListResponse};
-use rusqlite::{params, Connection, Result};
+use super::models::{AffectedPackage, ScanFinding, ScanRun, Suppression, Vulnerability};
+#[cfg(feature = "desktop")]
+use super::models::{ScanHistory, VulnFilter, VulnListResponse, WatchedProject};
+use crate::api::osv::{OsvAffected, OsvAffectedPackage, OsvEvent, OsvRange, OsvVulnerability};
+use crate::scanner::purl;
+use rusqlite::{params, Connection, OptionalExtension, Result};
 
 /// 脆弱性を保存（upsert）
-pub fn upsert_vulnerability(conn: &Connection, vuln: &Vulnerability) -> Result<()> {
+/// 行を置き換えると影響パッケージ・範囲・別名のテーブルまで連鎖削除されるため、既存の行は更新する
+/// 保存済みのレコードより古いか情報の少ないレコードでは上書きせず、false を返す
+pub fn upsert_vulnerability(conn: &Connection, vuln: &Vulnerability) -> Result<bool> {
+    if let Some(stored) = get_vulnerability_by_id(conn, &vuln.id)? {
+        if !supersedes(vuln, &stored) {
+            return Ok(false);
+        }
+    }
     let references_json = serde_json::to_string(&vuln.references).unwrap_or_else(|_| "[]".to_string());
     
     conn.execute(
-        "INSERT OR REPLACE INTO vulnerabilities 
+        "INSERT INTO vulnerabilities 
          (id, source, severity, cvss_score, title, description, affected_package, 
-          affected_ecosystem, affected_versions, fixed_versions, published_at, reference_urls, fetched_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, datetime('now'))",
+          affected_ecosystem, affected_versions, fixed_versions, published_at, reference_urls, fetched_at,
+          cvss_vector, cvss_version, severity_source, modified, canonical_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, datetime('now'), ?13, ?14, ?15, ?16, ?1)
+         ON CONFLICT(id) DO UPDATE SET
+            source = excluded.source,
+            severity = excluded.severity,
+            cvss_score = excluded.cvss_score,
+            title = excluded.title,
+            description = excluded.description,
+            affected_package = excluded.affected_package,
+            affected_ecosystem = excluded.affected_ecosystem,
+            affected_versions = excluded.affected_versions,
+            fixed_versions = excluded.fixed_versions,
+            published_at = excluded.published_at,
+            reference_urls = excluded.reference_urls,
+            fetched_at = excluded.fetched_at,
+            cvss_vector = excluded.cvss_vector,
+            cvss_version = excluded.cvss_version,
+            severity_source = excluded.severity_source,
+            modified = excluded.modified",
         params![
             vuln.id,
             vuln.source,
@@ -25,11 +54,150 @@ pub fn upsert_vulnerability(conn: &Connection, vuln: &Vulnerability) -> Result<(
             vuln.fixed_versions,
             vuln.published_at,
             references_json,
+            vuln.cvss_vector,
+            vuln.cvss_version,
+            vuln.severity_source,
+            vuln.modified,
         ],
     )?;
+    replace_aliases(conn, &vuln.id, &vuln.aliases)?;
+    Ok(true)
+}
+
+/// 保存済みのレコードを置き換えてよいか
+/// 更新日時（modified）が新しければ置き換え、古ければ残す。同じか不明なら情報の多い方を残す
+fn supersedes(incoming: &Vulnerability, stored: &Vulnerability) -> bool {
+    // "2024-01-01T00:00:00Z" と "2024-01-01T00:00:00.5Z" を正しく並べるため Z を外して比べる
+    let modified = |v: &Vulnerability| {
+        v.modified
+            .as_deref()
+            .map(|m| m.trim_end_matches('Z').to_string())
+    };
+    match (modified(incoming), modified(stored)) {
+        (Some(new), Some(old)) if new < old => false,
+        // 新しいレコードでも、影響範囲を持たない ID だけの応答（querybatch）では置き換えない
+        (Some(new), Some(old)) if new > old => !incoming.affected_packages.is_empty(),
+        _ => completeness(incoming) >= completeness(stored),
+    }
+}
+
+/// レコードの詳しさ（値のある項目の数）
+fn completeness(vuln: &Vulnerability) -> usize {
+    [
+        vuln.severity != "unknown",
+        vuln.cvss_score.is_some(),
+        vuln.cvss_vector.is_some(),
+        vuln.description.is_some(),
+        vuln.published_at.is_some(),
+        vuln.fixed_versions.is_some(),
+        !vuln.references.is_empty(),
+        !vuln.aliases.is_empty(),
+        !vuln.affected_packages.is_empty(),
+    ]
+    .into_iter()
+    .filter(|&present| present)
+    .count()
+}
+
+/// 正規 ID の優先順位（CVE → GHSA → その他）
+fn id_rank(id: &str) -> u8 {
+    if id.starts_with("CVE-") {
+        0
+    } else if id.starts_with("GHSA-") {
+        1
+    } else {
+        2
+    }
+}
+
+/// 同じ問題を指す ID の中から正規 ID を選ぶ（同順位なら辞書順で最小のもの）
+pub fn preferred_id<'a>(ids: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    ids.into_iter().min_by_key(|id| (id_rank(id), *id))
+}
+
+/// 別名を保存し、ID か別名を共有する脆弱性の正規 ID をそろえる
+/// GHSA → CVE、RUSTSEC → GHSA のように別名が片方向にしかない場合も同じ問題としてまとめる
+fn replace_aliases(conn: &Connection, vuln_id: &str, aliases: &[String]) -> Result<()> {
+    conn.execute(
+        "DELETE FROM vulnerability_aliases WHERE vuln_id = ?1",
+        [vuln_id],
+    )?;
+    let mut insert = conn.prepare_cached(
+        "INSERT OR IGNORE INTO vulnerability_aliases (vuln_id, alias) VALUES (?1, ?2)",
+    )?;
+    for alias in aliases.iter().filter(|a| *a != vuln_id) {
+        insert.execute(params![vuln_id, alias])?;
+    }
+
+    // 既存のグループ（正規 ID）のうち、この脆弱性の ID か別名を含むもの
+    let mut groups: Vec<String> = Vec::new();
+    let mut stmt = conn.prepare_cached(
+        "SELECT canonical_id FROM vulnerabilities WHERE id = ?1 AND id != ?2
+         UNION
+         SELECT v.canonical_id FROM vulnerability_aliases a
+         JOIN vulnerabilities v ON v.id = a.vuln_id
+         WHERE a.alias = ?1 AND a.vuln_id != ?2",
+    )?;
+    for id in std::iter::once(vuln_id).chain(aliases.iter().map(String::as_str)) {
+        let rows = stmt.query_map([id, vuln_id], |row| row.get::<_, String>(0))?;
+        for group in rows {
+            let group = group?;
+            if !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+    }
+
+    let canonical = preferred_id(
+        std::iter::once(vuln_id)
+            .chain(aliases.iter().map(String::as_str))
+            .chain(groups.iter().map(String::as_str)),
+    )
+    .unwrap_or(vuln_id)
+    .to_string();
+
+    conn.execute(
+        "UPDATE vulnerabilities SET canonical_id = ?1 WHERE id = ?2",
+        [&canonical, vuln_id],
+    )?;
+    for group in groups.iter().filter(|g| **g != canonical) {
+        conn.execute(
+            "UPDATE vulnerabilities SET canonical_id = ?1 WHERE canonical_id = ?2",
+            [&canonical, group],
+        )?;
+    }
     Ok(())
 }
 
+/// 脆弱性の正規 ID を取得（保存されていなければ None）
+pub fn get_canonical_id(conn: &Connection, vuln_id: &str) -> Result<Option<String>> {
+    conn.query_row(
+        "SELECT canonical_id FROM vulnerabilities WHERE id = ?1",
+        [vuln_id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// 同じ問題を指す自身以外のすべての ID（同じ正規 ID を持つ脆弱性とそれらの別名）
+pub fn get_aliases(conn: &Connection, vuln_id: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare_cached(
+        "WITH grp AS (
+             SELECT id FROM vulnerabilities
+             WHERE canonical_id = (SELECT canonical_id FROM vulnerabilities WHERE id = ?1)
+         )
+         SELECT id FROM grp
+         UNION
+         SELECT alias FROM vulnerability_aliases WHERE vuln_id IN (SELECT id FROM grp)",
+    )?;
+    let mut aliases = stmt
+        .query_map([vuln_id], |row| row.get::<_, String>(0))?
+        .filter(|id| !matches!(id, Ok(id) if id == vuln_id))
+        .collect::<Result<Vec<_>>>()?;
+    aliases.sort_by(|a, b| (id_rank(a), a).cmp(&(id_rank(b), b)));
+    Ok(aliases)
+}
+
 /// 複数の脆弱性を一括保存
 #[allow(dead_code)]
 pub fn upsert_vulnerabilities(conn: &Connection, vulns: &[Vulnerability]) -> Result<()> {
@@ -40,6 +208,7 @@ pub fn upsert_vulnerabilities(conn: &Connection, vulns: &[Vulnerability]) -> Res
 }
 
 /// 脆弱性を取得（フィルター付き、ページネーション）
+#[cfg(feature = "desktop")]
 pub fn get_vulnerabilities(
     conn: &Connection,
     filter: &VulnFilter,
@@ -52,8 +221,12 @@ pub fn get_vulnerabilities(
     let mut conditions = Vec::new();
     let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
     
+    // 代表パッケージ以外の影響パッケージも対象にする
     if let Some(ref ecosystem) = filter.ecosystem {
-        conditions.push("affected_ecosystem = ?");
+        conditions.push(
+            "(affected_ecosystem = ? OR id IN (SELECT vuln_id FROM vulnerability_affected WHERE ecosystem = ?))",
+        );
+        params_vec.push(Box::new(ecosystem.clone()));
         params_vec.push(Box::new(ecosystem.clone()));
     }
     
@@ -63,10 +236,16 @@ pub fn get_vulnerabilities(
     }
     
     if let Some(ref search) = filter.search {
-        conditions.push("(title LIKE ? OR affected_package LIKE ? OR id LIKE ?)");
+        conditions.push(
+            "(title LIKE ? OR affected_package LIKE ? OR id LIKE ?
+              OR id IN (SELECT vuln_id FROM vulnerability_affected WHERE name LIKE ?)
+              OR id IN (SELECT vuln_id FROM vulnerability_aliases WHERE alias LIKE ?))",
+        );
         let search_pattern = format!("%{}%", search);
         params_vec.push(Box::new(search_pattern.clone()));
         params_vec.push(Box::new(search_pattern.clone()));
+        params_vec.push(Box::new(search_pattern.clone()));
+        params_vec.push(Box::new(search_pattern.clone()));
         params_vec.push(Box::new(search_pattern));
     }
     
@@ -76,8 +255,11 @@ pub fn get_vulnerabilities(
         format!("WHERE {}", conditions.join(" AND "))
     };
     
-    // Get total count
-    let count_sql = format!("SELECT COUNT(*) FROM vulnerabilities {}", where_clause);
+    // Get total count（同じ問題を指す別 ID は 1 件として数える）
+    let count_sql = format!(
+        "SELECT COUNT(DISTINCT canonical_id) FROM vulnerabilities {}",
+        where_clause
+    );
     let total: i64 = {
         let mut stmt = conn.prepare(&count_sql)?;
         let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
@@ -85,10 +267,18 @@ pub fn get_vulnerabilities(
     };
     
     // Get vulnerabilities
+    // 条件に一致した行だけをグループ（正規 ID）に分け、その中で最もスコアの高い行を代表として返す
     let sql = format!(
         "SELECT id, source, severity, cvss_score, title, description, affected_package, 
-                affected_ecosystem, affected_versions, fixed_versions, published_at, reference_urls, fetched_at
-         FROM vulnerabilities {} 
+                affected_ecosystem, affected_versions, fixed_versions, published_at, reference_urls, fetched_at,
+                cvss_vector, cvss_version, severity_source, modified
+         FROM (
+             SELECT *, ROW_NUMBER() OVER (
+                 PARTITION BY canonical_id ORDER BY cvss_score DESC NULLS LAST, id
+             ) AS group_rank
+             FROM vulnerabilities {}
+         )
+         WHERE group_rank = 1
          ORDER BY published_at DESC NULLS LAST
          LIMIT ? OFFSET ?",
         where_clause
@@ -102,7 +292,7 @@ pub fn get_vulnerabilities(
     
     let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
     
-    let vulns = stmt
+    let mut vulns = stmt
         .query_map(params_refs.as_slice(), |row| {
             let references_json: String = row.get(11)?;
             let references: Vec<String> = serde_json::from_str(&references_json).unwrap_or_default();
@@ -121,10 +311,20 @@ pub fn get_vulnerabilities(
                 published_at: row.get(10)?,
                 references,
                 fetched_at: row.get(12)?,
+                cvss_vector: row.get(13)?,
+                cvss_version: row.get(14)?,
+                severity_source: row.get(15)?,
+                modified: row.get(16)?,
+                affected_packages: Vec::new(),
+                aliases: Vec::new(),
             })
         })?
         .collect::<Result<Vec<_>>>()?;
     
+    for vuln in &mut vulns {
+        vuln.aliases = get_aliases(conn, &vuln.id)?;
+    }
+
     Ok(VulnListResponse {
         vulnerabilities: vulns,
         total,
@@ -137,7 +337,8 @@ pub fn get_vulnerabilities(
 pub fn get_vulnerability_by_id(conn: &Connection, vuln_id: &str) -> Result<Option<Vulnerability>> {
     let mut stmt = conn.prepare(
         "SELECT id, source, severity, cvss_score, title, description, affected_package, 
-                affected_ecosystem, affected_versions, fixed_versions, published_at, reference_urls, fetched_at
+                affected_ecosystem, affected_versions, fixed_versions, published_at, reference_urls, fetched_at,
+                cvss_vector, cvss_version, severity_source, modified
          FROM vulnerabilities WHERE id = ?1"
     )?;
     
@@ -159,10 +360,21 @@ pub fn get_vulnerability_by_id(conn: &Connection, vuln_id: &str) -> Result<Optio
             published_at: row.get(10)?,
             references,
             fetched_at: row.get(12)?,
+            cvss_vector: row.get(13)?,
+            cvss_version: row.get(14)?,
+            severity_source: row.get(15)?,
+            modified: row.get(16)?,
+            affected_packages: Vec::new(),
+            aliases: Vec::new(),
         })
     })?;
     
-    vulns.next().transpose()
+    let Some(mut vuln) = vulns.next().transpose()? else {
+        return Ok(None);
+    };
+    vuln.affected_packages = get_affected_packages(conn, vuln_id)?;
+    vuln.aliases = get_aliases(conn, vuln_id)?;
+    Ok(Some(vuln))
 }
 
 /// パッケージ名とエコシステムで脆弱性を検索
@@ -174,14 +386,17 @@ pub fn find_vulnerabilities_for_package(
 ) -> Result<Vec<Vulnerability>> {
     let mut stmt = conn.prepare(
         "SELECT id, source, severity, cvss_score, title, description, affected_package, 
-                affected_ecosystem, affected_versions, fixed_versions, published_at, reference_urls, fetched_at
+                affected_ecosystem, affected_versions, fixed_versions, published_at, reference_urls, fetched_at,
+                cvss_vector, cvss_version, severity_source, modified
          FROM vulnerabilities 
-         WHERE affected_ecosystem = ?1 AND affected_package = ?2
+         WHERE (affected_ecosystem = ?1 AND affected_package = ?2)
+            OR id IN (SELECT vuln_id FROM vulnerability_affected WHERE ecosystem = ?1 AND package = ?3)
          ORDER BY cvss_score DESC NULLS LAST"
     )?;
     
+    let key = normalize_package(ecosystem, package_name);
     let vulns = stmt
-        .query_map([ecosystem, package_name], |row| {
+        .query_map([ecosystem, package_name, key.as_str()], |row| {
             let references_json: String = row.get(11)?;
             let references: Vec<String> = serde_json::from_str(&references_json).unwrap_or_default();
             
@@ -199,6 +414,12 @@ pub fn find_vulnerabilities_for_package(
                 published_at: row.get(10)?,
                 references,
                 fetched_at: row.get(12)?,
+                cvss_vector: row.get(13)?,
+                cvss_version: row.get(14)?,
+                severity_source: row.get(15)?,
+                modified: row.get(16)?,
+                affected_packages: Vec::new(),
+                aliases: Vec::new(),
             })
         })?
         .collect::<Result<Vec<_>>>()?;
@@ -206,24 +427,314 @@ pub fn find_vulnerabilities_for_package(
     Ok(vulns)
 }
 
+/// 照合用にパッケージ名を正規化（PyPI は PEP 503）
+pub fn normalize_package(ecosystem: &str, name: &str) -> String {
+    if ecosystem == "PyPI" {
+        purl::normalize_pypi_name(name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// "Debian:12" のようなサフィックスを除いたエコシステム名
+fn base_ecosystem(ecosystem: &str) -> &str {
+    ecosystem.split(':').next().unwrap_or(ecosystem)
+}
+
+/// 脆弱性が影響するすべてのパッケージと影響範囲を保存（既存の行は置き換え）
+pub fn replace_affected(conn: &Connection, vuln: &OsvVulnerability) -> Result<()> {
+    for table in [
+        "vulnerability_affected",
+        "vulnerability_ranges",
+        "vulnerability_versions",
+    ] {
+        conn.execute(
+            &format!("DELETE FROM {} WHERE vuln_id = ?1", table),
+            [&vuln.id],
+        )?;
+    }
+
+    let mut affected_stmt = conn.prepare_cached(
+        "INSERT INTO vulnerability_affected (vuln_id, affected_index, ecosystem, package, name, purl)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    let mut range_stmt = conn.prepare_cached(
+        "INSERT INTO vulnerability_ranges
+         (vuln_id, ecosystem, package, affected_index, range_index, range_type, event_index, event_type, version)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+    )?;
+    let mut version_stmt = conn.prepare_cached(
+        "INSERT OR IGNORE INTO vulnerability_versions (vuln_id, ecosystem, package, affected_index, version)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+
+    for (affected_index, affected) in vuln.affected.iter().enumerate() {
+        let Some(ref package) = affected.package else {
+            continue;
+        };
+        let ecosystem = base_ecosystem(&package.ecosystem);
+        let key = normalize_package(ecosystem, &package.name);
+
+        affected_stmt.execute(params![
+            vuln.id,
+            affected_index as i64,
+            ecosystem,
+            key,
+            package.name,
+            package.purl,
+        ])?;
+
+        for (range_index, range) in affected.ranges.iter().enumerate() {
+            for (event_index, event) in range.events.iter().enumerate() {
+                let kinds = [
+                    ("introduced", &event.introduced),
+                    ("fixed", &event.fixed),
+                    ("last_affected", &event.last_affected),
+                    ("limit", &event.limit),
+                ];
+                for (event_type, version) in kinds {
+                    if let Some(version) = version {
+                        range_stmt.execute(params![
+                            vuln.id,
+                            ecosystem,
+                            key,
+                            affected_index as i64,
+                            range_index as i64,
+                            range.range_type,
+                            event_index as i64,
+                            event_type,
+                            version,
+                        ])?;
+                        break;
+                    }
+                }
+            }
+        }
+
+        for version in &affected.versions {
+            version_stmt.execute(params![
+                vuln.id,
+                ecosystem,
+                key,
+                affected_index as i64,
+                version
+            ])?;
+        }
+    }
+    Ok(())
+}
+
+/// ローカルに保存された影響範囲をパッケージ単位で取得
+/// 脆弱性 ID と、評価用に再構成した affected エントリの組を返す
+pub fn get_local_affected(
+    conn: &Connection,
+    ecosystem: &str,
+    package_name: &str,
+) -> Result<Vec<(String, OsvAffected)>> {
+    let key = normalize_package(ecosystem, package_name);
+    let entries = load_affected(conn, "ecosystem = ?1 AND package = ?2", &[&ecosystem, &key])?;
+    Ok(entries
+        .into_iter()
+        .map(|(vuln_id, _, affected)| (vuln_id, affected))
+        .collect())
+}
+
+/// 脆弱性が影響するパッケージの一覧を取得
+pub fn get_affected_packages(conn: &Connection, vuln_id: &str) -> Result<Vec<AffectedPackage>> {
+    let entries = load_affected(conn, "vuln_id = ?1", &[&vuln_id])?;
+    Ok(entries
+        .iter()
+        .filter_map(|(_, _, affected)| affected_package(affected))
+        .collect())
+}
+
+/// OSV の affected エントリを表示用のパッケージ情報に変換（エコシステムはサフィックスを除く）
+pub fn affected_package(affected: &OsvAffected) -> Option<AffectedPackage> {
+    let package = affected.package.as_ref()?;
+    let intervals = affected.version_intervals();
+    let fixed = affected.fixed_versions();
+    Some(AffectedPackage {
+        ecosystem: base_ecosystem(&package.ecosystem).to_string(),
+        name: package.name.clone(),
+        purl: package.purl.clone(),
+        affected_versions: (!intervals.is_empty()).then(|| intervals.join("; ")),
+        fixed_versions: (!fixed.is_empty()).then(|| fixed.join(", ")),
+    })
+}
+
+/// 保存された affected エントリを (脆弱性 ID, affected_index) ごとに再構成
+/// filter は 3 つのテーブルに共通の列（vuln_id / ecosystem / package）に対する条件
+fn load_affected(
+    conn: &Connection,
+    filter: &str,
+    filter_params: &[&dyn rusqlite::ToSql],
+) -> Result<Vec<(String, i64, OsvAffected)>> {
+    let mut entries: Vec<(String, i64, OsvAffected)> = Vec::new();
+
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT vuln_id, affected_index, ecosystem, name, purl
+         FROM vulnerability_affected
+         WHERE {}
+         ORDER BY vuln_id, affected_index",
+        filter
+    ))?;
+    let rows = stmt.query_map(filter_params, |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, Option<String>>(4)?,
+        ))
+    })?;
+    for row in rows {
+        let (vuln_id, affected_index, ecosystem, name, purl) = row?;
+        let entry = affected_entry(&mut entries, &vuln_id, affected_index, &ecosystem, &name);
+        if let Some(ref mut package) = entry.package {
+            package.purl = purl;
+        }
+    }
+
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT vuln_id, affected_index, range_index, range_type, event_type, version, ecosystem, package
+         FROM vulnerability_ranges
+         WHERE {}
+         ORDER BY vuln_id, affected_index, range_index, event_index",
+        filter
+    ))?;
+    let rows = stmt.query_map(filter_params, |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, String>(5)?,
+            row.get::<_, String>(6)?,
+            row.get::<_, String>(7)?,
+        ))
+    })?;
+
+    let mut last_range: Option<(String, i64, i64)> = None;
+    for row in rows {
+        let (
+            vuln_id,
+            affected_index,
+            range_index,
+            range_type,
+            event_type,
+            version,
+            ecosystem,
+            package,
+        ) = row?;
+        let entry = affected_entry(&mut entries, &vuln_id, affected_index, &ecosystem, &package);
+
+        let key = (vuln_id, affected_index, range_index);
+        if last_range.as_ref() != Some(&key) {
+            entry.ranges.push(OsvRange {
+                range_type,
+                events: Vec::new(),
+            });
+            last_range = Some(key);
+        }
+
+        let mut event = OsvEvent {
+            introduced: None,
+            fixed: None,
+            last_affected: None,
+            limit: None,
+        };
+        match event_type.as_str() {
+            "introduced" => event.introduced = Some(version),
+            "fixed" => event.fixed = Some(version),
+            "last_affected" => event.last_affected = Some(version),
+            _ => event.limit = Some(version),
+        }
+        if let Some(range) = entry.ranges.last_mut() {
+            range.events.push(event);
+        }
+    }
+
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT vuln_id, affected_index, version, ecosystem, package
+         FROM vulnerability_versions
+         WHERE {}",
+        filter
+    ))?;
+    let rows = stmt.query_map(filter_params, |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+        ))
+    })?;
+    for row in rows {
+        let (vuln_id, affected_index, version, ecosystem, package) = row?;
+        affected_entry(&mut entries, &vuln_id, affected_index, &ecosystem, &package)
+            .versions
+            .push(version);
+    }
+
+    Ok(entries)
+}
+
+/// (脆弱性 ID, affected_index) に対応するエントリを取得（なければ追加）
+fn affected_entry<'a>(
+    entries: &'a mut Vec<(String, i64, OsvAffected)>,
+    vuln_id: &str,
+    affected_index: i64,
+    ecosystem: &str,
+    name: &str,
+) -> &'a mut OsvAffected {
+    let position = entries
+        .iter()
+        .position(|(id, index, _)| id == vuln_id && *index == affected_index);
+    let position = match position {
+        Some(position) => position,
+        None => {
+            entries.push((
+                vuln_id.to_string(),
+                affected_index,
+                OsvAffected {
+                    package: Some(OsvAffectedPackage {
+                        name: name.to_string(),
+                        ecosystem: ecosystem.to_string(),
+                        purl: None,
+                    }),
+                    ranges: Vec::new(),
+                    versions: Vec::new(),
+                    ecosystem_specific: None,
+                    database_specific: None,
+                },
+            ));
+            entries.len() - 1
+        }
+    };
+    &mut entries[position].2
+}
+
 /// スキャン履歴を保存
 pub fn add_scan_history(
     conn: &Connection,
     directory: &str,
+    project: &str,
     ecosystem: &str,
     vuln_count: i32,
 ) -> Result<i64> {
     conn.execute(
-        "INSERT INTO scan_history (directory, ecosystem, vuln_count) VALUES (?1, ?2, ?3)",
-        params![directory, ecosystem, vuln_count],
+        "INSERT INTO scan_history (directory, project, ecosystem, vuln_count) VALUES (?1, ?2, ?3, ?4)",
+        params![directory, project, ecosystem, vuln_count],
     )?;
     Ok(conn.last_insert_rowid())
 }
 
 /// スキャン履歴を取得
+#[cfg(feature = "desktop")]
 pub fn get_scan_history(conn: &Connection, limit: i32) -> Result<Vec<ScanHistory>> {
     let mut stmt = conn.prepare(
-        "SELECT id, directory, ecosystem, vuln_count, scanned_at
+        "SELECT id, directory, project, ecosystem, vuln_count, scanned_at
          FROM scan_history
          ORDER BY scanned_at DESC
          LIMIT ?1"
@@ -234,9 +745,10 @@ pub fn get_scan_history(conn: &Connection, limit: i32) -> Result<Vec<ScanHistory
             Ok(ScanHistory {
                 id: row.get(0)?,
                 directory: row.get(1)?,
-                ecosystem: row.get(2)?,
-                vuln_count: row.get(3)?,
-                scanned_at: row.get(4)?,
+                project: row.get(2)?,
+                ecosystem: row.get(3)?,
+                vuln_count: row.get(4)?,
+                scanned_at: row.get(5)?,
             })
         })?
         .collect::<Result<Vec<_>>>()?;
@@ -244,21 +756,290 @@ pub fn get_scan_history(conn: &Connection, limit: i32) -> Result<Vec<ScanHistory
     Ok(history)
 }
 
+/// スキャン実行と検出の一覧を保存（件数は抑制済みを除く）
+pub fn save_scan_run(
+    conn: &Connection,
+    directory: &str,
+    total_packages: i32,
+    findings: &[ScanFinding],
+) -> Result<i64> {
+    let tx = conn.unchecked_transaction()?;
+    let vuln_count = findings.iter().filter(|f| !f.suppressed).count() as i32;
+    tx.execute(
+        "INSERT INTO scan_runs (directory, total_packages, vuln_count) VALUES (?1, ?2, ?3)",
+        params![directory, total_packages, vuln_count],
+    )?;
+    let run_id = tx.last_insert_rowid();
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO scan_findings
+             (run_id, project, source_file, ecosystem, package, version, vuln_id, canonical_id, severity, suppressed)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        )?;
+        for f in findings {
+            stmt.execute(params![
+                run_id,
+                f.project,
+                f.source_file,
+                f.ecosystem,
+                f.package_name,
+                f.installed_version,
+                f.vuln_id,
+                f.canonical_id,
+                f.severity,
+                f.suppressed,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(run_id)
+}
+
+fn scan_run_from_row(row: &rusqlite::Row) -> Result<ScanRun> {
+    Ok(ScanRun {
+        id: row.get(0)?,
+        directory: row.get(1)?,
+        total_packages: row.get(2)?,
+        vuln_count: row.get(3)?,
+        scanned_at: row.get(4)?,
+    })
+}
+
+/// スキャン実行の一覧を新しい順に取得（directory を指定するとそのディレクトリのみ）
+pub fn get_scan_runs(
+    conn: &Connection,
+    directory: Option<&str>,
+    limit: i32,
+) -> Result<Vec<ScanRun>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, directory, total_packages, vuln_count, scanned_at
+         FROM scan_runs
+         WHERE ?1 IS NULL OR directory = ?1
+         ORDER BY id DESC
+         LIMIT ?2",
+    )?;
+    let runs = stmt
+        .query_map(params![directory, limit], scan_run_from_row)?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(runs)
+}
+
+/// スキャン実行を ID で取得
+pub fn get_scan_run(conn: &Connection, id: i64) -> Result<Option<ScanRun>> {
+    conn.query_row(
+        "SELECT id, directory, total_packages, vuln_count, scanned_at FROM scan_runs WHERE id = ?1",
+        [id],
+        scan_run_from_row,
+    )
+    .optional()
+}
+
+/// 同じディレクトリの直前のスキャン実行を取得
+pub fn get_previous_scan_run(conn: &Connection, run: &ScanRun) -> Result<Option<ScanRun>> {
+    conn.query_row(
+        "SELECT id, directory, total_packages, vuln_count, scanned_at
+         FROM scan_runs
+         WHERE directory = ?1 AND id < ?2
+         ORDER BY id DESC
+         LIMIT 1",
+        params![run.directory, run.id],
+        scan_run_from_row,
+    )
+    .optional()
+}
+
+/// スキャン実行の検出一覧を取得
+pub fn get_scan_findings(conn: &Connection, run_id: i64) -> Result<Vec<ScanFinding>> {
+    let mut stmt = conn.prepare(
+        "SELECT project, source_file, ecosystem, package, version, vuln_id, canonical_id, severity, suppressed
+         FROM scan_findings
+         WHERE run_id = ?1
+         ORDER BY rowid",
+    )?;
+    let findings = stmt
+        .query_map([run_id], |row| {
+            Ok(ScanFinding {
+                project: row.get(0)?,
+                source_file: row.get(1)?,
+                ecosystem: row.get(2)?,
+                package_name: row.get(3)?,
+                installed_version: row.get(4)?,
+                vuln_id: row.get(5)?,
+                canonical_id: row.get(6)?,
+                severity: row.get(7)?,
+                suppressed: row.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(findings)
+}
+
+/// 監視対象プロジェクトを追加（登録済みなら間隔を更新して有効にする）
+#[cfg(feature = "desktop")]
+pub fn add_watched_project(conn: &Connection, directory: &str, interval_hours: i64) -> Result<i64> {
+    conn.query_row(
+        "INSERT INTO watched_projects (directory, interval_hours) VALUES (?1, ?2)
+         ON CONFLICT(directory) DO UPDATE SET interval_hours = excluded.interval_hours, enabled = 1
+         RETURNING id",
+        params![directory, interval_hours],
+        |row| row.get(0),
+    )
+}
+
+#[cfg(feature = "desktop")]
+const WATCHED_PROJECT_COLUMNS: &str =
+    "id, directory, interval_hours, enabled, last_scanned_at, last_run_id, last_error";
+
+#[cfg(feature = "desktop")]
+fn watched_project_from_row(row: &rusqlite::Row) -> Result<WatchedProject> {
+    Ok(WatchedProject {
+        id: row.get(0)?,
+        directory: row.get(1)?,
+        interval_hours: row.get(2)?,
+        enabled: row.get(3)?,
+        last_scanned_at: row.get(4)?,
+        last_run_id: row.get(5)?,
+        last_error: row.get(6)?,
+    })
+}
+
+/// 監視対象プロジェクトの一覧を取得
+#[cfg(feature = "desktop")]
+pub fn get_watched_projects(conn: &Connection) -> Result<Vec<WatchedProject>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM watched_projects ORDER BY directory",
+        WATCHED_PROJECT_COLUMNS
+    ))?;
+    let projects = stmt
+        .query_map([], watched_project_from_row)?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(projects)
+}
+
+/// 再スキャンの時期が来た監視対象プロジェクトを取得（未スキャンのものを含む）
+#[cfg(feature = "desktop")]
+pub fn get_due_watched_projects(conn: &Connection) -> Result<Vec<WatchedProject>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM watched_projects
+         WHERE enabled = 1
+           AND (last_scanned_at IS NULL
+                OR datetime(last_scanned_at, '+' || interval_hours || ' hours') <= datetime('now'))
+         ORDER BY last_scanned_at IS NOT NULL, last_scanned_at",
+        WATCHED_PROJECT_COLUMNS
+    ))?;
+    let projects = stmt
+        .query_map([], watched_project_from_row)?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(projects)
+}
+
+/// 再スキャンの結果を記録（失敗した場合も次の時期まで待つ）
+#[cfg(feature = "desktop")]
+pub fn mark_watched_project_scanned(
+    conn: &Connection,
+    id: i64,
+    run_id: Option<i64>,
+    error: Option<&str>,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE watched_projects
+         SET last_scanned_at = datetime('now'),
+             last_run_id = COALESCE(?2, last_run_id),
+             last_error = ?3
+         WHERE id = ?1",
+        params![id, run_id, error],
+    )?;
+    Ok(())
+}
+
+/// 監視対象プロジェクトの有効・無効を切り替え
+#[cfg(feature = "desktop")]
+pub fn set_watched_project_enabled(conn: &Connection, id: i64, enabled: bool) -> Result<usize> {
+    conn.execute(
+        "UPDATE watched_projects SET enabled = ?2 WHERE id = ?1",
+        params![id, enabled],
+    )
+}
+
+/// 監視対象プロジェクトを削除（保存済みのスキャン実行は残す）
+#[cfg(feature = "desktop")]
+pub fn delete_watched_project(conn: &Connection, id: i64) -> Result<usize> {
+    conn.execute("DELETE FROM watched_projects WHERE id = ?1", [id])
+}
+
+/// 抑制ルールを保存
+#[cfg(feature = "desktop")]
+pub fn add_suppression(conn: &Connection, suppression: &Suppression) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO suppressions (vuln_id, package, ecosystem, path, directory, expires, reason)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            suppression.vuln_id,
+            suppression.package,
+            suppression.ecosystem,
+            suppression.path,
+            suppression.directory,
+            suppression.expires,
+            suppression.reason,
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// 抑制ルールを取得（directory を指定するとそのディレクトリに適用されるもののみ）
+pub fn get_suppressions(conn: &Connection, directory: Option<&str>) -> Result<Vec<Suppression>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, vuln_id, package, ecosystem, path, directory, expires, reason
+         FROM suppressions
+         WHERE ?1 IS NULL OR directory IS NULL OR directory = ?1
+         ORDER BY id",
+    )?;
+    let suppressions = stmt
+        .query_map([directory], |row| {
+            Ok(Suppression {
+                id: row.get(0)?,
+                vuln_id: row.get(1)?,
+                package: row.get(2)?,
+                ecosystem: row.get(3)?,
+                path: row.get(4)?,
+                directory: row.get(5)?,
+                expires: row.get(6)?,
+                reason: row.get(7)?,
+                source: "database".to_string(),
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(suppressions)
+}
+
+/// 抑制ルールを削除
+#[cfg(feature = "desktop")]
+pub fn delete_suppression(conn: &Connection, id: i64) -> Result<usize> {
+    conn.execute("DELETE FROM suppressions WHERE id = ?1", [id])
+}
+
 /// 脆弱性の総数を取得
+#[cfg(feature = "desktop")]
 pub fn get_vulnerability_count(conn: &Connection, ecosystem: Option<&str>) -> Result<i64> {
     if let Some(eco) = ecosystem {
         conn.query_row(
-            "SELECT COUNT(*) FROM vulnerabilities WHERE affected_ecosystem = ?1",
+            "SELECT COUNT(DISTINCT canonical_id) FROM vulnerabilities
+             WHERE affected_ecosystem = ?1
+                OR id IN (SELECT vuln_id FROM vulnerability_affected WHERE ecosystem = ?1)",
             [eco],
             |row| row.get(0),
         )
     } else {
-        conn.query_row("SELECT COUNT(*) FROM vulnerabilities", [], |row| row.get(0))
+        conn.query_row(
+            "SELECT COUNT(DISTINCT canonical_id) FROM vulnerabilities",
+            [],
+            |row| row.get(0),
+        )
     }
 }
 
 /// 古い脆弱性を削除（キャッシュクリーンアップ用）
-#[allow(dead_code)]
+/// 影響パッケージ・範囲・別名の行は外部キーの ON DELETE CASCADE で削除される
 pub fn delete_old_vulnerabilities(conn: &Connection, days_old: i32) -> Result<usize> {
     let affected = conn.execute(
         "DELETE FROM vulnerabilities 
@@ -271,14 +1052,14 @@ pub fn delete_old_vulnerabilities(conn: &Connection, days_old: i32) -> Result<us
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::init_db;
+    use crate::db::{get_connection, init_db};
     use std::fs;
     
     fn create_test_db() -> (Connection, String) {
         let db_path = format!("/tmp/test_vuln_{}.db", std::process::id());
         let _ = fs::remove_file(&db_path);
         init_db(&db_path).unwrap();
-        let conn = Connection::open(&db_path).unwrap();
+        let conn = get_connection(&db_path).unwrap();
         (conn, db_path)
     }
     
@@ -303,7 +1084,7 @@ mod tests {
             fixed_versions: Some("2.0.0".to_string()),
             published_at: Some("2024-01-01".to_string()),
             references: vec!["https://example.com".to_string()],
-            fetched_at: None,
+            ..Default::default()
         };
         
         upsert_vulnerability(&conn, &vuln).unwrap();
@@ -316,6 +1097,7 @@ mod tests {
         cleanup_test_db(&path);
     }
     
+    #[cfg(feature = "desktop")]
     #[test]
     fn test_get_vulnerabilities_with_filter() {
         let (conn, path) = create_test_db();
@@ -328,14 +1110,10 @@ mod tests {
                 severity: "high".to_string(),
                 cvss_score: Some(7.5),
                 title: "NPM Vuln".to_string(),
-                description: None,
                 affected_package: "lodash".to_string(),
                 affected_ecosystem: "npm".to_string(),
-                affected_versions: None,
-                fixed_versions: None,
                 published_at: Some("2024-01-01".to_string()),
-                references: vec![],
-                fetched_at: None,
+                ..Default::default()
             },
             Vulnerability {
                 id: "CVE-2024-0002".to_string(),
@@ -343,14 +1121,10 @@ mod tests {
                 severity: "medium".to_string(),
                 cvss_score: Some(5.0),
                 title: "Cargo Vuln".to_string(),
-                description: None,
                 affected_package: "serde".to_string(),
                 affected_ecosystem: "crates.io".to_string(),
-                affected_versions: None,
-                fixed_versions: None,
                 published_at: Some("2024-01-02".to_string()),
-                references: vec![],
-                fetched_at: None,
+                ..Default::default()
             },
         ];
         
@@ -367,4 +1141,444 @@ mod tests {
         
         cleanup_test_db(&path);
     }
+
+    #[test]
+    fn test_affected_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("vuln.db");
+        let db_path = db_path.to_str().unwrap();
+        init_db(db_path).unwrap();
+        let conn = get_connection(db_path).unwrap();
+
+        let osv: OsvVulnerability = serde_json::from_str(
+            r#"{"id": "PYSEC-1", "affected": [
+                {"package": {"ecosystem": "PyPI", "name": "Django"},
+                 "versions": ["4.0"],
+                 "ranges": [{"type": "ECOSYSTEM", "events": [
+                    {"introduced": "3.2"}, {"fixed": "3.2.19"}, {"introduced": "4.1"}
+                 ]}]},
+                {"package": {"ecosystem": "npm", "name": "other"},
+                 "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}]}]}
+            ]}"#,
+        )
+        .unwrap();
+        let vuln = Vulnerability {
+            id: "PYSEC-1".to_string(),
+            source: "osv".to_string(),
+            severity: "unknown".to_string(),
+            title: "PYSEC-1".to_string(),
+            affected_package: "Django".to_string(),
+            affected_ecosystem: "PyPI".to_string(),
+            ..Default::default()
+        };
+        upsert_vulnerability(&conn, &vuln).unwrap();
+        replace_affected(&conn, &osv).unwrap();
+        // 再保存しても重複しない
+        replace_affected(&conn, &osv).unwrap();
+
+        let local = get_local_affected(&conn, "PyPI", "django").unwrap();
+        assert_eq!(local.len(), 1);
+        let (id, affected) = &local[0];
+        assert_eq!(id, "PYSEC-1");
+        assert_eq!(affected.versions, vec!["4.0"]);
+        assert_eq!(affected.ranges.len(), 1);
+        let events = &affected.ranges[0].events;
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1].fixed.as_deref(), Some("3.2.19"));
+        assert_eq!(events[2].introduced.as_deref(), Some("4.1"));
+
+        assert!(get_local_affected(&conn, "npm", "django")
+            .unwrap()
+            .is_empty());
+        assert_eq!(get_local_affected(&conn, "npm", "other").unwrap().len(), 1);
+        // 照合は正規化した名前、表示はアドバイザリ上の表記
+        assert_eq!(affected.package.as_ref().unwrap().name, "Django");
+    }
+
+    #[cfg(feature = "desktop")]
+    #[test]
+    fn test_multi_package_advisory() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("vuln.db");
+        let db_path = db_path.to_str().unwrap();
+        init_db(db_path).unwrap();
+        let conn = get_connection(db_path).unwrap();
+
+        let osv: OsvVulnerability = serde_json::from_str(
+            r#"{"id": "GHSA-multi", "affected": [
+                {"package": {"ecosystem": "npm", "name": "first"},
+                 "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "1.2.0"}]}]},
+                {"package": {"ecosystem": "Maven", "name": "org.example:second"},
+                 "ranges": [{"type": "ECOSYSTEM", "events": [{"introduced": "2.0"}, {"fixed": "2.5"}]}]}
+            ]}"#,
+        )
+        .unwrap();
+        let mut vuln = Vulnerability {
+            id: "GHSA-multi".to_string(),
+            source: "osv".to_string(),
+            severity: "high".to_string(),
+            title: "Multi".to_string(),
+            affected_package: "first".to_string(),
+            affected_ecosystem: "npm".to_string(),
+            affected_packages: osv.affected.iter().filter_map(affected_package).collect(),
+            ..Default::default()
+        };
+        upsert_vulnerability(&conn, &vuln).unwrap();
+        replace_affected(&conn, &osv).unwrap();
+
+        // 既存の行を更新しても影響パッケージは消えない
+        vuln.severity = "critical".to_string();
+        upsert_vulnerability(&conn, &vuln).unwrap();
+
+        let stored = get_vulnerability_by_id(&conn, "GHSA-multi")
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.severity, "critical");
+        assert_eq!(stored.affected_package, "first");
+        assert_eq!(stored.affected_packages.len(), 2);
+        let second = &stored.affected_packages[1];
+        assert_eq!(second.ecosystem, "Maven");
+        assert_eq!(second.name, "org.example:second");
+        assert_eq!(second.affected_versions.as_deref(), Some(">= 2.0, < 2.5"));
+        assert_eq!(second.fixed_versions.as_deref(), Some("2.5"));
+
+        // どの影響パッケージからも検索できる
+        for (ecosystem, name) in [("npm", "first"), ("Maven", "org.example:second")] {
+            let found = find_vulnerabilities_for_package(&conn, ecosystem, name).unwrap();
+            assert_eq!(found.len(), 1, "{} {}", ecosystem, name);
+        }
+        assert_eq!(get_vulnerability_count(&conn, Some("Maven")).unwrap(), 1);
+        let filter = VulnFilter {
+            ecosystem: Some("Maven".to_string()),
+            severity: None,
+            search: Some("second".to_string()),
+        };
+        assert_eq!(get_vulnerabilities(&conn, &filter, 1, 20).unwrap().total, 1);
+    }
+
+    #[test]
+    fn test_upsert_keeps_complete_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("vuln.db");
+        let db_path = db_path.to_str().unwrap();
+        init_db(db_path).unwrap();
+        let conn = get_connection(db_path).unwrap();
+
+        let osv: OsvVulnerability = serde_json::from_str(
+            r#"{"id": "GHSA-35jh-r3h4-6jhm", "affected": [
+                {"package": {"ecosystem": "npm", "name": "lodash"},
+                 "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "4.17.21"}]}]}
+            ]}"#,
+        )
+        .unwrap();
+        let full = |modified: &str, severity: &str| Vulnerability {
+            id: "GHSA-35jh-r3h4-6jhm".to_string(),
+            severity: severity.to_string(),
+            cvss_score: Some(7.2),
+            cvss_vector: Some("CVSS:3.1/AV:N/AC:L/PR:H/UI:N/S:U/C:H/I:H/A:H".to_string()),
+            fixed_versions: Some("4.17.21".to_string()),
+            modified: Some(modified.to_string()),
+            aliases: vec!["CVE-2021-23337".to_string()],
+            affected_packages: osv.affected.iter().filter_map(affected_package).collect(),
+            ..Default::default()
+        };
+        assert!(upsert_vulnerability(&conn, &full("2024-02-01T00:00:00Z", "high")).unwrap());
+        replace_affected(&conn, &osv).unwrap();
+
+        // querybatch の応答（id と modified のみ）では、同じ日時でも新しい日時でも上書きしない
+        for modified in ["2024-02-01T00:00:00Z", "2024-03-01T00:00:00Z"] {
+            let stub = Vulnerability {
+                id: "GHSA-35jh-r3h4-6jhm".to_string(),
+                severity: "unknown".to_string(),
+                modified: Some(modified.to_string()),
+                ..Default::default()
+            };
+            assert!(!upsert_vulnerability(&conn, &stub).unwrap());
+        }
+        // 古いレコードでも上書きしない
+        assert!(!upsert_vulnerability(&conn, &full("2024-01-31T23:59:59.5Z", "low")).unwrap());
+
+        let stored = get_vulnerability_by_id(&conn, "GHSA-35jh-r3h4-6jhm")
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.severity, "high");
+        assert_eq!(stored.cvss_score, Some(7.2));
+        assert_eq!(stored.aliases, vec!["CVE-2021-23337"]);
+        assert_eq!(get_local_affected(&conn, "npm", "lodash").unwrap().len(), 1);
+
+        // 新しい完全なレコードで更新する
+        assert!(upsert_vulnerability(&conn, &full("2024-02-01T00:00:00.5Z", "critical")).unwrap());
+        let stored = get_vulnerability_by_id(&conn, "GHSA-35jh-r3h4-6jhm")
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.severity, "critical");
+        assert_eq!(stored.modified.as_deref(), Some("2024-02-01T00:00:00.5Z"));
+    }
+
+    #[test]
+    fn test_delete_old_vulnerabilities_cascades() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("vuln.db");
+        let db_path = db_path.to_str().unwrap();
+        init_db(db_path).unwrap();
+        let conn = get_connection(db_path).unwrap();
+
+        for (id, package) in [("GHSA-old", "lodash"), ("GHSA-new", "minimist")] {
+            let osv: OsvVulnerability = serde_json::from_value(serde_json::json!({
+                "id": id,
+                "aliases": [format!("CVE-{}", id)],
+                "affected": [{
+                    "package": {"ecosystem": "npm", "name": package},
+                    "versions": ["1.0.0"],
+                    "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "2.0.0"}]}]
+                }]
+            }))
+            .unwrap();
+            let vuln = Vulnerability {
+                id: id.to_string(),
+                severity: "high".to_string(),
+                affected_package: package.to_string(),
+                affected_ecosystem: "npm".to_string(),
+                aliases: osv.aliases.clone(),
+                ..Default::default()
+            };
+            upsert_vulnerability(&conn, &vuln).unwrap();
+            replace_affected(&conn, &osv).unwrap();
+        }
+        conn.execute(
+            "UPDATE vulnerabilities SET fetched_at = datetime('now', '-40 days') WHERE id = 'GHSA-old'",
+            [],
+        )
+        .unwrap();
+
+        assert_eq!(delete_old_vulnerabilities(&conn, 30).unwrap(), 1);
+        assert!(get_vulnerability_by_id(&conn, "GHSA-old").unwrap().is_none());
+        assert!(get_local_affected(&conn, "npm", "lodash").unwrap().is_empty());
+        assert_eq!(get_local_affected(&conn, "npm", "minimist").unwrap().len(), 1);
+
+        // 子テーブルに削除した脆弱性の行が残らない
+        for table in [
+            "vulnerability_affected",
+            "vulnerability_ranges",
+            "vulnerability_versions",
+            "vulnerability_aliases",
+        ] {
+            let orphans: i64 = conn
+                .query_row(
+                    &format!("SELECT COUNT(*) FROM {} WHERE vuln_id = 'GHSA-old'", table),
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(orphans, 0, "{}", table);
+        }
+    }
+
+    #[cfg(feature = "desktop")]
+    #[test]
+    fn test_alias_deduplication() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("vuln.db");
+        let db_path = db_path.to_str().unwrap();
+        init_db(db_path).unwrap();
+        let conn = get_connection(db_path).unwrap();
+
+        let advisory = |id: &str, score: f64, aliases: &[&str]| Vulnerability {
+            id: id.to_string(),
+            source: "osv".to_string(),
+            severity: if score >= 7.0 { "high" } else { "medium" }.to_string(),
+            cvss_score: Some(score),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            title: id.to_string(),
+            affected_package: "openssl".to_string(),
+            affected_ecosystem: "crates.io".to_string(),
+            ..Default::default()
+        };
+
+        // RUSTSEC は GHSA のみ、GHSA は CVE を別名に持つ（片方向の別名でもまとめる）
+        upsert_vulnerability(
+            &conn,
+            &advisory("RUSTSEC-2024-0001", 5.0, &["GHSA-aaaa-bbbb-cccc"]),
+        )
+        .unwrap();
+        upsert_vulnerability(
+            &conn,
+            &advisory("GHSA-aaaa-bbbb-cccc", 7.5, &["CVE-2024-1234"]),
+        )
+        .unwrap();
+        upsert_vulnerability(&conn, &advisory("RUSTSEC-2024-0002", 4.0, &[])).unwrap();
+
+        for id in ["RUSTSEC-2024-0001", "GHSA-aaaa-bbbb-cccc"] {
+            assert_eq!(
+                get_canonical_id(&conn, id).unwrap().as_deref(),
+                Some("CVE-2024-1234")
+            );
+        }
+        assert_eq!(
+            get_aliases(&conn, "GHSA-aaaa-bbbb-cccc").unwrap(),
+            vec!["CVE-2024-1234", "RUSTSEC-2024-0001"]
+        );
+
+        let list = get_vulnerabilities(&conn, &VulnFilter::default(), 1, 20).unwrap();
+        assert_eq!(list.total, 2);
+        assert_eq!(list.vulnerabilities.len(), 2);
+        let merged = list
+            .vulnerabilities
+            .iter()
+            .find(|v| v.id == "GHSA-aaaa-bbbb-cccc")
+            .expect("highest-scored advisory represents the group");
+        assert!(merged.aliases.contains(&"RUSTSEC-2024-0001".to_string()));
+        assert_eq!(get_vulnerability_count(&conn, None).unwrap(), 2);
+
+        // 別名でも検索できる
+        let filter = VulnFilter {
+            ecosystem: None,
+            severity: None,
+            search: Some("CVE-2024-1234".to_string()),
+        };
+        assert_eq!(get_vulnerabilities(&conn, &filter, 1, 20).unwrap().total, 1);
+
+        // 代表は条件に一致した行から選ぶ（グループ内のより深刻な行は返さない）
+        let filter = VulnFilter {
+            ecosystem: None,
+            severity: Some("medium".to_string()),
+            search: None,
+        };
+        let list = get_vulnerabilities(&conn, &filter, 1, 20).unwrap();
+        assert_eq!(list.total, 2);
+        let mut ids: Vec<&str> = list.vulnerabilities.iter().map(|v| v.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["RUSTSEC-2024-0001", "RUSTSEC-2024-0002"]);
+        assert!(list.vulnerabilities.iter().all(|v| v.severity == "medium"));
+
+        assert_eq!(preferred_id(["PYSEC-1", "GHSA-x", "CVE-2"]), Some("CVE-2"));
+    }
+
+    #[cfg(feature = "desktop")]
+    #[test]
+    fn test_suppressions_crud() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("vuln.db");
+        let db_path = db_path.to_str().unwrap();
+        init_db(db_path).unwrap();
+        let conn = get_connection(db_path).unwrap();
+
+        let global = Suppression {
+            vuln_id: Some("GHSA-abc".to_string()),
+            expires: Some("2026-12-31".to_string()),
+            reason: "accepted".to_string(),
+            ..Default::default()
+        };
+        let scoped = Suppression {
+            package: Some("lodash".to_string()),
+            directory: Some("/work/app".to_string()),
+            reason: "build only".to_string(),
+            ..Default::default()
+        };
+        let global_id = add_suppression(&conn, &global).unwrap();
+        add_suppression(&conn, &scoped).unwrap();
+
+        assert_eq!(get_suppressions(&conn, None).unwrap().len(), 2);
+        assert_eq!(get_suppressions(&conn, Some("/work/app")).unwrap().len(), 2);
+        let other = get_suppressions(&conn, Some("/work/other")).unwrap();
+        assert_eq!(other.len(), 1);
+        assert_eq!(other[0].id, Some(global_id));
+        assert_eq!(other[0].source, "database");
+        assert_eq!(other[0].expires.as_deref(), Some("2026-12-31"));
+
+        assert_eq!(delete_suppression(&conn, global_id).unwrap(), 1);
+        assert_eq!(get_suppressions(&conn, None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_scan_runs_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("vuln.db");
+        let db_path = db_path.to_str().unwrap();
+        init_db(db_path).unwrap();
+        let conn = get_connection(db_path).unwrap();
+
+        let finding = |vuln_id: &str, suppressed: bool| ScanFinding {
+            project: ".".to_string(),
+            source_file: "/work/app/Cargo.lock".to_string(),
+            ecosystem: "crates.io".to_string(),
+            package_name: "time".to_string(),
+            installed_version: "0.1.0".to_string(),
+            vuln_id: vuln_id.to_string(),
+            canonical_id: vuln_id.to_string(),
+            severity: "medium".to_string(),
+            suppressed,
+        };
+        let first = save_scan_run(&conn, "/work/app", 12, &[finding("RUSTSEC-1", false)]).unwrap();
+        save_scan_run(&conn, "/work/other", 3, &[]).unwrap();
+        let second = save_scan_run(
+            &conn,
+            "/work/app",
+            12,
+            &[finding("RUSTSEC-1", false), finding("RUSTSEC-2", true)],
+        )
+        .unwrap();
+
+        let runs = get_scan_runs(&conn, Some("/work/app"), 10).unwrap();
+        assert_eq!(
+            runs.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![second, first]
+        );
+        assert_eq!(runs[0].vuln_count, 1);
+        assert_eq!(get_scan_runs(&conn, None, 10).unwrap().len(), 3);
+
+        let latest = get_scan_run(&conn, second).unwrap().unwrap();
+        assert_eq!(latest.total_packages, 12);
+        let previous = get_previous_scan_run(&conn, &latest).unwrap().unwrap();
+        assert_eq!(previous.id, first);
+        assert!(get_previous_scan_run(&conn, &previous).unwrap().is_none());
+        assert!(get_scan_run(&conn, 999).unwrap().is_none());
+
+        let findings = get_scan_findings(&conn, second).unwrap();
+        assert_eq!(
+            findings,
+            vec![finding("RUSTSEC-1", false), finding("RUSTSEC-2", true)]
+        );
+    }
+
+    #[cfg(feature = "desktop")]
+    #[test]
+    fn test_watched_projects() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("vuln.db");
+        let db_path = db_path.to_str().unwrap();
+        init_db(db_path).unwrap();
+        let conn = get_connection(db_path).unwrap();
+
+        let app = add_watched_project(&conn, "/work/app", 24).unwrap();
+        let lib = add_watched_project(&conn, "/work/lib", 6).unwrap();
+        // 登録済みのディレクトリは間隔を更新する
+        assert_eq!(add_watched_project(&conn, "/work/app", 12).unwrap(), app);
+        assert_eq!(get_watched_projects(&conn).unwrap()[0].interval_hours, 12);
+
+        // 未スキャンのものはすぐに対象
+        assert_eq!(get_due_watched_projects(&conn).unwrap().len(), 2);
+
+        mark_watched_project_scanned(&conn, app, Some(7), None).unwrap();
+        mark_watched_project_scanned(&conn, lib, None, Some("not found")).unwrap();
+        assert!(get_due_watched_projects(&conn).unwrap().is_empty());
+        let projects = get_watched_projects(&conn).unwrap();
+        assert_eq!(projects[0].last_run_id, Some(7));
+        assert!(projects[0].last_scanned_at.is_some());
+        assert_eq!(projects[1].last_error.as_deref(), Some("not found"));
+
+        // 間隔が過ぎたものだけが対象
+        conn.execute(
+            "UPDATE watched_projects SET last_scanned_at = datetime('now', '-7 hours') WHERE id = ?1",
+            [lib],
+        )
+        .unwrap();
+        let due = get_due_watched_projects(&conn).unwrap();
+        assert_eq!(due.iter().map(|p| p.id).collect::<Vec<_>>(), vec![lib]);
+
+        set_watched_project_enabled(&conn, lib, false).unwrap();
+        assert!(get_due_watched_projects(&conn).unwrap().is_empty());
+        assert_eq!(delete_watched_project(&conn, lib).unwrap(), 1);
+        assert_eq!(get_watched_projects(&conn).unwrap().len(), 1);
+    }
 }
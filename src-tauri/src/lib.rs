@@ -1,19 +1,30 @@
 mod api;
+pub mod cli;
 mod commands;
+mod cvss;
 mod db;
+mod report;
 mod scanner;
+#[cfg(feature = "desktop")]
+mod scheduler;
 mod settings;
+mod version;
 
 use settings::AppSettings;
 use std::path::PathBuf;
 use std::sync::RwLock;
+#[cfg(feature = "desktop")]
 use tauri::Manager;
+#[cfg(feature = "desktop")]
+use tauri_plugin_notification::NotificationExt;
 
+#[cfg(feature = "desktop")]
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             // Get app data directory
             let app_handle = app.handle();
@@ -28,6 +39,28 @@ pub fn run() {
             // Load settings
             let settings = AppSettings::load(&app_data_dir);
             
+            // Rescan watched projects in the background and notify about new high/critical findings
+            let scheduler_handle = app_handle.clone();
+            let scheduler_db_path = db_path_str.clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(scheduler::CHECK_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    for alert in scheduler::rescan_due(&scheduler_db_path).await {
+                        let (title, body) = scheduler::notification(&alert);
+                        if let Err(e) = scheduler_handle
+                            .notification()
+                            .builder()
+                            .title(title)
+                            .body(body)
+                            .show()
+                        {
+                            eprintln!("Failed to show notification: {}", e);
+                        }
+                    }
+                }
+            });
+
             // Store state
             app.manage(AppState {
                 db_path: db_path_str,
@@ -66,6 +99,18 @@ pub fn run() {
             commands::vuln_commands::get_vulnerabilities,
             commands::vuln_commands::fetch_vulnerabilities,
             commands::vuln_commands::scan_directory,
+            commands::vuln_commands::import_osv_archive,
+            commands::vuln_commands::get_suppressions,
+            commands::vuln_commands::add_suppression,
+            commands::vuln_commands::delete_suppression,
+            commands::vuln_commands::get_watched_projects,
+            commands::vuln_commands::add_watched_project,
+            commands::vuln_commands::set_watched_project_enabled,
+            commands::vuln_commands::delete_watched_project,
+            commands::vuln_commands::get_scan_runs,
+            commands::vuln_commands::diff_scan_runs,
+            commands::vuln_commands::export_scan_sarif,
+            commands::vuln_commands::export_sbom,
             commands::vuln_commands::get_vulnerability_detail,
             commands::vuln_commands::get_scan_history,
             commands::vuln_commands::get_vulnerability_count,
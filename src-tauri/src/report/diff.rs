@@ -0,0 +1,192 @@
+//! スキャン結果の差分
+//! 同じプロジェクトの 2 回のスキャンを、新規 / 修正済み / 継続中の検出に分類する
+
+use crate::commands::vuln_commands::severity_order;
+use crate::db::models::{ScanDiff, ScanFinding, ScanRun, VulnMatch};
+use crate::db::vuln_queries::normalize_package;
+
+/// 検出を照合するキー（バージョンの更新後も同じ問題が残っていれば継続中とみなす）
+fn finding_key(finding: &ScanFinding) -> (String, String, String, String) {
+    (
+        finding.project.clone(),
+        finding.ecosystem.clone(),
+        normalize_package(&finding.ecosystem, &finding.package_name),
+        finding.canonical_id.clone(),
+    )
+}
+
+/// スキャン結果の検出を保存用に変換（canonical_id は呼び出し側で解決したもの）
+pub fn to_finding(m: &VulnMatch, canonical_id: String) -> ScanFinding {
+    ScanFinding {
+        project: m.project.clone(),
+        source_file: m.source_file.clone(),
+        ecosystem: m.vulnerability.affected_ecosystem.clone(),
+        package_name: m.package_name.clone(),
+        installed_version: m.installed_version.clone(),
+        vuln_id: m.vulnerability.id.clone(),
+        canonical_id,
+        severity: m.vulnerability.severity.clone(),
+        suppressed: m.suppression.is_some(),
+    }
+}
+
+/// 2 回のスキャンの検出を比較
+pub fn diff(
+    base: ScanRun,
+    base_findings: Vec<ScanFinding>,
+    target: ScanRun,
+    target_findings: Vec<ScanFinding>,
+) -> ScanDiff {
+    let base_keys: Vec<_> = base_findings.iter().map(finding_key).collect();
+    let target_keys: Vec<_> = target_findings.iter().map(finding_key).collect();
+
+    let (still_present, new): (Vec<_>, Vec<_>) = target_findings
+        .into_iter()
+        .zip(&target_keys)
+        .partition(|(_, key)| base_keys.contains(key));
+    let mut new: Vec<ScanFinding> = new.into_iter().map(|(finding, _)| finding).collect();
+    let mut still_present: Vec<ScanFinding> = still_present
+        .into_iter()
+        .map(|(finding, _)| finding)
+        .collect();
+    let mut fixed: Vec<ScanFinding> = base_findings
+        .into_iter()
+        .zip(&base_keys)
+        .filter(|(_, key)| !target_keys.contains(key))
+        .map(|(finding, _)| finding)
+        .collect();
+    for findings in [&mut new, &mut fixed, &mut still_present] {
+        sort_findings(findings);
+    }
+
+    ScanDiff {
+        base,
+        target,
+        new,
+        fixed,
+        still_present,
+    }
+}
+
+/// 深刻度の高い順、同じならパッケージ名順
+fn sort_findings(findings: &mut [ScanFinding]) {
+    findings.sort_by(|a, b| {
+        severity_order(&b.severity)
+            .cmp(&severity_order(&a.severity))
+            .then_with(|| a.package_name.cmp(&b.package_name))
+            .then_with(|| a.vuln_id.cmp(&b.vuln_id))
+    });
+}
+
+/// 週次レビュー向けのテキスト
+pub fn format_diff(diff: &ScanDiff) -> String {
+    let mut lines = vec![format!(
+        "Scan #{} ({}) -> #{} ({}): {} new, {} fixed, {} still present",
+        diff.base.id,
+        diff.base.scanned_at,
+        diff.target.id,
+        diff.target.scanned_at,
+        diff.new.len(),
+        diff.fixed.len(),
+        diff.still_present.len()
+    )];
+    for (title, findings) in [
+        ("New", &diff.new),
+        ("Fixed", &diff.fixed),
+        ("Still present", &diff.still_present),
+    ] {
+        if findings.is_empty() {
+            continue;
+        }
+        lines.push(format!("\n{}:", title));
+        for f in findings {
+            let project = if f.project.is_empty() || f.project == "." {
+                String::new()
+            } else {
+                format!("{}: ", f.project)
+            };
+            let suppressed = if f.suppressed { " [suppressed]" } else { "" };
+            lines.push(format!(
+                "  [{:<8}] {}{} {}@{}{}",
+                f.severity, project, f.vuln_id, f.package_name, f.installed_version, suppressed
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(id: i64) -> ScanRun {
+        ScanRun {
+            id,
+            directory: "/work/app".to_string(),
+            total_packages: 10,
+            vuln_count: 2,
+            scanned_at: format!("2026-10-0{}T09:00:00Z", id),
+        }
+    }
+
+    fn finding(
+        package: &str,
+        version: &str,
+        vuln_id: &str,
+        canonical_id: &str,
+        severity: &str,
+    ) -> ScanFinding {
+        ScanFinding {
+            project: ".".to_string(),
+            source_file: "/work/app/package-lock.json".to_string(),
+            ecosystem: "npm".to_string(),
+            package_name: package.to_string(),
+            installed_version: version.to_string(),
+            vuln_id: vuln_id.to_string(),
+            canonical_id: canonical_id.to_string(),
+            severity: severity.to_string(),
+            suppressed: false,
+        }
+    }
+
+    #[test]
+    fn test_diff_classifies_findings() {
+        let base = vec![
+            finding("lodash", "4.17.20", "GHSA-a", "CVE-2024-1", "high"),
+            finding("axios", "0.21.0", "GHSA-b", "CVE-2024-2", "medium"),
+        ];
+        let target = vec![
+            // バージョンを上げても残っていて、代表 ID が別名に変わっていても継続中
+            finding("lodash", "4.17.21", "CVE-2024-1", "CVE-2024-1", "high"),
+            finding("minimist", "1.2.5", "GHSA-c", "GHSA-c", "low"),
+            finding("express", "4.0.0", "GHSA-d", "GHSA-d", "critical"),
+        ];
+
+        let diff = diff(run(1), base, run(2), target);
+        let ids = |findings: &[ScanFinding]| -> Vec<String> {
+            findings.iter().map(|f| f.vuln_id.clone()).collect()
+        };
+        assert_eq!(ids(&diff.new), vec!["GHSA-d", "GHSA-c"]);
+        assert_eq!(ids(&diff.fixed), vec!["GHSA-b"]);
+        assert_eq!(ids(&diff.still_present), vec!["CVE-2024-1"]);
+        assert_eq!(diff.still_present[0].installed_version, "4.17.21");
+
+        let text = format_diff(&diff);
+        assert!(text.starts_with("Scan #1 (2026-10-01T09:00:00Z) -> #2"));
+        assert!(text.contains("2 new, 1 fixed, 1 still present"));
+        assert!(text.contains("Fixed:\n  [medium  ] GHSA-b axios@0.21.0"));
+    }
+
+    #[test]
+    fn test_diff_separates_projects() {
+        let mut web = finding("lodash", "4.17.20", "GHSA-a", "GHSA-a", "high");
+        web.project = "services/web".to_string();
+        let api = finding("lodash", "4.17.20", "GHSA-a", "GHSA-a", "high");
+
+        // 同じ脆弱性でも別のサブプロジェクトに移ったら新規 + 修正済み
+        let diff = diff(run(1), vec![api], run(2), vec![web]);
+        assert_eq!(diff.new.len(), 1);
+        assert_eq!(diff.fixed.len(), 1);
+        assert!(diff.still_present.is_empty());
+    }
+}
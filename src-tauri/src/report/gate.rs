@@ -0,0 +1,257 @@
+//! CI ゲート判定
+//! スキャン結果を深刻度・CVSS の閾値で pass / fail に変換する
+
+use crate::commands::vuln_commands::severity_order;
+use crate::db::models::{ScanResult, VulnMatch};
+use serde::Serialize;
+
+/// ゲート条件（いずれかの閾値を満たす脆弱性が 1 件でもあれば失敗）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GatePolicy {
+    /// この深刻度以上で失敗（"critical" / "high" / "medium" / "low"）
+    pub min_severity: Option<String>,
+    /// この CVSS スコア以上で失敗
+    pub min_cvss: Option<f64>,
+}
+
+impl GatePolicy {
+    /// 深刻度名を検証してポリシーを作成
+    pub fn new(min_severity: Option<&str>, min_cvss: Option<f64>) -> Result<Self, String> {
+        if let Some(sev) = min_severity {
+            if severity_order(sev) == 0 {
+                return Err(format!(
+                    "invalid severity: {} (expected critical, high, medium or low)",
+                    sev
+                ));
+            }
+        }
+        if let Some(score) = min_cvss {
+            if !(0.0..=10.0).contains(&score) {
+                return Err(format!("invalid CVSS score: {} (expected 0.0-10.0)", score));
+            }
+        }
+        if min_severity.is_none() && min_cvss.is_none() {
+            return Err("a gate needs a minimum severity or a CVSS floor".to_string());
+        }
+        Ok(Self {
+            min_severity: min_severity.map(|s| s.to_lowercase()),
+            min_cvss,
+        })
+    }
+
+    /// 脆弱性がゲート条件に該当するか（抑制済みの検出は該当しない）
+    pub fn is_violation(&self, m: &VulnMatch) -> bool {
+        if m.suppression.is_some() {
+            return false;
+        }
+        let by_severity = self
+            .min_severity
+            .as_deref()
+            .is_some_and(|min| severity_order(&m.vulnerability.severity) >= severity_order(min));
+        let by_cvss = match (self.min_cvss, m.vulnerability.cvss_score) {
+            (Some(floor), Some(score)) => score >= floor,
+            _ => false,
+        };
+        by_severity || by_cvss
+    }
+}
+
+/// ゲート判定結果
+#[derive(Debug, Clone, Serialize)]
+pub struct GateResult {
+    pub passed: bool,
+    pub violations: Vec<VulnMatch>,
+    /// (深刻度, 件数) — 違反のみを critical から順に集計
+    #[serde(rename = "severityCounts")]
+    pub severity_counts: Vec<(String, usize)>,
+}
+
+/// スキャン結果をゲート条件で評価
+pub fn evaluate(result: &ScanResult, policy: &GatePolicy) -> GateResult {
+    // 後回しは並び順だけの設定なので、開発用の依存関係の検出も深刻度を下げずに判定する
+    let violations: Vec<VulnMatch> = result
+        .vulnerabilities
+        .iter()
+        .filter(|m| policy.is_violation(m))
+        .cloned()
+        .collect();
+
+    let mut severity_counts: Vec<(String, usize)> = Vec::new();
+    for sev in ["critical", "high", "medium", "low", "unknown"] {
+        let count = violations
+            .iter()
+            .filter(|m| {
+                let order = severity_order(&m.vulnerability.severity);
+                if sev == "unknown" {
+                    order == 0
+                } else {
+                    order == severity_order(sev)
+                }
+            })
+            .count();
+        if count > 0 {
+            severity_counts.push((sev.to_string(), count));
+        }
+    }
+
+    GateResult {
+        passed: violations.is_empty(),
+        violations,
+        severity_counts,
+    }
+}
+
+/// CI ログ向けの簡潔なサマリー
+pub fn format_summary(gate: &GateResult, policy: &GatePolicy) -> String {
+    let mut threshold = Vec::new();
+    if let Some(ref sev) = policy.min_severity {
+        threshold.push(format!("severity >= {}", sev));
+    }
+    if let Some(score) = policy.min_cvss {
+        threshold.push(format!("CVSS >= {:.1}", score));
+    }
+    let threshold = threshold.join(" or ");
+
+    if gate.passed {
+        return format!("Gate PASSED: no findings with {}", threshold);
+    }
+
+    let counts = gate
+        .severity_counts
+        .iter()
+        .map(|(sev, n)| format!("{}: {}", sev, n))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut lines = vec![format!(
+        "Gate FAILED: {} finding(s) with {} ({})",
+        gate.violations.len(),
+        threshold,
+        counts
+    )];
+    for m in &gate.violations {
+        let score = m
+            .vulnerability
+            .cvss_score
+            .map(|s| format!(" CVSS {:.1}", s))
+            .unwrap_or_default();
+        lines.push(format!(
+            "  {} {}@{} [{}{}]",
+            m.vulnerability.id,
+            m.package_name,
+            m.installed_version,
+            m.vulnerability.severity,
+            score
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::Suppression;
+    use crate::scanner::DevFindings;
+
+    fn scan_result(vulns: Vec<VulnMatch>) -> ScanResult {
+        ScanResult {
+            directory: "/project".to_string(),
+            vulnerabilities: vulns,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_gate_by_severity() {
+        let result = scan_result(vec![
+            VulnMatch::fixture("GHSA-1", "lodash", "4.17.20").with_severity("critical", Some(9.8)),
+            VulnMatch::fixture("GHSA-2", "lodash", "4.17.20").with_severity("high", None),
+            VulnMatch::fixture("GHSA-3", "lodash", "4.17.20").with_severity("medium", Some(5.0)),
+        ]);
+        let policy = GatePolicy::new(Some("high"), None).unwrap();
+        let gate = evaluate(&result, &policy);
+        assert!(!gate.passed);
+        assert_eq!(gate.violations.len(), 2);
+        assert_eq!(
+            gate.severity_counts,
+            vec![("critical".to_string(), 1), ("high".to_string(), 1)]
+        );
+
+        let summary = format_summary(&gate, &policy);
+        assert!(summary.starts_with("Gate FAILED: 2 finding(s)"));
+        assert!(summary.contains("GHSA-1 lodash@4.17.20 [critical CVSS 9.8]"));
+    }
+
+    #[test]
+    fn test_gate_by_cvss_floor() {
+        let result = scan_result(vec![
+            VulnMatch::fixture("GHSA-1", "lodash", "4.17.20").with_severity("medium", Some(7.5)),
+            VulnMatch::fixture("GHSA-2", "lodash", "4.17.20").with_severity("low", Some(3.1)),
+        ]);
+        let policy = GatePolicy::new(Some("critical"), Some(7.0)).unwrap();
+        let gate = evaluate(&result, &policy);
+        assert!(!gate.passed);
+        assert_eq!(gate.violations.len(), 1);
+        assert_eq!(gate.violations[0].vulnerability.id, "GHSA-1");
+    }
+
+    #[test]
+    fn test_gate_ignores_suppressed() {
+        let mut accepted =
+            VulnMatch::fixture("GHSA-1", "lodash", "4.17.20").with_severity("critical", Some(9.8));
+        accepted.suppression = Some(Suppression {
+            vuln_id: Some("GHSA-1".to_string()),
+            reason: "not reachable".to_string(),
+            ..Default::default()
+        });
+        let result = scan_result(vec![
+            accepted,
+            VulnMatch::fixture("GHSA-2", "lodash", "4.17.20").with_severity("low", None),
+        ]);
+        let gate = evaluate(&result, &GatePolicy::new(Some("high"), None).unwrap());
+        assert!(gate.passed);
+    }
+
+    #[test]
+    fn test_gate_deprioritize_differs_from_exclude() {
+        let mut tooling =
+            VulnMatch::fixture("GHSA-1", "lodash", "4.17.20").with_severity("critical", Some(9.8));
+        tooling.dev = true;
+        let policy = GatePolicy::new(Some("high"), None).unwrap();
+
+        // 後回しでも開発用の依存関係の検出は結果に残り、ゲートを失敗させる
+        let mut deprioritized = scan_result(vec![
+            tooling,
+            VulnMatch::fixture("GHSA-2", "lodash", "4.17.20").with_severity("low", None),
+        ]);
+        deprioritized.dev_findings = DevFindings::Deprioritize;
+        let gate = evaluate(&deprioritized, &policy);
+        assert!(!gate.passed);
+        assert_eq!(gate.violations[0].vulnerability.id, "GHSA-1");
+
+        // 除外では開発用の依存関係が照会されないため、検出がなくゲートを通過する
+        let mut excluded = scan_result(vec![
+            VulnMatch::fixture("GHSA-2", "lodash", "4.17.20").with_severity("low", None)
+        ]);
+        excluded.dev_findings = DevFindings::Exclude;
+        assert!(evaluate(&excluded, &policy).passed);
+    }
+
+    #[test]
+    fn test_gate_passes() {
+        let result = scan_result(vec![
+            VulnMatch::fixture("GHSA-1", "lodash", "4.17.20").with_severity("low", Some(2.0))
+        ]);
+        let policy = GatePolicy::new(Some("medium"), None).unwrap();
+        let gate = evaluate(&result, &policy);
+        assert!(gate.passed);
+        assert!(format_summary(&gate, &policy).starts_with("Gate PASSED"));
+    }
+
+    #[test]
+    fn test_invalid_policy() {
+        assert!(GatePolicy::new(Some("severe"), None).is_err());
+        assert!(GatePolicy::new(None, Some(11.0)).is_err());
+        assert!(GatePolicy::new(None, None).is_err());
+    }
+}
@@ -0,0 +1,8 @@
+//! スキャン結果のレポート・エクスポート
+
+pub mod diff;
+pub mod gate;
+pub mod remediation;
+pub mod sarif;
+pub mod sbom;
+pub mod suppression;
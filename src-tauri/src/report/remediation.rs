@@ -0,0 +1,211 @@
+//! アップグレード計画
+//! スキャン結果を依存関係ごとにまとめ、該当する脆弱性をすべて解消する最小のバージョンを提案する
+
+use crate::api::osv::OsvAffected;
+use crate::commands::vuln_commands::severity_order;
+use crate::db::models::{Remediation, VulnMatch};
+use crate::version::upgrade::{self, FixInfo};
+use crate::version::VersionMatch;
+
+/// 検出を依存関係（サブプロジェクト・ロックファイル・パッケージ・バージョン）ごとにまとめて計画を作る
+/// 抑制済みの検出と影響範囲外と判定された検出は対象外。
+/// affected はエコシステムとパッケージ名から保存済みの (脆弱性 ID, affected エントリ) を返す
+pub fn plan<F>(matches: &[VulnMatch], affected: F) -> Vec<Remediation>
+where
+    F: Fn(&str, &str) -> Vec<(String, OsvAffected)>,
+{
+    let mut groups: Vec<Vec<&VulnMatch>> = Vec::new();
+    for m in matches {
+        if m.suppression.is_some() || m.version_match == VersionMatch::NotAffected {
+            continue;
+        }
+        let same_dependency = |other: &&VulnMatch| {
+            other.project == m.project
+                && other.source_file == m.source_file
+                && other.vulnerability.affected_ecosystem == m.vulnerability.affected_ecosystem
+                && other.package_name == m.package_name
+                && other.installed_version == m.installed_version
+        };
+        match groups.iter_mut().find(|g| same_dependency(&g[0])) {
+            Some(group) => group.push(m),
+            None => groups.push(vec![m]),
+        }
+    }
+
+    let mut plan: Vec<Remediation> = groups
+        .into_iter()
+        .map(|group| remediate(&group, &affected))
+        .collect();
+    plan.sort_by(|a, b| {
+        severity_order(&b.severity)
+            .cmp(&severity_order(&a.severity))
+            .then_with(|| a.package_name.cmp(&b.package_name))
+            .then_with(|| a.project.cmp(&b.project))
+    });
+    plan
+}
+
+/// 1 つの依存関係について計画を作る
+fn remediate<F>(group: &[&VulnMatch], affected: &F) -> Remediation
+where
+    F: Fn(&str, &str) -> Vec<(String, OsvAffected)>,
+{
+    let first = group[0];
+    let ecosystem = &first.vulnerability.affected_ecosystem;
+    let stored = affected(ecosystem, &first.package_name);
+
+    let fixes: Vec<FixInfo> = group
+        .iter()
+        .map(|m| {
+            let vuln = &m.vulnerability;
+            // 統合済みの検出は別名の ID で保存されていることがある
+            let entries: Vec<&OsvAffected> = stored
+                .iter()
+                .filter(|(id, _)| *id == vuln.id || vuln.aliases.contains(id))
+                .map(|(_, entry)| entry)
+                .collect();
+            let mut fixed: Vec<String> = vuln
+                .fixed_versions
+                .as_deref()
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+                .collect();
+            for version in entries.iter().flat_map(|entry| entry.fixed_versions()) {
+                if !fixed.contains(&version) {
+                    fixed.push(version);
+                }
+            }
+            FixInfo {
+                vuln_id: &vuln.id,
+                fixed,
+                affected: entries,
+            }
+        })
+        .collect();
+    let advice = upgrade::advise(ecosystem, &first.installed_version, &fixes);
+
+    let severity = group
+        .iter()
+        .map(|m| &m.vulnerability.severity)
+        .max_by_key(|s| severity_order(s))
+        .cloned()
+        .unwrap_or_default();
+
+    Remediation {
+        project: first.project.clone(),
+        source_file: first.source_file.clone(),
+        ecosystem: ecosystem.clone(),
+        package_name: first.package_name.clone(),
+        installed_version: first.installed_version.clone(),
+        severity,
+        target_version: advice.version,
+        major_bump: advice.major_bump,
+        fixes: advice.fixes,
+        unfixed: advice.unfixed,
+    }
+}
+
+/// 開発者に渡すためのテキスト
+pub fn format_plan(plan: &[Remediation]) -> String {
+    let mut lines = Vec::new();
+    for r in plan {
+        let project = if r.project.is_empty() || r.project == "." {
+            String::new()
+        } else {
+            format!("{}: ", r.project)
+        };
+        let mut line = match r.target_version {
+            Some(ref target) => format!(
+                "  {}{} {} -> {}{} (fixes {})",
+                project,
+                r.package_name,
+                r.installed_version,
+                target,
+                if r.major_bump { " [major]" } else { "" },
+                r.fixes.join(", ")
+            ),
+            None => format!(
+                "  {}{} {}: no fixed version",
+                project, r.package_name, r.installed_version
+            ),
+        };
+        if r.target_version.is_some() && !r.unfixed.is_empty() {
+            line.push_str(&format!("; still affected by {}", r.unfixed.join(", ")));
+        } else if r.target_version.is_none() {
+            line.push_str(&format!(" for {}", r.unfixed.join(", ")));
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_per_dependency() {
+        let mut suppressed = VulnMatch::fixture("GHSA-s", "lodash", "4.17.11")
+            .with_severity("critical", None)
+            .with_fixed("5.0.0");
+        suppressed.suppression = Some(Default::default());
+        let matches = vec![
+            VulnMatch::fixture("GHSA-a", "lodash", "4.17.11")
+                .with_severity("medium", None)
+                .with_fixed("4.17.12"),
+            VulnMatch::fixture("GHSA-b", "lodash", "4.17.11")
+                .with_severity("high", None)
+                .with_fixed("4.17.19, 4.17.21"),
+            suppressed,
+            VulnMatch::fixture("GHSA-c", "minimist", "0.2.0")
+                .with_severity("low", None)
+                .with_fixed("1.2.6"),
+            VulnMatch::fixture("GHSA-d", "left-pad", "1.0.0").with_severity("critical", None),
+        ];
+
+        let plan = plan(&matches, |_, _| Vec::new());
+        assert_eq!(plan.len(), 3);
+
+        assert_eq!(plan[0].package_name, "left-pad");
+        assert_eq!(plan[0].target_version, None);
+        assert_eq!(plan[0].unfixed, vec!["GHSA-d"]);
+
+        // 抑制済みの 5.0.0 は考慮せず、両方を解消する最小のバージョン
+        let lodash = &plan[1];
+        assert_eq!(lodash.severity, "high");
+        assert_eq!(lodash.target_version.as_deref(), Some("4.17.19"));
+        assert_eq!(lodash.fixes, vec!["GHSA-a", "GHSA-b"]);
+        assert!(!lodash.major_bump);
+
+        assert!(plan[2].major_bump);
+
+        let text = format_plan(&plan);
+        assert!(text.contains("  left-pad 1.0.0: no fixed version for GHSA-d"));
+        assert!(text.contains("  lodash 4.17.11 -> 4.17.19 (fixes GHSA-a, GHSA-b)"));
+        assert!(text.contains("  minimist 0.2.0 -> 1.2.6 [major] (fixes GHSA-c)"));
+    }
+
+    #[test]
+    fn test_plan_uses_stored_ranges() {
+        let matches = vec![VulnMatch::fixture("GHSA-a", "pkg", "1.2.0")
+            .with_severity("high", None)
+            .with_fixed("1.2.5, 2.0.3")];
+        let stored: OsvAffected = serde_json::from_value(serde_json::json!({
+            "package": {"ecosystem": "npm", "name": "pkg"},
+            "ranges": [{"type": "SEMVER", "events": [
+                {"introduced": "1.0.0"}, {"fixed": "1.2.5"},
+                {"introduced": "2.0.0"}, {"fixed": "2.0.3"}
+            ]}]
+        }))
+        .unwrap();
+
+        let plan = plan(&matches, |ecosystem, name| {
+            assert_eq!((ecosystem, name), ("npm", "pkg"));
+            vec![("GHSA-a".to_string(), stored.clone())]
+        });
+        assert_eq!(plan[0].target_version.as_deref(), Some("1.2.5"));
+    }
+}
@@ -0,0 +1,409 @@
+//! SARIF 2.1.0 エクスポート
+//! https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use crate::db::models::{ScanResult, VulnMatch};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SRCROOT: &str = "%SRCROOT%";
+
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub original_uri_base_ids: HashMap<String, SarifArtifactLocation>,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: String,
+    pub version: String,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub short_description: SarifMessage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_description: Option<SarifMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help_uri: Option<String>,
+    pub default_configuration: SarifConfiguration,
+    pub properties: SarifRuleProperties,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifConfiguration {
+    pub level: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifRuleProperties {
+    pub tags: Vec<String>,
+    /// GitHub code scanning が深刻度の判定に使う CVSS スコア（文字列）
+    #[serde(rename = "security-severity", skip_serializing_if = "Option::is_none")]
+    pub security_severity: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: String,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+    pub partial_fingerprints: HashMap<String, String>,
+    /// 抑制ルールで受容済みの検出（GitHub code scanning では却下扱いになる）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<SarifSuppression>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifSuppression {
+    /// 結果の外部（.paperstack-ignore.toml / データベース）で抑制したので "external"
+    pub kind: String,
+    pub status: String,
+    pub justification: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri_base_id: Option<String>,
+}
+
+/// スキャン結果を SARIF ログに変換
+pub fn to_sarif(result: &ScanResult) -> SarifLog {
+    let mut rules: Vec<SarifRule> = Vec::new();
+    let mut rule_indices: HashMap<String, usize> = HashMap::new();
+    let mut results = Vec::new();
+    // "scan ." のような相対パスでも originalUriBaseIds が絶対 URI になるように一度だけ解決する
+    let directory = absolute_path(&result.directory);
+
+    for m in &result.vulnerabilities {
+        let vuln = &m.vulnerability;
+        let rule_index = *rule_indices.entry(vuln.id.clone()).or_insert_with(|| {
+            rules.push(build_rule(m));
+            rules.len() - 1
+        });
+
+        let (uri, uri_base_id) = artifact_uri(&directory, &m.source_file);
+        let fixed = vuln
+            .fixed_versions
+            .as_deref()
+            .map(|f| format!(" Fixed in: {}.", f))
+            .unwrap_or_default();
+
+        let mut partial_fingerprints = HashMap::new();
+        partial_fingerprints.insert(
+            "packageVulnerability/v1".to_string(),
+            format!(
+                "{}:{}@{}:{}",
+                vuln.affected_ecosystem, m.package_name, m.installed_version, vuln.id
+            ),
+        );
+
+        results.push(SarifResult {
+            rule_id: vuln.id.clone(),
+            rule_index,
+            level: sarif_level(&vuln.severity).to_string(),
+            message: SarifMessage {
+                text: format!(
+                    "{} {} ({}) is affected by {}: {}.{}",
+                    m.package_name,
+                    m.installed_version,
+                    vuln.affected_ecosystem,
+                    vuln.id,
+                    vuln.title,
+                    fixed
+                ),
+            },
+            locations: vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation { uri, uri_base_id },
+                },
+            }],
+            partial_fingerprints,
+            suppressions: m
+                .suppression
+                .iter()
+                .map(|s| SarifSuppression {
+                    kind: "external".to_string(),
+                    status: "accepted".to_string(),
+                    justification: s.reason.clone(),
+                })
+                .collect(),
+        });
+    }
+
+    let mut original_uri_base_ids = HashMap::new();
+    original_uri_base_ids.insert(
+        SRCROOT.to_string(),
+        SarifArtifactLocation {
+            uri: directory_uri(&directory),
+            uri_base_id: None,
+        },
+    );
+
+    SarifLog {
+        schema: SARIF_SCHEMA.to_string(),
+        version: SARIF_VERSION.to_string(),
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "Paperstack".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    rules,
+                },
+            },
+            original_uri_base_ids,
+            results,
+        }],
+    }
+}
+
+/// SARIF を JSON 文字列に変換
+pub fn to_sarif_json(result: &ScanResult) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&to_sarif(result))
+}
+
+fn build_rule(m: &VulnMatch) -> SarifRule {
+    let vuln = &m.vulnerability;
+    SarifRule {
+        id: vuln.id.clone(),
+        short_description: SarifMessage {
+            text: vuln.title.clone(),
+        },
+        full_description: vuln
+            .description
+            .as_ref()
+            .filter(|d| !d.is_empty())
+            .map(|d| SarifMessage { text: d.clone() }),
+        help_uri: vuln.references.first().cloned(),
+        default_configuration: SarifConfiguration {
+            level: sarif_level(&vuln.severity).to_string(),
+        },
+        properties: SarifRuleProperties {
+            tags: vec![
+                "security".to_string(),
+                "vulnerability".to_string(),
+                vuln.affected_ecosystem.clone(),
+            ],
+            security_severity: vuln.cvss_score.map(|s| format!("{:.1}", s)),
+        },
+    }
+}
+
+/// 深刻度を SARIF の level に変換
+fn sarif_level(severity: &str) -> &'static str {
+    match severity.to_lowercase().as_str() {
+        "critical" | "high" => "error",
+        "medium" => "warning",
+        "low" => "note",
+        _ => "warning",
+    }
+}
+
+/// パスを絶対パスに変換（存在するパスはシンボリックリンクも解決）
+fn absolute_path(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    path.canonicalize()
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// ロックファイルのパスをスキャン対象ディレクトリ（絶対パス）からの相対 URI に変換
+fn artifact_uri(directory: &Path, source_file: &str) -> (String, Option<String>) {
+    let source_file = absolute_path(source_file);
+    match source_file.strip_prefix(directory) {
+        Ok(relative) => (
+            relative.to_string_lossy().replace('\\', "/"),
+            Some(SRCROOT.to_string()),
+        ),
+        Err(_) => (source_file.to_string_lossy().replace('\\', "/"), None),
+    }
+}
+
+/// ディレクトリを file:// URI に変換（SARIF では末尾スラッシュが必要）
+fn directory_uri(directory: &Path) -> String {
+    let path = directory.to_string_lossy().replace('\\', "/");
+    // Windows の canonicalize が付ける \\?\ を外す
+    let mut path = path.strip_prefix("//?/").unwrap_or(&path).to_string();
+    if !path.ends_with('/') {
+        path.push('/');
+    }
+    if path.starts_with('/') {
+        format!("file://{}", path)
+    } else {
+        format!("file:///{}", path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::Suppression;
+
+    fn sample_result() -> ScanResult {
+        ScanResult {
+            directory: "/work/app".to_string(),
+            vulnerabilities: vec![
+                VulnMatch::fixture("GHSA-aaaa", "lodash", "1.0.0")
+                    .with_severity("critical", Some(9.1))
+                    .with_source("/work/app/package-lock.json", "."),
+                VulnMatch::fixture("GHSA-bbbb", "axios", "1.0.0")
+                    .with_severity("low", None)
+                    .with_source("/work/app/web/yarn.lock", "."),
+                VulnMatch::fixture("GHSA-aaaa", "lodash-es", "1.0.0")
+                    .with_severity("critical", Some(9.1))
+                    .with_source("/work/app/package-lock.json", "."),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sarif_required_fields() {
+        let json: serde_json::Value =
+            serde_json::from_str(&to_sarif_json(&sample_result()).unwrap()).unwrap();
+
+        // sarifLog: version, runs
+        assert_eq!(json["version"], "2.1.0");
+        let runs = json["runs"].as_array().unwrap();
+        assert_eq!(runs.len(), 1);
+
+        // run: tool.driver.name
+        let driver = &runs[0]["tool"]["driver"];
+        assert!(driver["name"].is_string());
+
+        // reportingDescriptor: id（ルールは脆弱性IDごとに1つ）
+        let rules = driver["rules"].as_array().unwrap();
+        let rule_ids: Vec<&str> = rules.iter().map(|r| r["id"].as_str().unwrap()).collect();
+        assert_eq!(rule_ids, vec!["GHSA-aaaa", "GHSA-bbbb"]);
+        assert_eq!(rules[0]["properties"]["security-severity"], "9.1");
+        assert!(rules[1]["properties"].get("security-severity").is_none());
+
+        // result: message.text, ruleId / ruleIndex の整合性, level
+        let results = runs[0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        for r in results {
+            assert!(r["message"]["text"].is_string());
+            let index = r["ruleIndex"].as_u64().unwrap() as usize;
+            assert_eq!(rules[index]["id"], r["ruleId"]);
+            let level = r["level"].as_str().unwrap();
+            assert!(["none", "note", "warning", "error"].contains(&level));
+        }
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[1]["level"], "note");
+    }
+
+    #[test]
+    fn test_sarif_relative_directory() {
+        // "paperstack-cli scan ." と同じく、カレントディレクトリ（クレートのルート）からの相対パス
+        let result = ScanResult {
+            directory: ".".to_string(),
+            vulnerabilities: vec![VulnMatch::fixture("GHSA-aaaa", "serde", "1.0.0")
+                .with_severity("high", Some(7.5))
+                .with_source("./Cargo.lock", ".")],
+            ..Default::default()
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&to_sarif_json(&result).unwrap()).unwrap();
+
+        assert_eq!(json["version"], "2.1.0");
+        assert_eq!(json["$schema"], SARIF_SCHEMA);
+        let run = &json["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "Paperstack");
+
+        let base = run["originalUriBaseIds"][SRCROOT]["uri"].as_str().unwrap();
+        let cwd = std::env::current_dir().unwrap().canonicalize().unwrap();
+        assert_eq!(base, directory_uri(&cwd));
+        assert!(base.starts_with("file:///") && base.ends_with('/'));
+        assert!(!base.contains("/./"));
+
+        let result = &run["results"][0];
+        assert!(result["message"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("GHSA-aaaa"));
+        let location = &result["locations"][0]["physicalLocation"]["artifactLocation"];
+        assert_eq!(location["uri"], "Cargo.lock");
+        assert_eq!(location["uriBaseId"], SRCROOT);
+    }
+
+    #[test]
+    fn test_sarif_marks_suppressed_results() {
+        let mut result = sample_result();
+        result.vulnerabilities[1].suppression = Some(Suppression {
+            vuln_id: Some("GHSA-bbbb".to_string()),
+            reason: "dev only".to_string(),
+            ..Default::default()
+        });
+        let json: serde_json::Value =
+            serde_json::from_str(&to_sarif_json(&result).unwrap()).unwrap();
+        let results = json["runs"][0]["results"].as_array().unwrap();
+        assert!(results[0].get("suppressions").is_none());
+        assert_eq!(results[1]["suppressions"][0]["kind"], "external");
+        assert_eq!(results[1]["suppressions"][0]["justification"], "dev only");
+    }
+
+    #[test]
+    fn test_sarif_locations_point_at_lockfile() {
+        let log = to_sarif(&sample_result());
+        let run = &log.runs[0];
+        let location = &run.results[1].locations[0]
+            .physical_location
+            .artifact_location;
+        assert_eq!(location.uri, "web/yarn.lock");
+        assert_eq!(location.uri_base_id.as_deref(), Some(SRCROOT));
+        assert_eq!(run.original_uri_base_ids[SRCROOT].uri, "file:///work/app/");
+    }
+
+    #[test]
+    fn test_artifact_uri_outside_directory() {
+        assert_eq!(
+            artifact_uri(Path::new("/work/app"), "/other/Cargo.lock"),
+            ("/other/Cargo.lock".to_string(), None)
+        );
+    }
+}
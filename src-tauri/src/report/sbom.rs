@@ -0,0 +1,565 @@
+//! SBOM（CycloneDX 1.5 / SPDX 2.3）エクスポート
+//! スキャナーが収集した依存関係をそのまま部品表として出力する
+
+use crate::commands::vuln_commands::chrono_now;
+use crate::scanner::{purl, Dependency, ScanDependencies};
+use serde::Serialize;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+
+const TOOL_NAME: &str = "Paperstack";
+const SOURCE_FILE_PROPERTY: &str = "paperstack:sourceFile";
+
+/// SBOM の形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomFormat {
+    CycloneDx,
+    Spdx,
+}
+
+impl SbomFormat {
+    pub fn parse(s: &str) -> Option<SbomFormat> {
+        match s.to_lowercase().as_str() {
+            "cyclonedx" | "cdx" => Some(SbomFormat::CycloneDx),
+            "spdx" => Some(SbomFormat::Spdx),
+            _ => None,
+        }
+    }
+}
+
+/// 重複を除いた SBOM の構成要素
+struct Component<'a> {
+    dependency: &'a Dependency,
+    purl: Option<String>,
+    source_files: Vec<&'a str>,
+}
+
+/// 複数のロックファイルにまたがる同一パッケージを1つにまとめる
+fn collect_components(scans: &[ScanDependencies]) -> Vec<Component<'_>> {
+    let mut components: Vec<Component> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for scan in scans {
+        for dep in &scan.dependencies {
+            let purl = purl::to_purl(dep);
+            let key = purl
+                .clone()
+                .unwrap_or_else(|| format!("{}:{}@{}", dep.ecosystem, dep.name, dep.version));
+            match index.get(&key) {
+                Some(&i) => {
+                    if !components[i]
+                        .source_files
+                        .contains(&scan.source_file.as_str())
+                    {
+                        components[i].source_files.push(&scan.source_file);
+                    }
+                }
+                None => {
+                    index.insert(key, components.len());
+                    components.push(Component {
+                        dependency: dep,
+                        purl,
+                        source_files: vec![&scan.source_file],
+                    });
+                }
+            }
+        }
+    }
+
+    components
+}
+
+/// 指定形式の SBOM を JSON 文字列で生成
+pub fn to_sbom_json(
+    scans: &[ScanDependencies],
+    project_name: &str,
+    format: SbomFormat,
+) -> Result<String, serde_json::Error> {
+    match format {
+        SbomFormat::CycloneDx => serde_json::to_string_pretty(&to_cyclonedx(scans, project_name)),
+        SbomFormat::Spdx => serde_json::to_string_pretty(&to_spdx(scans, project_name)),
+    }
+}
+
+// ============================================================================
+// CycloneDX 1.5
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CycloneDxBom {
+    pub bom_format: String,
+    pub spec_version: String,
+    pub serial_number: String,
+    pub version: u32,
+    pub metadata: CycloneDxMetadata,
+    pub components: Vec<CycloneDxComponent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CycloneDxMetadata {
+    pub timestamp: String,
+    pub tools: CycloneDxTools,
+    pub component: CycloneDxComponent,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CycloneDxTools {
+    pub components: Vec<CycloneDxComponent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CycloneDxComponent {
+    #[serde(rename = "type")]
+    pub component_type: String,
+    #[serde(rename = "bom-ref", skip_serializing_if = "Option::is_none")]
+    pub bom_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purl: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hashes: Vec<CycloneDxHash>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<CycloneDxProperty>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CycloneDxHash {
+    pub alg: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CycloneDxProperty {
+    pub name: String,
+    pub value: String,
+}
+
+/// CycloneDX 1.5 の BOM を生成
+pub fn to_cyclonedx(scans: &[ScanDependencies], project_name: &str) -> CycloneDxBom {
+    let components = collect_components(scans)
+        .into_iter()
+        .map(|c| {
+            let dep = c.dependency;
+            // Maven は group / name を分けて記録する
+            let (group, name) = match (dep.ecosystem.as_str(), dep.name.split_once(':')) {
+                ("Maven", Some((g, a))) => (Some(g.to_string()), a.to_string()),
+                _ => (None, dep.name.clone()),
+            };
+            let bom_ref = c
+                .purl
+                .clone()
+                .unwrap_or_else(|| format!("{}:{}@{}", dep.ecosystem, dep.name, dep.version));
+            let mut properties = vec![CycloneDxProperty {
+                name: "paperstack:ecosystem".to_string(),
+                value: dep.ecosystem.clone(),
+            }];
+            properties.extend(c.source_files.iter().map(|f| CycloneDxProperty {
+                name: SOURCE_FILE_PROPERTY.to_string(),
+                value: f.to_string(),
+            }));
+
+            CycloneDxComponent {
+                component_type: "library".to_string(),
+                bom_ref: Some(bom_ref),
+                group,
+                name,
+                version: Some(dep.version.clone()),
+                purl: c.purl,
+                hashes: dep
+                    .checksum
+                    .iter()
+                    .map(|checksum| CycloneDxHash {
+                        alg: "SHA-256".to_string(),
+                        content: checksum.clone(),
+                    })
+                    .collect(),
+                properties,
+            }
+        })
+        .collect();
+
+    CycloneDxBom {
+        bom_format: "CycloneDX".to_string(),
+        spec_version: "1.5".to_string(),
+        serial_number: format!("urn:uuid:{}", new_uuid()),
+        version: 1,
+        metadata: CycloneDxMetadata {
+            timestamp: chrono_now(),
+            tools: CycloneDxTools {
+                components: vec![CycloneDxComponent {
+                    component_type: "application".to_string(),
+                    bom_ref: None,
+                    group: None,
+                    name: TOOL_NAME.to_string(),
+                    version: Some(env!("CARGO_PKG_VERSION").to_string()),
+                    purl: None,
+                    hashes: vec![],
+                    properties: vec![],
+                }],
+            },
+            component: CycloneDxComponent {
+                component_type: "application".to_string(),
+                bom_ref: Some(project_name.to_string()),
+                group: None,
+                name: project_name.to_string(),
+                version: None,
+                purl: None,
+                hashes: vec![],
+                properties: vec![],
+            },
+        },
+        components,
+    }
+}
+
+// ============================================================================
+// SPDX 2.3
+// ============================================================================
+
+const SPDX_NOASSERTION: &str = "NOASSERTION";
+const SPDX_ROOT_ID: &str = "SPDXRef-RootPackage";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxDocument {
+    pub spdx_version: String,
+    pub data_license: String,
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    pub name: String,
+    pub document_namespace: String,
+    pub creation_info: SpdxCreationInfo,
+    pub packages: Vec<SpdxPackage>,
+    pub relationships: Vec<SpdxRelationship>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SpdxCreationInfo {
+    pub created: String,
+    pub creators: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxPackage {
+    pub name: String,
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_info: Option<String>,
+    pub download_location: String,
+    pub files_analyzed: bool,
+    pub license_concluded: String,
+    pub license_declared: String,
+    pub copyright_text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_info: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checksums: Vec<SpdxChecksum>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub external_refs: Vec<SpdxExternalRef>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxChecksum {
+    pub algorithm: String,
+    pub checksum_value: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxExternalRef {
+    pub reference_category: String,
+    pub reference_type: String,
+    pub reference_locator: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxRelationship {
+    pub spdx_element_id: String,
+    pub relationship_type: String,
+    pub related_spdx_element: String,
+}
+
+/// SPDX 2.3 のドキュメントを生成
+pub fn to_spdx(scans: &[ScanDependencies], project_name: &str) -> SpdxDocument {
+    let mut packages = vec![SpdxPackage {
+        name: project_name.to_string(),
+        spdx_id: SPDX_ROOT_ID.to_string(),
+        version_info: None,
+        download_location: SPDX_NOASSERTION.to_string(),
+        files_analyzed: false,
+        license_concluded: SPDX_NOASSERTION.to_string(),
+        license_declared: SPDX_NOASSERTION.to_string(),
+        copyright_text: SPDX_NOASSERTION.to_string(),
+        source_info: None,
+        checksums: vec![],
+        external_refs: vec![],
+    }];
+    let mut relationships = vec![SpdxRelationship {
+        spdx_element_id: "SPDXRef-DOCUMENT".to_string(),
+        relationship_type: "DESCRIBES".to_string(),
+        related_spdx_element: SPDX_ROOT_ID.to_string(),
+    }];
+
+    for (i, c) in collect_components(scans).into_iter().enumerate() {
+        // SPDXID は [A-Za-z0-9.-]+ のみ許可されるため連番で振る
+        let spdx_id = format!("SPDXRef-Package-{}", i + 1);
+        let external_refs = c
+            .purl
+            .map(|p| SpdxExternalRef {
+                reference_category: "PACKAGE-MANAGER".to_string(),
+                reference_type: "purl".to_string(),
+                reference_locator: p,
+            })
+            .into_iter()
+            .collect();
+
+        packages.push(SpdxPackage {
+            name: c.dependency.name.clone(),
+            spdx_id: spdx_id.clone(),
+            version_info: Some(c.dependency.version.clone()),
+            download_location: SPDX_NOASSERTION.to_string(),
+            files_analyzed: false,
+            license_concluded: SPDX_NOASSERTION.to_string(),
+            license_declared: SPDX_NOASSERTION.to_string(),
+            copyright_text: SPDX_NOASSERTION.to_string(),
+            source_info: Some(format!("found in {}", c.source_files.join(", "))),
+            checksums: c
+                .dependency
+                .checksum
+                .iter()
+                .map(|checksum| SpdxChecksum {
+                    algorithm: "SHA256".to_string(),
+                    checksum_value: checksum.clone(),
+                })
+                .collect(),
+            external_refs,
+        });
+        relationships.push(SpdxRelationship {
+            spdx_element_id: SPDX_ROOT_ID.to_string(),
+            relationship_type: "DEPENDS_ON".to_string(),
+            related_spdx_element: spdx_id,
+        });
+    }
+
+    let namespace_name: String = project_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+
+    SpdxDocument {
+        spdx_version: "SPDX-2.3".to_string(),
+        data_license: "CC0-1.0".to_string(),
+        spdx_id: "SPDXRef-DOCUMENT".to_string(),
+        name: project_name.to_string(),
+        document_namespace: format!(
+            "https://paperstack.app/spdx/{}-{}",
+            namespace_name,
+            new_uuid()
+        ),
+        creation_info: SpdxCreationInfo {
+            created: chrono_now(),
+            creators: vec![format!("Tool: {}-{}", TOOL_NAME, env!("CARGO_PKG_VERSION"))],
+        },
+        packages,
+        relationships,
+    }
+}
+
+/// ランダムな UUID v4 を生成（外部クレートなし）
+fn new_uuid() -> String {
+    let random_u64 = || {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or_default(),
+        );
+        hasher.finish()
+    };
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&random_u64().to_le_bytes());
+    bytes[8..].copy_from_slice(&random_u64().to_le_bytes());
+    bytes[6] = (bytes[6] & 0x0f) | 0x40; // version 4
+    bytes[8] = (bytes[8] & 0x3f) | 0x80; // RFC 4122 variant
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_scans() -> Vec<ScanDependencies> {
+        let dep = |name: &str, version: &str, ecosystem: &str| Dependency {
+            name: name.to_string(),
+            version: version.to_string(),
+            ecosystem: ecosystem.to_string(),
+            ..Default::default()
+        };
+        vec![
+            ScanDependencies {
+                ecosystem: "npm".to_string(),
+                source_file: "/app/package-lock.json".to_string(),
+                dependencies: vec![
+                    dep("@types/node", "18.11.18", "npm"),
+                    dep("lodash", "4.17.21", "npm"),
+                ],
+                ..Default::default()
+            },
+            ScanDependencies {
+                ecosystem: "npm".to_string(),
+                source_file: "/app/web/yarn.lock".to_string(),
+                dependencies: vec![dep("lodash", "4.17.21", "npm")],
+                ..Default::default()
+            },
+            ScanDependencies {
+                ecosystem: "Maven".to_string(),
+                source_file: "/app/pom.xml".to_string(),
+                dependencies: vec![dep("org.slf4j:slf4j-api", "1.7.36", "Maven")],
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_cyclonedx() {
+        let json: serde_json::Value = serde_json::from_str(
+            &to_sbom_json(&sample_scans(), "app", SbomFormat::CycloneDx).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(json["bomFormat"], "CycloneDX");
+        assert_eq!(json["specVersion"], "1.5");
+        assert!(json["serialNumber"]
+            .as_str()
+            .unwrap()
+            .starts_with("urn:uuid:"));
+
+        let components = json["components"].as_array().unwrap();
+        assert_eq!(components.len(), 3); // lodash は重複排除
+        assert_eq!(components[0]["purl"], "pkg:npm/%40types/node@18.11.18");
+        assert_eq!(components[0]["bom-ref"], components[0]["purl"]);
+
+        let lodash = &components[1];
+        let sources: Vec<&str> = lodash["properties"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|p| p["name"] == SOURCE_FILE_PROPERTY)
+            .map(|p| p["value"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            sources,
+            vec!["/app/package-lock.json", "/app/web/yarn.lock"]
+        );
+
+        let slf4j = &components[2];
+        assert_eq!(slf4j["group"], "org.slf4j");
+        assert_eq!(slf4j["name"], "slf4j-api");
+        assert_eq!(slf4j["purl"], "pkg:maven/org.slf4j/slf4j-api@1.7.36");
+    }
+
+    #[test]
+    fn test_spdx() {
+        let json: serde_json::Value = serde_json::from_str(
+            &to_sbom_json(&sample_scans(), "my app", SbomFormat::Spdx).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(json["spdxVersion"], "SPDX-2.3");
+        assert_eq!(json["dataLicense"], "CC0-1.0");
+        assert_eq!(json["SPDXID"], "SPDXRef-DOCUMENT");
+        assert!(json["documentNamespace"]
+            .as_str()
+            .unwrap()
+            .starts_with("https://paperstack.app/spdx/my-app-"));
+
+        let packages = json["packages"].as_array().unwrap();
+        assert_eq!(packages.len(), 4); // ルート + 3
+        for p in packages {
+            let id = p["SPDXID"].as_str().unwrap();
+            assert!(id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.'));
+            assert!(p["downloadLocation"].is_string());
+        }
+        assert_eq!(
+            packages[2]["externalRefs"][0]["referenceLocator"],
+            "pkg:npm/lodash@4.17.21"
+        );
+
+        let relationships = json["relationships"].as_array().unwrap();
+        assert_eq!(relationships.len(), 4);
+        assert_eq!(relationships[0]["relationshipType"], "DESCRIBES");
+    }
+
+    #[test]
+    fn test_checksums() {
+        let scans = vec![ScanDependencies {
+            ecosystem: "crates.io".to_string(),
+            source_file: "/app/Cargo.lock".to_string(),
+            dependencies: vec![Dependency {
+                name: "regex".to_string(),
+                version: "1.10.2".to_string(),
+                ecosystem: "crates.io".to_string(),
+                checksum: Some("380b951a".to_string()),
+                ..Default::default()
+            }],
+            warnings: Vec::new(),
+        }];
+
+        let cyclonedx: serde_json::Value =
+            serde_json::from_str(&to_sbom_json(&scans, "app", SbomFormat::CycloneDx).unwrap())
+                .unwrap();
+        let hash = &cyclonedx["components"][0]["hashes"][0];
+        assert_eq!(hash["alg"], "SHA-256");
+        assert_eq!(hash["content"], "380b951a");
+
+        let spdx: serde_json::Value =
+            serde_json::from_str(&to_sbom_json(&scans, "app", SbomFormat::Spdx).unwrap()).unwrap();
+        let checksum = &spdx["packages"][1]["checksums"][0];
+        assert_eq!(checksum["algorithm"], "SHA256");
+        assert_eq!(checksum["checksumValue"], "380b951a");
+        assert!(spdx["packages"][0].get("checksums").is_none());
+    }
+
+    #[test]
+    fn test_new_uuid_format() {
+        let uuid = new_uuid();
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(&uuid[14..15], "4");
+        assert_ne!(uuid, new_uuid());
+    }
+
+    #[test]
+    fn test_sbom_format_parse() {
+        assert_eq!(SbomFormat::parse("CycloneDX"), Some(SbomFormat::CycloneDx));
+        assert_eq!(SbomFormat::parse("spdx"), Some(SbomFormat::Spdx));
+        assert_eq!(SbomFormat::parse("xml"), None);
+    }
+}
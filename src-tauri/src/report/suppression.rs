@@ -0,0 +1,425 @@
+//! 抑制ルール（既知のリスクの受容）
+//! プロジェクト内の .paperstack-ignore.toml とデータベースのルールをスキャン結果に適用する
+//!
+//! ```toml
+//! [[ignore]]
+//! id = "GHSA-xxxx-xxxx-xxxx"
+//! package = "lodash"
+//! path = "services/web"
+//! until = 2026-12-31
+//! reason = "Only used at build time"
+//! ```
+
+use crate::db::models::{Suppression, VulnMatch};
+use crate::db::vuln_queries::normalize_package;
+use serde::Deserialize;
+use std::path::Path;
+
+/// プロジェクトに置く抑制ファイル名
+pub const IGNORE_FILE: &str = ".paperstack-ignore.toml";
+
+#[derive(Debug, Deserialize)]
+struct IgnoreFile {
+    #[serde(default)]
+    ignore: Vec<IgnoreEntry>,
+}
+
+/// ファイル上の 1 件（until は日付リテラル・文字列のどちらでもよい）
+#[derive(Debug, Deserialize)]
+struct IgnoreEntry {
+    id: Option<String>,
+    package: Option<String>,
+    ecosystem: Option<String>,
+    path: Option<String>,
+    until: Option<toml::Value>,
+    reason: Option<String>,
+}
+
+/// ディレクトリの抑制ファイルを読み込む（ファイルがなければ空）
+/// 解析できないファイル・不正なエントリは警告として返す
+pub fn load_file(dir: &Path) -> (Vec<Suppression>, Vec<String>) {
+    let Ok(content) = std::fs::read_to_string(dir.join(IGNORE_FILE)) else {
+        return (Vec::new(), Vec::new());
+    };
+    match parse(&content) {
+        Ok(parsed) => parsed,
+        Err(e) => (Vec::new(), vec![format!("{}: {}", IGNORE_FILE, e)]),
+    }
+}
+
+/// 抑制ファイルの内容を解析（不正なエントリは読み飛ばして警告にする）
+pub fn parse(content: &str) -> Result<(Vec<Suppression>, Vec<String>), toml::de::Error> {
+    // 日付リテラルを toml::Value::Datetime のまま受け取るため、いったんテーブルとして読む
+    let file: IgnoreFile = content.parse::<toml::Table>()?.try_into()?;
+    let mut suppressions = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (index, entry) in file.ignore.into_iter().enumerate() {
+        let expires = entry.until.map(|until| match until {
+            toml::Value::String(s) => s,
+            other => other.to_string(),
+        });
+        let suppression = Suppression {
+            id: None,
+            vuln_id: entry.id,
+            package: entry.package,
+            ecosystem: entry.ecosystem,
+            path: entry.path,
+            directory: None,
+            expires,
+            reason: entry.reason.unwrap_or_default(),
+            source: "file".to_string(),
+        };
+        match validate(&suppression) {
+            Ok(()) => suppressions.push(suppression),
+            Err(e) => warnings.push(format!("{}: ignore[{}] skipped: {}", IGNORE_FILE, index, e)),
+        }
+    }
+    Ok((suppressions, warnings))
+}
+
+/// 抑制ルールを検証（条件が 1 つ以上あり、理由と正しい形式の期限があること）
+pub fn validate(suppression: &Suppression) -> Result<(), String> {
+    if suppression.vuln_id.is_none()
+        && suppression.package.is_none()
+        && suppression.ecosystem.is_none()
+        && suppression.path.is_none()
+    {
+        return Err("at least one of id, package, ecosystem or path is required".to_string());
+    }
+    if suppression.reason.trim().is_empty() {
+        return Err("a reason is required".to_string());
+    }
+    if let Some(ref date) = suppression.expires {
+        if !is_date(date) {
+            return Err(format!(
+                "invalid expiry date: {} (expected YYYY-MM-DD)",
+                date
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// YYYY-MM-DD 形式か
+fn is_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+    let digits = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+    digits(year, 4)
+        && digits(month, 2)
+        && digits(day, 2)
+        && (1..=12).contains(&month.parse::<u32>().unwrap_or(0))
+        && (1..=31).contains(&day.parse::<u32>().unwrap_or(0))
+}
+
+/// 期限切れか（期限の日までは有効。today は ISO 8601 の日時でもよい）
+pub fn is_expired(suppression: &Suppression, today: &str) -> bool {
+    let today = today.get(..10).unwrap_or(today);
+    suppression
+        .expires
+        .as_deref()
+        .is_some_and(|expires| expires < today)
+}
+
+/// スキャン対象ディレクトリに適用されるルールか（データベースのルールは対象を限定できる）
+fn applies_to(suppression: &Suppression, directory: &str) -> bool {
+    match suppression.directory {
+        None => true,
+        Some(ref dir) => same_directory(dir, directory),
+    }
+}
+
+fn same_directory(a: &str, b: &str) -> bool {
+    normalize_path(a) == normalize_path(b)
+}
+
+/// 比較用にパスを正規化（存在しないパスは末尾の区切り文字を除くだけにする）
+fn normalize_path(path: &str) -> String {
+    Path::new(path)
+        .canonicalize()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| path.trim_end_matches(['/', '\\']).to_string())
+}
+
+/// 検出が抑制ルールの条件をすべて満たすか
+pub fn matches(suppression: &Suppression, m: &VulnMatch, directory: &str) -> bool {
+    let vuln = &m.vulnerability;
+    if let Some(ref id) = suppression.vuln_id {
+        let known = std::iter::once(&vuln.id).chain(&vuln.aliases);
+        if !known.into_iter().any(|v| v.eq_ignore_ascii_case(id)) {
+            return false;
+        }
+    }
+    if let Some(ref ecosystem) = suppression.ecosystem {
+        if !ecosystem.eq_ignore_ascii_case(&vuln.affected_ecosystem) {
+            return false;
+        }
+    }
+    if let Some(ref package) = suppression.package {
+        let ecosystem = &vuln.affected_ecosystem;
+        if normalize_package(ecosystem, package) != normalize_package(ecosystem, &m.package_name) {
+            return false;
+        }
+    }
+    if let Some(ref path) = suppression.path {
+        if !path_matches(path, m, directory) {
+            return false;
+        }
+    }
+    true
+}
+
+/// パスが検出のサブプロジェクトかロックファイルに一致するか（"services/web" は "services/web/..." も含む）
+fn path_matches(pattern: &str, m: &VulnMatch, directory: &str) -> bool {
+    let pattern = pattern.replace('\\', "/");
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    if pattern.is_empty() || pattern == "." {
+        return true;
+    }
+    // 相対パスで指定されたスキャン対象でも外せるよう、両方を正規化してから比べる
+    let source_file = normalize_path(&m.source_file);
+    let source = Path::new(&source_file)
+        .strip_prefix(normalize_path(directory))
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| source_file.clone())
+        .replace('\\', "/");
+    let prefix = format!("{}/", pattern);
+    [m.project.as_str(), source.as_str()]
+        .iter()
+        .any(|candidate| *candidate == pattern || candidate.starts_with(&prefix))
+}
+
+/// ルールの条件を表示用に連結
+pub fn describe(suppression: &Suppression) -> String {
+    [
+        &suppression.vuln_id,
+        &suppression.package,
+        &suppression.ecosystem,
+        &suppression.path,
+    ]
+    .into_iter()
+    .flatten()
+    .cloned()
+    .collect::<Vec<_>>()
+    .join(" / ")
+}
+
+/// スキャン対象に適用されるルールを有効なものと期限切れに分け、期限切れの警告を返す
+/// 期限切れのルールは適用しない（検出は通常どおり報告される）
+pub fn split_expired(
+    suppressions: Vec<Suppression>,
+    directory: &str,
+    today: &str,
+) -> (Vec<Suppression>, Vec<String>) {
+    let mut active = Vec::new();
+    let mut warnings = Vec::new();
+    for suppression in suppressions
+        .into_iter()
+        .filter(|s| applies_to(s, directory))
+    {
+        if is_expired(&suppression, today) {
+            warnings.push(format!(
+                "suppression for {} expired on {} ({})",
+                describe(&suppression),
+                suppression.expires.as_deref().unwrap_or_default(),
+                suppression.reason
+            ));
+        } else {
+            active.push(suppression);
+        }
+    }
+    (active, warnings)
+}
+
+/// 有効なルールに該当する検出に印を付ける（結果からは削除しない）
+pub fn mark(matches_found: &mut [VulnMatch], active: &[Suppression], directory: &str) {
+    for m in matches_found.iter_mut() {
+        m.suppression = active.iter().find(|s| matches(s, m, directory)).cloned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ignore_file() {
+        let (suppressions, warnings) = parse(
+            r#"
+            [[ignore]]
+            id = "GHSA-abc"
+            package = "lodash"
+            path = "services/web"
+            until = 2026-12-31
+            reason = "Only used at build time"
+
+            [[ignore]]
+            ecosystem = "PyPI"
+            until = "2027-01-15"
+            reason = "Vendored"
+
+            [[ignore]]
+            id = "GHSA-no-reason"
+
+            [[ignore]]
+            id = "GHSA-bad-date"
+            until = "31/12/2026"
+            reason = "typo"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(suppressions.len(), 2);
+        assert_eq!(suppressions[0].vuln_id.as_deref(), Some("GHSA-abc"));
+        assert_eq!(suppressions[0].expires.as_deref(), Some("2026-12-31"));
+        assert_eq!(suppressions[0].source, "file");
+        assert_eq!(suppressions[1].expires.as_deref(), Some("2027-01-15"));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("reason"));
+        assert!(warnings[1].contains("31/12/2026"));
+
+        assert!(parse("ignore = 3").is_err());
+    }
+
+    #[test]
+    fn test_matching_criteria() {
+        let rule = Suppression {
+            vuln_id: Some("CVE-2024-1".to_string()),
+            package: Some("lodash".to_string()),
+            path: Some("services/web".to_string()),
+            reason: "accepted".to_string(),
+            ..Default::default()
+        };
+        let dir = "/work/app";
+
+        // 別名の CVE でも一致し、サブディレクトリも含む
+        assert!(matches(
+            &rule,
+            &VulnMatch::fixture("GHSA-x", "lodash", "1.0.0")
+                .with_aliases(&["CVE-2024-1"])
+                .with_source("/work/app/services/web/package-lock.json", "services/web"),
+            dir
+        ));
+        assert!(matches(
+            &rule,
+            &VulnMatch::fixture("CVE-2024-1", "lodash", "1.0.0").with_source(
+                "/work/app/services/web/admin/package-lock.json",
+                "services/web/admin"
+            ),
+            dir
+        ));
+        assert!(!matches(
+            &rule,
+            &VulnMatch::fixture("CVE-2024-1", "lodash", "1.0.0").with_source(
+                "/work/app/services/webapp/package-lock.json",
+                "services/webapp"
+            ),
+            dir
+        ));
+        assert!(!matches(
+            &rule,
+            &VulnMatch::fixture("CVE-2024-1", "axios", "1.0.0")
+                .with_source("/work/app/services/web/package-lock.json", "services/web"),
+            dir
+        ));
+        assert!(!matches(
+            &rule,
+            &VulnMatch::fixture("CVE-2024-2", "lodash", "1.0.0")
+                .with_source("/work/app/services/web/package-lock.json", "services/web"),
+            dir
+        ));
+
+        // PyPI のパッケージ名は正規化して比較
+        let rule = Suppression {
+            package: Some("Django_Rest".to_string()),
+            ecosystem: Some("pypi".to_string()),
+            reason: "accepted".to_string(),
+            ..Default::default()
+        };
+        assert!(matches(
+            &rule,
+            &VulnMatch::fixture("PYSEC-1", "django-rest", "1.0.0").with_ecosystem("PyPI"),
+            dir
+        ));
+        assert!(!matches(
+            &rule,
+            &VulnMatch::fixture("PYSEC-1", "django-rest", "1.0.0"),
+            dir
+        ));
+    }
+
+    #[test]
+    fn test_path_matches_relative_scan_directory() {
+        // スキャン対象は相対パス、検出のロックファイルは正規化済みの絶対パス
+        let dir = tempfile::tempdir_in(".").unwrap();
+        let lockfile = dir.path().join("services/web/package-lock.json");
+        std::fs::create_dir_all(lockfile.parent().unwrap()).unwrap();
+        std::fs::write(&lockfile, "{}").unwrap();
+        let source = lockfile.canonicalize().unwrap();
+        let relative = Path::new(".").join(dir.path().file_name().unwrap());
+
+        let rule = Suppression {
+            path: Some("services/web".to_string()),
+            reason: "accepted".to_string(),
+            ..Default::default()
+        };
+        let found = VulnMatch::fixture("GHSA-x", "lodash", "1.0.0")
+            .with_source(source.to_str().unwrap(), ".");
+        assert!(matches(&rule, &found, relative.to_str().unwrap()));
+
+        let rule = Suppression {
+            path: Some("services/api".to_string()),
+            ..rule
+        };
+        assert!(!matches(&rule, &found, relative.to_str().unwrap()));
+    }
+
+    #[test]
+    fn test_apply_marks_and_warns_on_expiry() {
+        let active = Suppression {
+            vuln_id: Some("GHSA-a".to_string()),
+            expires: Some("2026-12-31".to_string()),
+            reason: "accepted".to_string(),
+            ..Default::default()
+        };
+        let expired = Suppression {
+            vuln_id: Some("GHSA-b".to_string()),
+            expires: Some("2024-01-31".to_string()),
+            reason: "old".to_string(),
+            ..Default::default()
+        };
+        let elsewhere = Suppression {
+            vuln_id: Some("GHSA-b".to_string()),
+            directory: Some("/other/project".to_string()),
+            reason: "not this project".to_string(),
+            ..Default::default()
+        };
+        let mut found = vec![
+            VulnMatch::fixture("GHSA-a", "lodash", "1.0.0"),
+            VulnMatch::fixture("GHSA-b", "lodash", "1.0.0"),
+        ];
+
+        let (active_rules, warnings) = split_expired(
+            vec![active.clone(), expired, elsewhere],
+            "/work/app",
+            "2026-12-31T10:00:00Z",
+        );
+        mark(&mut found, &active_rules, "/work/app");
+
+        // 抑制しても結果からは削除しない
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].suppression.as_ref(), Some(&active));
+        assert!(found[1].suppression.is_none());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("GHSA-b expired on 2024-01-31"));
+
+        // 期限の翌日には適用されない
+        let (active_rules, warnings) = split_expired(vec![active], "/work/app", "2027-01-01");
+        mark(&mut found, &active_rules, "/work/app");
+        assert!(found[0].suppression.is_none());
+        assert_eq!(warnings.len(), 1);
+    }
+}
@@ -1,101 +1,210 @@
 //! Cargo (Rust) 依存関係パーサー
 
-use super::{Dependency, ScanDependencies, ScanError};
+use super::{assign_relations, Dependency, ScanDependencies, ScanError};
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-/// Cargo.lock をパース
+/// crates.io のインデックス（git と sparse）
+const CRATES_IO_SOURCES: &[&str] = &[
+    "registry+https://github.com/rust-lang/crates.io-index",
+    "sparse+https://index.crates.io/",
+];
+
+/// Cargo.lock をパース（version 1〜4）
+/// crates.io 以外のソース（git・別のレジストリ）と path 依存は照会せずに警告として報告し、
+/// ワークスペースのメンバー（ソースのないパッケージ）は照会の対象から外してその依存を直接依存とする
 pub fn parse_cargo_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
     let content = fs::read_to_string(path)?;
+    let lock: CargoLock = toml::from_str(&content).map_err(|e| ScanError::Parse(e.to_string()))?;
+    let members = path.parent().map(workspace_members).unwrap_or_default();
+
     let mut dependencies = Vec::new();
+    let mut warnings = Vec::new();
+    let mut direct: Vec<String> = Vec::new();
+    let source_file = path.to_string_lossy().to_string();
 
-    // Cargo.lock is TOML format
-    // We'll parse it manually to avoid adding toml crate dependency
-    // Format:
-    // [[package]]
-    // name = "package-name"
-    // version = "1.0.0"
-    // source = "registry+https://github.com/rust-lang/crates.io-index"
-
-    let mut current_name: Option<String> = None;
-    let mut current_version: Option<String> = None;
-    let mut in_package_section = false;
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-
-        // Start of package section
-        if trimmed == "[[package]]" {
-            // Save previous package if exists
-            if let (Some(name), Some(version)) = (current_name.take(), current_version.take()) {
-                dependencies.push(Dependency {
-                    name,
-                    version,
-                    ecosystem: "crates.io".to_string(),
-                });
+    for package in &lock.package {
+        let Some(ref source) = package.source else {
+            // ソースがなければワークスペースのメンバーか path 依存
+            direct.extend(
+                package
+                    .dependencies
+                    .iter()
+                    .map(|d| dependency_name(d).to_string()),
+            );
+            if !members.contains(&package.name) {
+                warnings.push(format!(
+                    "{}: skipped path dependency {} {}",
+                    source_file, package.name, package.version
+                ));
             }
-            in_package_section = true;
             continue;
-        }
-
-        // Empty line or new section ends current package
-        if trimmed.is_empty() || (trimmed.starts_with('[') && trimmed != "[[package]]") {
-            if let (Some(name), Some(version)) = (current_name.take(), current_version.take()) {
-                dependencies.push(Dependency {
-                    name,
-                    version,
-                    ecosystem: "crates.io".to_string(),
-                });
-            }
-            in_package_section = trimmed == "[[package]]";
+        };
+        if !CRATES_IO_SOURCES.contains(&source.as_str()) {
+            let kind = if source.starts_with("git+") {
+                "git dependency"
+            } else {
+                "dependency from another registry"
+            };
+            warnings.push(format!(
+                "{}: skipped {} {} {} ({})",
+                source_file, kind, package.name, package.version, source
+            ));
             continue;
         }
 
-        if in_package_section {
-            // Parse name = "value"
-            if let Some(name) = parse_toml_string_value(trimmed, "name") {
-                current_name = Some(name);
-            } else if let Some(version) = parse_toml_string_value(trimmed, "version") {
-                current_version = Some(version);
-            }
-        }
-    }
-
-    // Don't forget the last package
-    if let (Some(name), Some(version)) = (current_name, current_version) {
+        let mut parents: Vec<String> = lock
+            .package
+            .iter()
+            .filter(|p| p.dependencies.iter().any(|d| refers_to(d, package)))
+            .map(|p| p.name.clone())
+            .collect();
+        parents.sort();
+        parents.dedup();
         dependencies.push(Dependency {
-            name,
-            version,
+            name: package.name.clone(),
+            version: package.version.clone(),
             ecosystem: "crates.io".to_string(),
+            parents,
+            checksum: package
+                .checksum
+                .clone()
+                .or_else(|| lock.metadata_checksum(package)),
+            ..Default::default()
         });
     }
+    assign_relations(&mut dependencies, &direct);
 
     Ok(ScanDependencies {
         ecosystem: "crates.io".to_string(),
-        source_file: path.to_string_lossy().to_string(),
+        source_file,
         dependencies,
+        warnings,
     })
 }
 
-/// TOML の string 値をパース
-/// 'key = "value"' -> Some("value")
-fn parse_toml_string_value(line: &str, key: &str) -> Option<String> {
-    let pattern = format!("{} = ", key);
-    if line.starts_with(&pattern) {
-        let value_part = &line[pattern.len()..];
-        // Remove quotes
-        let value = value_part
-            .trim()
-            .trim_start_matches('"')
-            .trim_end_matches('"');
-        return Some(value.to_string());
+/// Cargo.toml があるのに Cargo.lock がない（このディレクトリにも親にもない）場合の警告
+/// ロックファイルがなければ解決済みのバージョンが分からないので照会はしない
+pub fn manifest_without_lock(dir: &Path) -> Option<ScanDependencies> {
+    let manifest = dir.join("Cargo.toml");
+    if !manifest.exists() || dir.ancestors().any(|d| d.join("Cargo.lock").exists()) {
+        return None;
+    }
+    let source_file = manifest.to_string_lossy().to_string();
+    Some(ScanDependencies {
+        ecosystem: "crates.io".to_string(),
+        warnings: vec![format!(
+            "{}: no Cargo.lock found; run `cargo generate-lockfile` to scan its dependencies",
+            source_file
+        )],
+        source_file,
+        dependencies: Vec::new(),
+    })
+}
+
+/// Cargo.toml から、ワークスペースのメンバー（ルートのパッケージを含む）のパッケージ名を集める
+/// members は "crates/*" のような末尾のワイルドカードのみ展開する
+fn workspace_members(dir: &Path) -> Vec<String> {
+    let Some(manifest) = read_manifest(&dir.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let mut members: Vec<String> = manifest.package.into_iter().map(|p| p.name).collect();
+    let patterns = manifest.workspace.map(|w| w.members).unwrap_or_default();
+    for pattern in patterns {
+        let dirs: Vec<PathBuf> = match pattern.strip_suffix("/*") {
+            Some(parent) => fs::read_dir(dir.join(parent))
+                .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+                .unwrap_or_default(),
+            None => vec![dir.join(&pattern)],
+        };
+        for member in dirs {
+            if let Some(package) = read_manifest(&member.join("Cargo.toml")).and_then(|m| m.package)
+            {
+                members.push(package.name);
+            }
+        }
+    }
+    members
+}
+
+fn read_manifest(path: &Path) -> Option<CargoManifest> {
+    toml::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// dependencies の要素からパッケージ名を取り出す（"name" / "name version" / "name version (source)"）
+fn dependency_name(entry: &str) -> &str {
+    entry.split(' ').next().unwrap_or(entry)
+}
+
+/// dependencies の要素が package を指しているか（バージョンが書かれていればそれも照合する）
+fn refers_to(entry: &str, package: &LockPackage) -> bool {
+    let mut parts = entry.split(' ');
+    parts.next() == Some(package.name.as_str())
+        && parts
+            .next()
+            .is_none_or(|version| version == package.version)
+}
+
+// --- TOML Types ---
+
+#[derive(Debug, Deserialize)]
+struct CargoLock {
+    #[serde(default)]
+    package: Vec<LockPackage>,
+    /// version 1 はチェックサムを "checksum name version (source)" というキーでここに置く
+    #[serde(default)]
+    metadata: BTreeMap<String, String>,
+}
+
+impl CargoLock {
+    fn metadata_checksum(&self, package: &LockPackage) -> Option<String> {
+        let key = format!(
+            "checksum {} {} ({})",
+            package.name,
+            package.version,
+            package.source.as_deref()?
+        );
+        self.metadata.get(&key).cloned()
     }
-    None
+}
+
+#[derive(Debug, Deserialize)]
+struct LockPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    checksum: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoManifest {
+    #[serde(default)]
+    package: Option<ManifestPackage>,
+    #[serde(default)]
+    workspace: Option<ManifestWorkspace>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestPackage {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scanner::DependencyRelation;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -134,15 +243,150 @@ dependencies = [
     }
 
     #[test]
-    fn test_parse_toml_string_value() {
+    fn test_parse_cargo_lock_graph() {
+        let content = r#"version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "hyper",
+ "serde 1.0.193",
+]
+
+[[package]]
+name = "hyper"
+version = "0.14.27"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = [
+ "h2",
+]
+
+[[package]]
+name = "h2"
+version = "0.3.21"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde"
+version = "1.0.193"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["serde_derive"]
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let result = parse_cargo_lock(file.path()).unwrap();
+        let find = |name: &str| result.dependencies.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(find("hyper").relation, DependencyRelation::Direct);
+        assert_eq!(find("serde").relation, DependencyRelation::Direct);
+        assert_eq!(find("h2").relation, DependencyRelation::Transitive);
+        assert_eq!(find("h2").parents, vec!["hyper"]);
+        assert_eq!(find("hyper").parents, vec!["app"]);
         assert_eq!(
-            parse_toml_string_value(r#"name = "serde""#, "name"),
-            Some("serde".to_string())
+            crate::scanner::dependency_path(&result.dependencies, find("h2")),
+            vec!["hyper@0.14.27", "h2@0.3.21"]
         );
+    }
+
+    #[test]
+    fn test_parse_cargo_lock_sources_and_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("crates/core")).unwrap();
+        fs::write(
+            root.join("crates/core/Cargo.toml"),
+            "[package]\nname = \"acme-core\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("Cargo.lock"),
+            r#"version = 4
+
+[[package]]
+name = "acme-core"
+version = "0.1.0"
+dependencies = [
+ "local-helper",
+ "patched",
+ "regex",
+]
+
+[[package]]
+name = "local-helper"
+version = "0.2.0"
+
+[[package]]
+name = "patched"
+version = "0.3.0"
+source = "git+https://github.com/acme/patched?branch=main#0123abcd"
+
+[[package]]
+name = "regex"
+version = "1.10.2"
+source = "sparse+https://index.crates.io/"
+checksum = "380b951a9c5e80ddfd6136919eef32310721aa4aacd4889a8d39124b026ab343"
+"#,
+        )
+        .unwrap();
+
+        let result = parse_cargo_lock(&root.join("Cargo.lock")).unwrap();
+        assert_eq!(result.dependencies.len(), 1);
+        let regex = &result.dependencies[0];
+        assert_eq!(regex.name, "regex");
+        assert_eq!(regex.relation, DependencyRelation::Direct);
+        assert_eq!(
+            regex.checksum.as_deref(),
+            Some("380b951a9c5e80ddfd6136919eef32310721aa4aacd4889a8d39124b026ab343")
+        );
+        // メンバーは報告せず、path / git の依存関係は警告にする
+        assert_eq!(result.warnings.len(), 2);
+        assert!(result.warnings[0].ends_with("skipped path dependency local-helper 0.2.0"));
+        assert!(result.warnings[1].contains("skipped git dependency patched 0.3.0"));
+
+        // メンバーの Cargo.toml はワークスペースの Cargo.lock があるので警告しない
+        assert!(manifest_without_lock(&root.join("crates/core")).is_none());
+        fs::remove_file(root.join("Cargo.lock")).unwrap();
+        let missing = manifest_without_lock(root).unwrap();
+        assert!(missing.dependencies.is_empty());
+        assert!(missing.warnings[0].contains("no Cargo.lock found"));
+    }
+
+    #[test]
+    fn test_parse_cargo_lock_v1_metadata() {
+        let content = r#"[[package]]
+name = "libc"
+version = "0.2.40"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "rand"
+version = "0.4.2"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = [
+ "libc 0.2.40 (registry+https://github.com/rust-lang/crates.io-index)",
+]
+
+[metadata]
+"checksum libc 0.2.40 (registry+https://github.com/rust-lang/crates.io-index)" = "6fd41f331ac7c5b8ac259b8bf82c75c0fb2e469bbf37d2becbba9a6a2221965b"
+"checksum rand 0.4.2 (registry+https://github.com/rust-lang/crates.io-index)" = "eba5f8cb59cc50ed56be8880a5c7b496bfd9bd26394e176bc67884094145c2c5"
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let result = parse_cargo_lock(file.path()).unwrap();
+        let libc = &result.dependencies[0];
         assert_eq!(
-            parse_toml_string_value(r#"version = "1.0.0""#, "version"),
-            Some("1.0.0".to_string())
+            libc.checksum.as_deref(),
+            Some("6fd41f331ac7c5b8ac259b8bf82c75c0fb2e469bbf37d2becbba9a6a2221965b")
         );
-        assert_eq!(parse_toml_string_value(r#"name = "serde""#, "version"), None);
+        assert_eq!(libc.parents, vec!["rand"]);
+        assert_eq!(libc.relation, DependencyRelation::Transitive);
+        assert_eq!(result.dependencies[1].relation, DependencyRelation::Direct);
     }
 }
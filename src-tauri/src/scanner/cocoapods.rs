@@ -38,6 +38,7 @@ pub fn parse_podfile_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
         ecosystem: "CocoaPods".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
@@ -57,6 +58,7 @@ fn parse_pod_entry(entry: &str) -> Option<Dependency> {
             name: name.to_string(),
             version: version.to_string(),
             ecosystem: "CocoaPods".to_string(),
+            ..Default::default()
         });
     }
 
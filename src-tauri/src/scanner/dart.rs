@@ -1,81 +1,64 @@
 //! Pub (Dart/Flutter) 依存関係パーサー
 
-use super::{Dependency, ScanDependencies, ScanError};
+use super::{yaml, Dependency, DependencyRelation, ScanDependencies, ScanError};
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
 /// pubspec.lock をパース (YAML形式)
+/// dependency が "direct main" / "direct dev" / "direct overridden" のものを直接依存、"transitive" を推移的依存とする
 pub fn parse_pubspec_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
     let content = fs::read_to_string(path)?;
-    let mut dependencies = Vec::new();
+    let lock: PubspecLock = yaml::from_str(&content)?;
 
-    let mut in_packages_section = false;
-    let mut current_package: Option<String> = None;
-    let mut current_version: Option<String> = None;
-
-    for line in content.lines() {
-        // Detect packages section
-        if line == "packages:" {
-            in_packages_section = true;
-            continue;
-        }
-
-        // New top-level section (no indentation)
-        if !line.starts_with(' ') && !line.is_empty() && line != "packages:" {
-            in_packages_section = false;
-            continue;
-        }
-
-        if in_packages_section {
-            // Package name (2 spaces indentation)
-            // "  flutter:"
-            if line.starts_with("  ") && !line.starts_with("    ") {
-                // Save previous package
-                if let (Some(name), Some(version)) = (current_package.take(), current_version.take()) {
-                    dependencies.push(Dependency {
-                        name,
-                        version,
-                        ecosystem: "Pub".to_string(),
-                    });
-                }
-
-                // Parse new package name
-                let trimmed = line.trim();
-                if let Some(name) = trimmed.strip_suffix(':') {
-                    current_package = Some(name.to_string());
-                    current_version = None;
-                }
-            }
-
-            // Version field (4 spaces indentation)
-            // "    version: \"1.0.0\""
-            if line.starts_with("    version:") {
-                let version_part = line.trim().strip_prefix("version:").unwrap_or("").trim();
-                // Remove quotes if present
-                let version = version_part.trim_matches('"').trim_matches('\'');
-                if !version.is_empty() {
-                    current_version = Some(version.to_string());
-                }
-            }
-        }
-    }
-
-    // Don't forget the last package
-    if let (Some(name), Some(version)) = (current_package, current_version) {
-        dependencies.push(Dependency {
-            name,
-            version,
-            ecosystem: "Pub".to_string(),
-        });
-    }
+    let dependencies = lock
+        .packages
+        .into_iter()
+        .filter_map(|(name, package)| {
+            let version = package.version?;
+            let kind = package.dependency.unwrap_or_default();
+            Some(Dependency {
+                name,
+                version,
+                ecosystem: "Pub".to_string(),
+                relation: if kind.starts_with("direct") {
+                    DependencyRelation::Direct
+                } else if kind == "transitive" {
+                    DependencyRelation::Transitive
+                } else {
+                    DependencyRelation::Unknown
+                },
+                dev: kind == "direct dev",
+                ..Default::default()
+            })
+        })
+        .collect();
 
     Ok(ScanDependencies {
         ecosystem: "Pub".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
+// --- YAML Types ---
+
+#[derive(Debug, Deserialize)]
+struct PubspecLock {
+    #[serde(default)]
+    packages: BTreeMap<String, PubspecPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PubspecPackage {
+    #[serde(default)]
+    dependency: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,5 +102,50 @@ sdks:
 
         let args = result.dependencies.iter().find(|d| d.name == "args").unwrap();
         assert_eq!(args.version, "2.3.1");
+        assert_eq!(args.relation, DependencyRelation::Transitive);
+    }
+
+    #[test]
+    fn test_parse_pubspec_lock_dev_and_git() {
+        let content = r#"packages:
+  build_runner:
+    dependency: "direct dev"
+    description:
+      name: build_runner
+      sha256: "abc"
+      url: "https://pub.dev"
+    source: hosted
+    version: "2.4.6"
+  my_fork:
+    dependency: "direct main"
+    description:
+      path: "."
+      ref: main
+      resolved-ref: "0123abcd"
+      url: "https://github.com/acme/my_fork.git"
+    source: git
+    version: "1.2.0"
+  "yaml":
+    dependency: transitive
+    description:
+      name: yaml
+      url: "https://pub.dev"
+    source: hosted
+    version: "3.1.2"
+sdks:
+  dart: ">=3.0.0 <4.0.0"
+"#;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let deps = parse_pubspec_lock(file.path()).unwrap().dependencies;
+        assert_eq!(deps.len(), 3);
+        assert!(deps[0].dev);
+        assert_eq!(deps[0].relation, DependencyRelation::Direct);
+        assert_eq!(deps[1].name, "my_fork");
+        assert!(!deps[1].dev);
+        assert_eq!(deps[2].name, "yaml");
+        assert_eq!(deps[2].version, "3.1.2");
     }
 }
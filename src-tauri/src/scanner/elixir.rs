@@ -33,6 +33,7 @@ pub fn parse_mix_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
         ecosystem: "Hex".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
@@ -79,6 +80,7 @@ fn parse_mix_lock_line(line: &str) -> Option<Dependency> {
             name: package_name.to_string(),
             version: version.to_string(),
             ecosystem: "Hex".to_string(),
+            ..Default::default()
         });
     }
 
@@ -1,11 +1,12 @@
 //! Go modules 依存関係パーサー
 
-use super::{Dependency, ScanDependencies, ScanError};
+use super::{Dependency, DependencyRelation, ScanDependencies, ScanError};
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
-/// go.sum をパース
+/// go.sum をパース（go.mod がない場合に使用）
+/// go.sum は検討されたすべてのモジュールを含むため、実際にビルドに使われないものも含まれる
 pub fn parse_go_sum(path: &Path) -> Result<ScanDependencies, ScanError> {
     let content = fs::read_to_string(path)?;
     let mut dependencies = Vec::new();
@@ -44,6 +45,7 @@ pub fn parse_go_sum(path: &Path) -> Result<ScanDependencies, ScanError> {
                     name: module_path.to_string(),
                     version,
                     ecosystem: "Go".to_string(),
+                    ..Default::default()
                 });
             }
         }
@@ -53,81 +55,253 @@ pub fn parse_go_sum(path: &Path) -> Result<ScanDependencies, ScanError> {
         ecosystem: "Go".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
-/// Go.mod をパース（補助的に使用）
-#[allow(dead_code)]
+/// go.mod をパース
+/// require を依存関係の正とし（// indirect は推移的依存）、replace による置き換えと exclude を反映する。
+/// go / toolchain ディレクティブのバージョンを標準ライブラリ（stdlib）として加え、
+/// 隣に go.sum があれば各モジュールのチェックサムが記録されているかを確かめる
 pub fn parse_go_mod(path: &Path) -> Result<ScanDependencies, ScanError> {
     let content = fs::read_to_string(path)?;
-    let mut dependencies = Vec::new();
-
-    let mut in_require_block = false;
+    let go_mod = read_go_mod(&content);
+    let source_file = path.to_string_lossy().to_string();
+    let go_sum = path
+        .parent()
+        .map(|dir| dir.join("go.sum"))
+        .and_then(|go_sum| fs::read_to_string(go_sum).ok())
+        .map(|content| go_sum_entries(&content));
 
-    for line in content.lines() {
-        let trimmed = line.trim();
+    let mut dependencies = Vec::new();
+    let mut warnings = Vec::new();
 
-        // Skip empty lines and comments
-        if trimmed.is_empty() || trimmed.starts_with("//") {
-            continue;
-        }
+    // ビルドに使うツールチェーン（toolchain が優先、なければ go ディレクティブ）
+    if let Some(version) = go_mod
+        .toolchain
+        .as_deref()
+        .and_then(|t| t.strip_prefix("go"))
+        .or(go_mod.go.as_deref())
+        .and_then(stdlib_version)
+    {
+        dependencies.push(Dependency {
+            name: "stdlib".to_string(),
+            version,
+            ecosystem: "Go".to_string(),
+            relation: DependencyRelation::Direct,
+            ..Default::default()
+        });
+    }
 
-        // Start of require block
-        if trimmed.starts_with("require (") || trimmed == "require (" {
-            in_require_block = true;
+    for require in &go_mod.require {
+        if go_mod
+            .exclude
+            .iter()
+            .any(|(module, version)| *module == require.path && *version == require.version)
+        {
+            warnings.push(format!(
+                "{}: skipped excluded module {} {}",
+                source_file, require.path, require.version
+            ));
             continue;
         }
 
-        // End of require block
-        if trimmed == ")" && in_require_block {
-            in_require_block = false;
-            continue;
-        }
+        // バージョン指定のある replace が、指定のないものより優先される
+        let replace = go_mod
+            .replace
+            .iter()
+            .find(|r| {
+                r.old_path == require.path && r.old_version.as_ref() == Some(&require.version)
+            })
+            .or_else(|| {
+                go_mod
+                    .replace
+                    .iter()
+                    .find(|r| r.old_path == require.path && r.old_version.is_none())
+            });
+        let (name, version) = match replace {
+            Some(replace) => match &replace.new_version {
+                Some(version) => (replace.new_path.clone(), version.clone()),
+                // ローカルのディレクトリへの置き換えは OSV で照合できない
+                None => {
+                    warnings.push(format!(
+                        "{}: skipped {} replaced by local path {}",
+                        source_file, require.path, replace.new_path
+                    ));
+                    continue;
+                }
+            },
+            None => (require.path.clone(), require.version.clone()),
+        };
 
-        // Single-line require
-        if trimmed.starts_with("require ") && !trimmed.contains('(') {
-            let rest = trimmed.strip_prefix("require ").unwrap().trim();
-            if let Some(dep) = parse_go_require_line(rest) {
-                dependencies.push(dep);
+        if let Some(entries) = &go_sum {
+            if !entries.contains(&(name.clone(), version.clone())) {
+                warnings.push(format!(
+                    "{}: {} {} is missing from go.sum; run `go mod tidy`",
+                    source_file, name, version
+                ));
             }
-            continue;
         }
 
-        // Inside require block
-        if in_require_block {
-            if let Some(dep) = parse_go_require_line(trimmed) {
-                dependencies.push(dep);
-            }
-        }
+        dependencies.push(Dependency {
+            name,
+            version: clean_go_version(&version),
+            ecosystem: "Go".to_string(),
+            relation: if require.indirect {
+                DependencyRelation::Transitive
+            } else {
+                DependencyRelation::Direct
+            },
+            ..Default::default()
+        });
     }
 
     Ok(ScanDependencies {
         ecosystem: "Go".to_string(),
-        source_file: path.to_string_lossy().to_string(),
+        source_file,
         dependencies,
+        warnings,
     })
 }
 
-/// require 行をパース
-/// "github.com/pkg/errors v0.9.1" -> Dependency
-/// "github.com/pkg/errors v0.9.1 // indirect" -> Dependency
-fn parse_go_require_line(line: &str) -> Option<Dependency> {
-    // Remove comments
-    let line = line.split("//").next().unwrap_or(line).trim();
+/// go.mod の内容
+#[derive(Debug, Default)]
+struct GoMod {
+    go: Option<String>,
+    toolchain: Option<String>,
+    require: Vec<GoRequire>,
+    replace: Vec<GoReplace>,
+    /// (モジュール, バージョン)
+    exclude: Vec<(String, String)>,
+}
 
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.len() >= 2 {
-        let module_path = parts[0];
-        let version = clean_go_version(parts[1]);
+#[derive(Debug)]
+struct GoRequire {
+    path: String,
+    version: String,
+    indirect: bool,
+}
 
-        return Some(Dependency {
-            name: module_path.to_string(),
-            version,
-            ecosystem: "Go".to_string(),
-        });
+/// "old [version] => new [version]"（new にバージョンがなければローカルのパス）
+#[derive(Debug)]
+struct GoReplace {
+    old_path: String,
+    old_version: Option<String>,
+    new_path: String,
+    new_version: Option<String>,
+}
+
+/// go.mod を読む（"require ( ... )" のようなブロックと 1 行の形式の両方）
+fn read_go_mod(content: &str) -> GoMod {
+    let mut go_mod = GoMod::default();
+    let mut block: Option<String> = None;
+
+    for line in content.lines() {
+        let (code, comment) = match line.find("//") {
+            Some(pos) => (&line[..pos], line[pos + 2..].trim()),
+            None => (line, ""),
+        };
+        let code = code.trim();
+        if code.is_empty() {
+            continue;
+        }
+
+        let (directive, rest) = match &block {
+            Some(_) if code == ")" => {
+                block = None;
+                continue;
+            }
+            Some(directive) => (directive.clone(), code),
+            None => {
+                let (directive, rest) = code.split_once(char::is_whitespace).unwrap_or((code, ""));
+                let rest = rest.trim();
+                if rest == "(" {
+                    block = Some(directive.to_string());
+                    continue;
+                }
+                (directive.to_string(), rest)
+            }
+        };
+
+        let fields: Vec<&str> = rest
+            .split_whitespace()
+            .map(|f| f.trim_matches('"'))
+            .collect();
+        match (directive.as_str(), fields.as_slice()) {
+            ("go", [version]) => go_mod.go = Some(version.to_string()),
+            ("toolchain", [name]) => go_mod.toolchain = Some(name.to_string()),
+            ("require", [path, version]) => go_mod.require.push(GoRequire {
+                path: path.to_string(),
+                version: version.to_string(),
+                indirect: comment.split(';').any(|c| c.trim() == "indirect"),
+            }),
+            ("exclude", [path, version]) => {
+                go_mod.exclude.push((path.to_string(), version.to_string()));
+            }
+            ("replace", _) => {
+                let Some(arrow) = fields.iter().position(|f| *f == "=>") else {
+                    continue;
+                };
+                let (old, new) = (&fields[..arrow], &fields[arrow + 1..]);
+                let (Some(old_path), Some(new_path)) = (old.first(), new.first()) else {
+                    continue;
+                };
+                go_mod.replace.push(GoReplace {
+                    old_path: old_path.to_string(),
+                    old_version: old.get(1).map(|v| v.to_string()),
+                    new_path: new_path.to_string(),
+                    new_version: new.get(1).map(|v| v.to_string()),
+                });
+            }
+            _ => {}
+        }
     }
+    go_mod
+}
+
+/// go.sum に記録された (モジュール, バージョン)
+fn go_sum_entries(content: &str) -> HashSet<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let module = parts.next()?;
+            let version = parts.next()?;
+            let version = version.strip_suffix("/go.mod").unwrap_or(version);
+            Some((module.to_string(), version.to_string()))
+        })
+        .collect()
+}
 
-    None
+/// go / toolchain のバージョンを OSV の stdlib のバージョン表記にする
+/// "1.22.3" -> "1.22.3"、"1.21" -> "1.21.0"、"1.21rc2" -> "1.21.0-rc.2"
+fn stdlib_version(version: &str) -> Option<String> {
+    let version = version.trim();
+    let split = version
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(version.len());
+    let (release, pre) = version.split_at(split);
+    let mut parts: Vec<&str> = release.split('.').collect();
+    if parts
+        .iter()
+        .any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    let release = parts.join(".");
+    if pre.is_empty() {
+        return Some(release);
+    }
+    let number_at = pre.find(|c: char| c.is_ascii_digit()).unwrap_or(pre.len());
+    let (kind, number) = pre.split_at(number_at);
+    if number.is_empty() {
+        Some(format!("{}-{}", release, kind))
+    } else {
+        Some(format!("{}-{}.{}", release, kind, number))
+    }
 }
 
 /// Go バージョン文字列をクリーンアップ
@@ -166,6 +340,84 @@ golang.org/x/sys v0.0.0-20210615035016-665e8c7367d1 h1:SrN+KX8Art/Sf4HNj6Zcz06G7
         assert_eq!(result.dependencies[0].version, "v0.9.1");
     }
 
+    #[test]
+    fn test_parse_go_mod() {
+        let dir = tempfile::tempdir().unwrap();
+        let go_mod = dir.path().join("go.mod");
+        fs::write(
+            &go_mod,
+            r#"module example.com/app
+
+go 1.21
+
+toolchain go1.22.3
+
+require github.com/pkg/errors v0.9.1
+
+require (
+	golang.org/x/net v0.17.0
+	golang.org/x/text v0.13.0 // indirect
+	github.com/old/lib v1.0.0
+	example.com/local v0.1.0
+	github.com/bad/mod v1.2.0
+)
+
+replace (
+	github.com/old/lib => github.com/new/lib v1.1.0
+	example.com/local => ../local
+)
+
+exclude github.com/bad/mod v1.2.0
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("go.sum"),
+            r#"github.com/pkg/errors v0.9.1 h1:FEBLx1zS214owpjy7qsBeixbURkuhQAwrK5UwLGTwt4=
+golang.org/x/net v0.17.0/go.mod h1:NxSsAGuq816PNPmqtQdLE42eU2Fs7NoRIZrHJAlaCOE=
+golang.org/x/text v0.13.0 h1:ablQoSUd0tRdKxZewP80B+BaqeKJuVhuRxj/dkrun3k=
+"#,
+        )
+        .unwrap();
+
+        let result = parse_go_mod(&go_mod).unwrap();
+        let deps: Vec<(&str, &str, DependencyRelation)> = result
+            .dependencies
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str(), d.relation))
+            .collect();
+        assert_eq!(
+            deps,
+            vec![
+                ("stdlib", "1.22.3", DependencyRelation::Direct),
+                (
+                    "github.com/pkg/errors",
+                    "v0.9.1",
+                    DependencyRelation::Direct
+                ),
+                ("golang.org/x/net", "v0.17.0", DependencyRelation::Direct),
+                (
+                    "golang.org/x/text",
+                    "v0.13.0",
+                    DependencyRelation::Transitive
+                ),
+                ("github.com/new/lib", "v1.1.0", DependencyRelation::Direct),
+            ]
+        );
+        assert_eq!(result.warnings.len(), 3);
+        assert!(result.warnings[0].contains("github.com/new/lib v1.1.0 is missing from go.sum"));
+        assert!(result.warnings[1].contains("example.com/local replaced by local path ../local"));
+        assert!(result.warnings[2].contains("excluded module github.com/bad/mod v1.2.0"));
+    }
+
+    #[test]
+    fn test_stdlib_version() {
+        assert_eq!(stdlib_version("1.22.3").as_deref(), Some("1.22.3"));
+        assert_eq!(stdlib_version("1.21").as_deref(), Some("1.21.0"));
+        assert_eq!(stdlib_version("1.21rc2").as_deref(), Some("1.21.0-rc.2"));
+        assert_eq!(stdlib_version("default"), None);
+    }
+
     #[test]
     fn test_clean_go_version() {
         assert_eq!(clean_go_version("v1.2.3"), "v1.2.3");
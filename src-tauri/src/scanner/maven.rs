@@ -1,8 +1,18 @@
 //! Maven / Gradle 依存関係パーサー
 
-use super::{Dependency, ScanDependencies, ScanError};
+use super::{Dependency, DependencyRelation, ScanDependencies, ScanError};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// 親 POM / BOM をたどる深さの上限
+const MAX_PARENT_DEPTH: usize = 16;
+/// リポジトリ内で読む POM の上限
+const MAX_REACTOR_POMS: usize = 1000;
+/// プロパティの入れ子の上限
+const MAX_INTERPOLATION_DEPTH: usize = 32;
 
 /// gradle.lockfile をパース
 pub fn parse_gradle_lockfile(path: &Path) -> Result<ScanDependencies, ScanError> {
@@ -38,80 +48,484 @@ pub fn parse_gradle_lockfile(path: &Path) -> Result<ScanDependencies, ScanError>
         ecosystem: "Maven".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
-/// pom.xml から依存関係を抽出（簡易パーサー）
+/// pom.xml から依存関係を抽出
+/// リポジトリ内の親 POM をたどって properties / dependencies / dependencyManagement を継承し、
+/// `${...}` を補間してバージョンを決める。バージョンのない依存は dependencyManagement
+/// （リポジトリ内の BOM の import を含む）から補う
 pub fn parse_pom_xml(path: &Path) -> Result<ScanDependencies, ScanError> {
+    let source_file = path.to_string_lossy().to_string();
+    let reactor = reactor_poms(path);
+    let mut warnings = Vec::new();
+    let chain = load_chain(path, &reactor, &source_file, &mut warnings)?;
+    let properties = effective_properties(&chain);
+    let managed = managed_versions(
+        &chain,
+        &properties,
+        &reactor,
+        0,
+        &source_file,
+        &mut warnings,
+    );
+
+    let mut dependencies: Vec<Dependency> = Vec::new();
+    for dep in chain.iter().flat_map(|(_, pom)| &pom.dependencies) {
+        let (Some(group_id), Some(artifact_id)) = (
+            interpolate(&dep.group_id, &properties),
+            interpolate(&dep.artifact_id, &properties),
+        ) else {
+            continue;
+        };
+        let name = format!("{}:{}", group_id, artifact_id);
+        // 子の宣言が親の宣言より優先される
+        if dependencies.iter().any(|d| d.name == name) {
+            continue;
+        }
+        let managed = managed.iter().find(|m| m.name == name);
+        let version = match &dep.version {
+            Some(version) => interpolate(version, &properties),
+            None => managed.and_then(|m| m.version.clone()),
+        };
+        let Some(version) = version else {
+            warnings.push(format!(
+                "{}: skipped {}: version could not be resolved",
+                source_file, name
+            ));
+            continue;
+        };
+        let scope = dep
+            .scope
+            .as_deref()
+            .or_else(|| managed.and_then(|m| m.scope.as_deref()));
+        dependencies.push(Dependency {
+            name,
+            version,
+            ecosystem: "Maven".to_string(),
+            relation: DependencyRelation::Direct,
+            dev: scope == Some("test"),
+            optional: dep.optional,
+            ..Default::default()
+        });
+    }
+
+    Ok(ScanDependencies {
+        ecosystem: "Maven".to_string(),
+        source_file,
+        dependencies,
+        warnings,
+    })
+}
+
+/// gradle/libs.versions.toml（バージョンカタログ）をパース
+/// [libraries] のうちバージョンが決まるもの（直接指定、version.ref、strictly / require / prefer）を対象とする
+pub fn parse_version_catalog(path: &Path) -> Result<ScanDependencies, ScanError> {
     let content = fs::read_to_string(path)?;
+    let catalog: toml::Table =
+        toml::from_str(&content).map_err(|e| ScanError::Parse(e.to_string()))?;
+    let source_file = path.to_string_lossy().to_string();
+    let versions = catalog.get("versions").and_then(|v| v.as_table());
+
     let mut dependencies = Vec::new();
+    let mut warnings = Vec::new();
+    let libraries = catalog.get("libraries").and_then(|l| l.as_table());
+    for (alias, library) in libraries.into_iter().flatten() {
+        // "group:name:version" の短縮形
+        if let Some(notation) = library.as_str() {
+            if let Some(dep) = parse_maven_coordinates(notation) {
+                dependencies.push(Dependency {
+                    relation: DependencyRelation::Direct,
+                    ..dep
+                });
+            }
+            continue;
+        }
+        let module = match (
+            library.get("module").and_then(|m| m.as_str()),
+            library.get("group").and_then(|g| g.as_str()),
+            library.get("name").and_then(|n| n.as_str()),
+        ) {
+            (Some(module), _, _) => module.to_string(),
+            (None, Some(group), Some(name)) => format!("{}:{}", group, name),
+            _ => continue,
+        };
+        let version = match library.get("version") {
+            Some(toml::Value::Table(version)) => {
+                match version.get("ref").and_then(|r| r.as_str()) {
+                    Some(reference) => versions
+                        .and_then(|v| v.get(reference))
+                        .and_then(catalog_version),
+                    None => catalog_version(&toml::Value::Table(version.clone())),
+                }
+            }
+            Some(version) => catalog_version(version),
+            None => None,
+        };
+        match version {
+            Some(version) => dependencies.push(Dependency {
+                name: module,
+                version,
+                ecosystem: "Maven".to_string(),
+                relation: DependencyRelation::Direct,
+                ..Default::default()
+            }),
+            // プラットフォーム（BOM）からバージョンを得るライブラリ
+            None => warnings.push(format!(
+                "{}: skipped {} ({}): no version in the catalog",
+                source_file, alias, module
+            )),
+        }
+    }
+
+    Ok(ScanDependencies {
+        ecosystem: "Maven".to_string(),
+        source_file,
+        dependencies,
+        warnings,
+    })
+}
 
-    // Simple regex-free XML parsing for dependencies
-    // Looking for <dependency>...</dependency> blocks
-    let mut in_dependency = false;
-    let mut current_group_id = String::new();
-    let mut current_artifact_id = String::new();
-    let mut current_version = String::new();
-    let mut in_dependencies_section = false;
+/// カタログのバージョン指定（"1.0" または { strictly / require / prefer = "1.0" }）
+fn catalog_version(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(version) => Some(version.clone()),
+        toml::Value::Table(rich) => ["strictly", "require", "prefer"]
+            .iter()
+            .find_map(|key| rich.get(*key).and_then(|v| v.as_str()))
+            .map(str::to_string),
+        _ => None,
+    }
+}
 
-    for line in content.lines() {
-        let trimmed = line.trim();
+/// pom.xml の内容（継承・補間の前）
+#[derive(Debug, Default)]
+struct Pom {
+    group_id: Option<String>,
+    artifact_id: Option<String>,
+    version: Option<String>,
+    parent: Option<PomParent>,
+    properties: HashMap<String, String>,
+    dependencies: Vec<PomDependency>,
+    /// dependencyManagement に書かれた依存
+    managed: Vec<PomDependency>,
+    modules: Vec<String>,
+}
 
-        // Track if we're in a <dependencies> section
-        if trimmed.contains("<dependencies>") || trimmed.contains("<dependencies ") {
-            in_dependencies_section = true;
-        }
-        if trimmed.contains("</dependencies>") {
-            in_dependencies_section = false;
+impl Pom {
+    /// "groupId:artifactId"（groupId は親から継承することがある）
+    fn coordinates(&self) -> Option<String> {
+        let group_id = self
+            .group_id
+            .as_ref()
+            .or(self.parent.as_ref().map(|p| &p.group_id))?;
+        Some(format!("{}:{}", group_id, self.artifact_id.as_ref()?))
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct PomParent {
+    group_id: String,
+    artifact_id: String,
+    version: String,
+    /// 空の <relativePath/> はリポジトリ内を探さない
+    relative_path: Option<String>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct PomDependency {
+    group_id: String,
+    artifact_id: String,
+    version: Option<String>,
+    scope: Option<String>,
+    r#type: Option<String>,
+    optional: bool,
+}
+
+/// dependencyManagement から解決したバージョン
+#[derive(Debug)]
+struct ManagedVersion {
+    name: String,
+    version: Option<String>,
+    scope: Option<String>,
+}
+
+/// pom.xml を読む（必要な要素だけをパスで取り出す）
+fn read_pom(path: &Path) -> Result<Pom, ScanError> {
+    let content = fs::read_to_string(path)?;
+    let mut reader = Reader::from_str(&content);
+    reader.trim_text(true);
+
+    let mut pom = Pom::default();
+    let mut elements: Vec<String> = Vec::new();
+    let mut parent = PomParent::default();
+    let mut dependency = PomDependency::default();
+    loop {
+        match reader
+            .read_event()
+            .map_err(|e| ScanError::Parse(e.to_string()))?
+        {
+            Event::Start(e) => {
+                elements.push(String::from_utf8_lossy(e.local_name().as_ref()).into_owned());
+            }
+            Event::Empty(e)
+                if elements == ["project", "parent"]
+                    && e.local_name().as_ref() == b"relativePath" =>
+            {
+                parent.relative_path = Some(String::new());
+            }
+            Event::End(_) => {
+                let path: Vec<&str> = elements.iter().map(String::as_str).collect();
+                match path.as_slice() {
+                    ["project", "parent"] => pom.parent = Some(std::mem::take(&mut parent)),
+                    ["project", "dependencies", "dependency"] => {
+                        pom.dependencies.push(std::mem::take(&mut dependency));
+                    }
+                    ["project", "dependencyManagement", "dependencies", "dependency"] => {
+                        pom.managed.push(std::mem::take(&mut dependency));
+                    }
+                    _ => {}
+                }
+                elements.pop();
+            }
+            Event::Text(e) => {
+                let text = e
+                    .unescape()
+                    .map_err(|e| ScanError::Parse(e.to_string()))?
+                    .trim()
+                    .to_string();
+                let path: Vec<&str> = elements.iter().map(String::as_str).collect();
+                match path.as_slice() {
+                    ["project", "groupId"] => pom.group_id = Some(text),
+                    ["project", "artifactId"] => pom.artifact_id = Some(text),
+                    ["project", "version"] => pom.version = Some(text),
+                    ["project", "parent", "groupId"] => parent.group_id = text,
+                    ["project", "parent", "artifactId"] => parent.artifact_id = text,
+                    ["project", "parent", "version"] => parent.version = text,
+                    ["project", "parent", "relativePath"] => parent.relative_path = Some(text),
+                    ["project", "properties", name] => {
+                        pom.properties.insert(name.to_string(), text);
+                    }
+                    ["project", "modules", "module"] => pom.modules.push(text),
+                    ["project", "dependencies", "dependency", field]
+                    | ["project", "dependencyManagement", "dependencies", "dependency", field] => {
+                        match *field {
+                            "groupId" => dependency.group_id = text,
+                            "artifactId" => dependency.artifact_id = text,
+                            "version" => dependency.version = Some(text),
+                            "scope" => dependency.scope = Some(text),
+                            "type" => dependency.r#type = Some(text),
+                            "optional" => dependency.optional = text == "true",
+                            _ => {}
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
         }
+    }
+    Ok(pom)
+}
+
+/// 同じリポジトリ（リアクター）の POM を座標ごとに集める
+/// pom.xml のある最も上の親ディレクトリから <modules> をたどる
+fn reactor_poms(path: &Path) -> HashMap<String, PathBuf> {
+    let mut poms = HashMap::new();
+    let Some(root) = path.parent().and_then(|dir| {
+        dir.ancestors()
+            .take_while(|d| d.join("pom.xml").exists())
+            .last()
+    }) else {
+        return poms;
+    };
 
-        // Only parse dependencies within <dependencies> section
-        if !in_dependencies_section {
+    let mut queue = vec![root.join("pom.xml")];
+    let mut visited: Vec<PathBuf> = Vec::new();
+    while let Some(pom_path) = queue.pop() {
+        if visited.contains(&pom_path) || visited.len() >= MAX_REACTOR_POMS {
+            continue;
+        }
+        visited.push(pom_path.clone());
+        let Ok(pom) = read_pom(&pom_path) else {
             continue;
+        };
+        let dir = pom_path.parent().unwrap_or(Path::new("."));
+        for module in &pom.modules {
+            let module_path = dir.join(module);
+            queue.push(if module_path.extension().is_some_and(|e| e == "xml") {
+                module_path
+            } else {
+                module_path.join("pom.xml")
+            });
         }
+        if let Some(coordinates) = pom.coordinates() {
+            poms.entry(coordinates).or_insert(pom_path);
+        }
+    }
+    poms
+}
 
-        if trimmed.contains("<dependency>") || trimmed.contains("<dependency ") {
-            in_dependency = true;
-            current_group_id.clear();
-            current_artifact_id.clear();
-            current_version.clear();
+/// POM とその親をたどる（子が先頭）。リポジトリにない親は警告して打ち切る
+fn load_chain(
+    path: &Path,
+    reactor: &HashMap<String, PathBuf>,
+    source_file: &str,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<(PathBuf, Pom)>, ScanError> {
+    let mut chain = vec![(path.to_path_buf(), read_pom(path)?)];
+    while chain.len() < MAX_PARENT_DEPTH {
+        let (pom_path, pom) = chain.last().expect("chain is never empty");
+        let Some(parent) = pom.parent.clone() else {
+            break;
+        };
+        let key = format!("{}:{}", parent.group_id, parent.artifact_id);
+        let dir = pom_path.parent().unwrap_or(Path::new("."));
+        let relative = match parent.relative_path.as_deref() {
+            Some("") => None,
+            Some(relative) => Some(dir.join(relative)),
+            None => Some(dir.join("../pom.xml")),
         }
+        .map(|p| if p.is_dir() { p.join("pom.xml") } else { p })
+        .filter(|p| p.exists());
 
-        if in_dependency {
-            if let Some(value) = extract_xml_value(trimmed, "groupId") {
-                current_group_id = value;
+        let mut found = None;
+        for candidate in relative.into_iter().chain(reactor.get(&key).cloned()) {
+            let candidate_pom = read_pom(&candidate)?;
+            if candidate_pom.coordinates().as_deref() == Some(key.as_str()) {
+                found = Some((candidate, candidate_pom));
+                break;
             }
-            if let Some(value) = extract_xml_value(trimmed, "artifactId") {
-                current_artifact_id = value;
+        }
+        match found {
+            Some(parent_pom) => chain.push(parent_pom),
+            None => {
+                warnings.push(format!(
+                    "{}: parent POM {}:{} is not in the repository; inherited versions may be missing",
+                    source_file, key, parent.version
+                ));
+                break;
             }
-            if let Some(value) = extract_xml_value(trimmed, "version") {
-                // Skip property references like ${project.version}
-                if !value.starts_with('$') {
-                    current_version = value;
-                }
+        }
+    }
+    Ok(chain)
+}
+
+/// 親から順に properties を重ね、project.* の組み込みプロパティを加える
+fn effective_properties(chain: &[(PathBuf, Pom)]) -> HashMap<String, String> {
+    let mut properties = HashMap::new();
+    for (_, pom) in chain.iter().rev() {
+        properties.extend(pom.properties.clone());
+    }
+
+    let (_, pom) = &chain[0];
+    let parent = pom.parent.as_ref();
+    let builtins = [
+        (
+            "project.groupId",
+            pom.group_id.clone().or(parent.map(|p| p.group_id.clone())),
+        ),
+        ("project.artifactId", pom.artifact_id.clone()),
+        (
+            "project.version",
+            pom.version.clone().or(parent.map(|p| p.version.clone())),
+        ),
+        ("project.parent.groupId", parent.map(|p| p.group_id.clone())),
+        ("project.parent.version", parent.map(|p| p.version.clone())),
+    ];
+    for (key, value) in builtins {
+        if let Some(value) = value {
+            // 古い書き方の pom.* / ${version} も同じ値
+            properties.insert(key.replacen("project.", "pom.", 1), value.clone());
+            if key == "project.version" {
+                properties.insert("version".to_string(), value.clone());
             }
+            properties.insert(key.to_string(), value);
         }
+    }
+    properties
+}
 
-        if trimmed.contains("</dependency>") {
-            in_dependency = false;
-            if !current_group_id.is_empty()
-                && !current_artifact_id.is_empty()
-                && !current_version.is_empty()
-            {
-                dependencies.push(Dependency {
-                    name: format!("{}:{}", current_group_id, current_artifact_id),
-                    version: current_version.clone(),
-                    ecosystem: "Maven".to_string(),
-                });
+/// dependencyManagement のバージョンを解決する（子の宣言が優先）
+/// scope が import の pom はリポジトリ内にあれば、その dependencyManagement を取り込む
+fn managed_versions(
+    chain: &[(PathBuf, Pom)],
+    properties: &HashMap<String, String>,
+    reactor: &HashMap<String, PathBuf>,
+    depth: usize,
+    source_file: &str,
+    warnings: &mut Vec<String>,
+) -> Vec<ManagedVersion> {
+    let mut managed: Vec<ManagedVersion> = Vec::new();
+    for dep in chain.iter().flat_map(|(_, pom)| &pom.managed) {
+        let (Some(group_id), Some(artifact_id)) = (
+            interpolate(&dep.group_id, properties),
+            interpolate(&dep.artifact_id, properties),
+        ) else {
+            continue;
+        };
+        let name = format!("{}:{}", group_id, artifact_id);
+        let version = dep
+            .version
+            .as_ref()
+            .and_then(|v| interpolate(v, properties));
+
+        if dep.scope.as_deref() == Some("import") && dep.r#type.as_deref() == Some("pom") {
+            let bom = reactor.get(&name).filter(|_| depth < MAX_PARENT_DEPTH);
+            let Some(bom_path) = bom else {
+                warnings.push(format!(
+                    "{}: BOM {}:{} is not in the repository; versions it manages were not resolved",
+                    source_file,
+                    name,
+                    version.as_deref().unwrap_or("?")
+                ));
+                continue;
+            };
+            let Ok(bom_chain) = load_chain(bom_path, reactor, source_file, warnings) else {
+                continue;
+            };
+            let bom_properties = effective_properties(&bom_chain);
+            for imported in managed_versions(
+                &bom_chain,
+                &bom_properties,
+                reactor,
+                depth + 1,
+                source_file,
+                warnings,
+            ) {
+                if !managed.iter().any(|m| m.name == imported.name) {
+                    managed.push(imported);
+                }
             }
+            continue;
+        }
+
+        if !managed.iter().any(|m| m.name == name) {
+            managed.push(ManagedVersion {
+                name,
+                version,
+                scope: dep.scope.clone(),
+            });
         }
     }
+    managed
+}
 
-    Ok(ScanDependencies {
-        ecosystem: "Maven".to_string(),
-        source_file: path.to_string_lossy().to_string(),
-        dependencies,
-    })
+/// `${name}` をプロパティで置き換える（解決できない参照があれば None）
+fn interpolate(value: &str, properties: &HashMap<String, String>) -> Option<String> {
+    let mut value = value.trim().to_string();
+    // プロパティが別のプロパティを参照する場合に備えて繰り返す
+    for _ in 0..MAX_INTERPOLATION_DEPTH {
+        let Some(start) = value.find("${") else {
+            return (!value.is_empty()).then_some(value);
+        };
+        let end = start + value[start..].find('}')?;
+        let replacement = properties.get(&value[start + 2..end])?;
+        value.replace_range(start..=end, replacement);
+    }
+    None
 }
 
 /// Maven座標 (group:artifact:version) をパース
@@ -126,27 +540,12 @@ fn parse_maven_coordinates(coords: &str) -> Option<Dependency> {
             name: format!("{}:{}", group_id, artifact_id),
             version: version.to_string(),
             ecosystem: "Maven".to_string(),
+            ..Default::default()
         });
     }
     None
 }
 
-/// XML タグから値を抽出
-fn extract_xml_value(line: &str, tag: &str) -> Option<String> {
-    let open_tag = format!("<{}>", tag);
-    let close_tag = format!("</{}>", tag);
-
-    if let Some(start) = line.find(&open_tag) {
-        if let Some(end) = line.find(&close_tag) {
-            let value_start = start + open_tag.len();
-            if value_start < end {
-                return Some(line[value_start..end].trim().to_string());
-            }
-        }
-    }
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,4 +577,224 @@ org.slf4j:slf4j-api:1.7.36=compileClasspath,runtimeClasspath
         assert_eq!(dep.name, "com.example:mylib");
         assert_eq!(dep.version, "1.0.0");
     }
+
+    #[test]
+    fn test_parse_pom_inheritance_and_bom() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("bom")).unwrap();
+        fs::create_dir_all(root.join("service")).unwrap();
+        fs::write(
+            root.join("pom.xml"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <parent>
+    <groupId>org.springframework.boot</groupId>
+    <artifactId>spring-boot-starter-parent</artifactId>
+    <version>3.2.0</version>
+    <relativePath/>
+  </parent>
+  <groupId>com.acme</groupId>
+  <artifactId>acme-parent</artifactId>
+  <version>1.4.0</version>
+  <packaging>pom</packaging>
+  <modules>
+    <module>bom</module>
+    <module>service</module>
+  </modules>
+  <properties>
+    <jackson.version>2.15.2</jackson.version>
+    <guava.version>32.1.2-jre</guava.version>
+  </properties>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>com.google.guava</groupId>
+        <artifactId>guava</artifactId>
+        <version>${guava.version}</version>
+      </dependency>
+      <dependency>
+        <groupId>com.acme</groupId>
+        <artifactId>acme-bom</artifactId>
+        <version>${project.version}</version>
+        <type>pom</type>
+        <scope>import</scope>
+      </dependency>
+      <dependency>
+        <groupId>org.springframework.cloud</groupId>
+        <artifactId>spring-cloud-dependencies</artifactId>
+        <version>2023.0.0</version>
+        <type>pom</type>
+        <scope>import</scope>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+  <dependencies>
+    <dependency>
+      <groupId>org.slf4j</groupId>
+      <artifactId>slf4j-api</artifactId>
+      <version>2.0.9</version>
+    </dependency>
+  </dependencies>
+</project>
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("bom/pom.xml"),
+            r#"<project>
+  <groupId>com.acme</groupId>
+  <artifactId>acme-bom</artifactId>
+  <version>1.4.0</version>
+  <packaging>pom</packaging>
+  <properties>
+    <netty.version>4.1.100.Final</netty.version>
+  </properties>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>io.netty</groupId>
+        <artifactId>netty-codec-http</artifactId>
+        <version>${netty.version}</version>
+      </dependency>
+      <dependency>
+        <groupId>junit</groupId>
+        <artifactId>junit</artifactId>
+        <version>4.13.2</version>
+        <scope>test</scope>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+</project>
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("service/pom.xml"),
+            r#"<project>
+  <parent>
+    <groupId>com.acme</groupId>
+    <artifactId>acme-parent</artifactId>
+    <version>1.4.0</version>
+  </parent>
+  <artifactId>service</artifactId>
+  <properties>
+    <jackson.version>2.16.0</jackson.version>
+  </properties>
+  <dependencies>
+    <dependency>
+      <groupId>com.fasterxml.jackson.core</groupId>
+      <artifactId>jackson-databind</artifactId>
+      <version>${jackson.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>com.google.guava</groupId>
+      <artifactId>guava</artifactId>
+    </dependency>
+    <dependency>
+      <groupId>io.netty</groupId>
+      <artifactId>netty-codec-http</artifactId>
+      <optional>true</optional>
+    </dependency>
+    <dependency>
+      <groupId>junit</groupId>
+      <artifactId>junit</artifactId>
+    </dependency>
+    <dependency>
+      <groupId>${project.groupId}</groupId>
+      <artifactId>acme-common</artifactId>
+      <version>${project.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>org.springframework.cloud</groupId>
+      <artifactId>spring-cloud-starter-gateway</artifactId>
+    </dependency>
+  </dependencies>
+</project>
+"#,
+        )
+        .unwrap();
+
+        let result = parse_pom_xml(&root.join("service/pom.xml")).unwrap();
+        let find = |name: &str| result.dependencies.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(result.dependencies.len(), 6);
+        // 子のプロパティが親より優先される
+        assert_eq!(
+            find("com.fasterxml.jackson.core:jackson-databind").version,
+            "2.16.0"
+        );
+        // dependencyManagement と BOM からバージョンを補う
+        assert_eq!(find("com.google.guava:guava").version, "32.1.2-jre");
+        assert_eq!(find("io.netty:netty-codec-http").version, "4.1.100.Final");
+        assert!(find("io.netty:netty-codec-http").optional);
+        assert!(find("junit:junit").dev);
+        assert_eq!(find("com.acme:acme-common").version, "1.4.0");
+        // 親の dependencies も継承する
+        assert_eq!(find("org.slf4j:slf4j-api").version, "2.0.9");
+        assert!(result
+            .dependencies
+            .iter()
+            .all(|d| d.relation == DependencyRelation::Direct));
+
+        assert_eq!(result.warnings.len(), 3);
+        assert!(result.warnings[0].contains(
+            "parent POM org.springframework.boot:spring-boot-starter-parent:3.2.0 is not in the repository"
+        ));
+        assert!(result.warnings[1]
+            .contains("BOM org.springframework.cloud:spring-cloud-dependencies:2023.0.0"));
+        assert!(result.warnings[2].ends_with(
+            "skipped org.springframework.cloud:spring-cloud-starter-gateway: version could not be resolved"
+        ));
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let properties: HashMap<String, String> =
+            [("base", "1.2"), ("full", "${base}.3"), ("self", "${self}")]
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+        assert_eq!(
+            interpolate("${full}-jre", &properties).as_deref(),
+            Some("1.2.3-jre")
+        );
+        assert_eq!(interpolate("${missing}", &properties), None);
+        assert_eq!(interpolate("${self}", &properties), None);
+    }
+
+    #[test]
+    fn test_parse_version_catalog() {
+        let content = r#"[versions]
+guava = "32.1.2-jre"
+jackson = { strictly = "2.15.2" }
+
+[libraries]
+guava = { module = "com.google.guava:guava", version.ref = "guava" }
+jackson-databind = { group = "com.fasterxml.jackson.core", name = "jackson-databind", version.ref = "jackson" }
+commons-lang3 = "org.apache.commons:commons-lang3:3.13.0"
+okhttp = { module = "com.squareup.okhttp3:okhttp", version = { require = "4.12.0" } }
+spring-core = { module = "org.springframework:spring-core" }
+
+[plugins]
+kotlin = { id = "org.jetbrains.kotlin.jvm", version = "1.9.20" }
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let result = parse_version_catalog(file.path()).unwrap();
+        let find = |name: &str| result.dependencies.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(result.dependencies.len(), 4);
+        assert_eq!(find("com.google.guava:guava").version, "32.1.2-jre");
+        assert_eq!(
+            find("com.fasterxml.jackson.core:jackson-databind").version,
+            "2.15.2"
+        );
+        assert_eq!(find("org.apache.commons:commons-lang3").version, "3.13.0");
+        assert_eq!(find("com.squareup.okhttp3:okhttp").version, "4.12.0");
+        assert_eq!(result.warnings.len(), 1);
+        assert!(
+            result.warnings[0].contains("skipped spring-core (org.springframework:spring-core)")
+        );
+    }
 }
@@ -11,26 +11,215 @@ pub mod npm;
 pub mod nuget;
 pub mod php;
 pub mod pip;
+pub mod purl;
 pub mod ruby;
+pub mod sbom;
 pub mod swift;
+mod yaml;
 
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 /// 検出した依存関係
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Dependency {
     pub name: String,
     pub version: String,
     pub ecosystem: String,
+    /// 直接依存か推移的依存か（ロックファイルから分かる場合）
+    #[serde(default)]
+    pub relation: DependencyRelation,
+    /// 開発時のみの依存か
+    #[serde(default)]
+    pub dev: bool,
+    /// 省略可能な依存か（npm の optionalDependencies、Poetry の extras など）
+    #[serde(default)]
+    pub optional: bool,
+    /// ロックファイルに記録された SHA-256 チェックサム（16 進数、Cargo.lock など）
+    #[serde(default)]
+    pub checksum: Option<String>,
+    /// この依存関係を必要としているパッケージ名
+    #[serde(default)]
+    pub parents: Vec<String>,
+}
+
+/// 依存関係の種類
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyRelation {
+    Direct,
+    Transitive,
+    #[default]
+    Unknown,
+}
+
+/// 依存グラフを遡り、直接依存から target までの経路を "name@version" の列で返す
+/// 親の情報がなければ空。直接依存そのものなら target だけを返す
+pub fn dependency_path(deps: &[Dependency], target: &Dependency) -> Vec<String> {
+    if target.relation == DependencyRelation::Unknown && target.parents.is_empty() {
+        return Vec::new();
+    }
+    let label = |d: &Dependency| format!("{}@{}", d.name, d.version);
+
+    // 幅優先で親を辿り、最初に見つかった直接依存からの最短経路を採用する
+    let mut visited: Vec<usize> = Vec::new();
+    let mut queue: std::collections::VecDeque<(usize, Vec<usize>)> = deps
+        .iter()
+        .position(|d| d == target)
+        .map(|i| (i, vec![i]))
+        .into_iter()
+        .collect();
+    if queue.is_empty() {
+        return if target.relation == DependencyRelation::Direct {
+            vec![label(target)]
+        } else {
+            Vec::new()
+        };
+    }
+    while let Some((index, path)) = queue.pop_front() {
+        let dep = &deps[index];
+        if dep.relation == DependencyRelation::Direct {
+            return path.iter().rev().map(|&i| label(&deps[i])).collect();
+        }
+        if visited.contains(&index) {
+            continue;
+        }
+        visited.push(index);
+        for parent in &dep.parents {
+            for (i, candidate) in deps.iter().enumerate() {
+                if candidate.name == *parent && !path.contains(&i) {
+                    let mut next = path.clone();
+                    next.push(i);
+                    queue.push_back((i, next));
+                }
+            }
+        }
+    }
+    Vec::new()
+}
+
+/// 親の情報から直接依存 / 推移的依存を補う
+/// マニフェストに書かれたもの（direct）と、どのパッケージからも必要とされていないものを直接依存とする
+pub fn assign_relations(deps: &mut [Dependency], direct: &[String]) {
+    for dep in deps.iter_mut() {
+        dep.relation =
+            if dep.parents.is_empty() || direct.iter().any(|d| d.eq_ignore_ascii_case(&dep.name)) {
+                DependencyRelation::Direct
+            } else {
+                DependencyRelation::Transitive
+            };
+    }
+}
+
+/// ロックファイルの依存グラフの頂点（pnpm のピア依存の違いなどは 1 つにまとめる）
+#[derive(Debug)]
+pub struct LockNode {
+    pub name: String,
+    pub version: String,
+    pub dev: Option<bool>,
+    pub optional: bool,
+    /// SHA-256 16進数
+    pub checksum: Option<String>,
+    pub dependencies: Vec<(String, String)>,
+}
+
+impl LockNode {
+    pub fn find_or_insert(nodes: &mut Vec<LockNode>, name: String, version: String) -> &mut Self {
+        let index = match nodes
+            .iter()
+            .position(|n| n.name == name && n.version == version)
+        {
+            Some(index) => index,
+            None => {
+                nodes.push(LockNode {
+                    name,
+                    version,
+                    dev: None,
+                    optional: false,
+                    checksum: None,
+                    dependencies: Vec::new(),
+                });
+                nodes.len() - 1
+            }
+        };
+        &mut nodes[index]
+    }
+}
+
+/// 依存グラフの頂点から依存関係の一覧を組み立てる（pnpm / yarn berry / bun / uv 共通）
+/// direct は各プロジェクトの直接依存と、それが開発用かどうか。
+/// dev フラグを持たない頂点は、本番の直接依存から辿れないものを開発用とする
+/// （直接依存が分からない場合は関係・開発用ともに判別しない）
+pub fn graph_dependencies(
+    ecosystem: &str,
+    packages: &[LockNode],
+    direct: &[((String, String), bool)],
+) -> Vec<Dependency> {
+    let mut production: Vec<(String, String)> = direct
+        .iter()
+        .filter(|(_, dev)| !dev)
+        .map(|(target, _)| target.clone())
+        .collect();
+    let mut index = 0;
+    while index < production.len() {
+        if let Some(node) = packages
+            .iter()
+            .find(|p| (&p.name, &p.version) == (&production[index].0, &production[index].1))
+        {
+            for child in &node.dependencies {
+                if !production.contains(child) {
+                    production.push(child.clone());
+                }
+            }
+        }
+        index += 1;
+    }
+
+    let mut dependencies: Vec<Dependency> = packages
+        .iter()
+        .map(|node| {
+            let key = (node.name.clone(), node.version.clone());
+            let mut parents: Vec<String> = packages
+                .iter()
+                .filter(|p| p.dependencies.contains(&key))
+                .map(|p| p.name.clone())
+                .collect();
+            parents.sort();
+            parents.dedup();
+            Dependency {
+                name: node.name.clone(),
+                version: node.version.clone(),
+                ecosystem: ecosystem.to_string(),
+                relation: if direct.is_empty() {
+                    DependencyRelation::Unknown
+                } else if direct.iter().any(|(target, _)| *target == key) {
+                    DependencyRelation::Direct
+                } else {
+                    DependencyRelation::Transitive
+                },
+                dev: node
+                    .dev
+                    .unwrap_or_else(|| !direct.is_empty() && !production.contains(&key)),
+                optional: node.optional,
+                checksum: node.checksum.clone(),
+                parents,
+            }
+        })
+        .collect();
+    dependencies.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+
+    dependencies
 }
 
 /// スキャン結果
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ScanDependencies {
     pub ecosystem: String,
     pub source_file: String,
     pub dependencies: Vec<Dependency>,
+    /// 照会の対象から外したもの（git / path の依存関係など）やロックファイルがない場合の警告
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 /// エコシステムの種類
@@ -71,6 +260,48 @@ impl Ecosystem {
         }
     }
 
+    /// OSV のエコシステム名から変換
+    pub fn from_osv_name(name: &str) -> Option<Ecosystem> {
+        Self::all().into_iter().find(|e| e.osv_name() == name)
+    }
+
+    /// すべてのエコシステム
+    pub fn all() -> [Ecosystem; 12] {
+        [
+            Ecosystem::Npm,
+            Ecosystem::Cargo,
+            Ecosystem::PyPI,
+            Ecosystem::Go,
+            Ecosystem::Maven,
+            Ecosystem::NuGet,
+            Ecosystem::RubyGems,
+            Ecosystem::Packagist,
+            Ecosystem::Pub,
+            Ecosystem::Hex,
+            Ecosystem::CocoaPods,
+            Ecosystem::SwiftURL,
+        ]
+    }
+
+    /// Package URL (purl) の type を取得
+    /// https://github.com/package-url/purl-spec/blob/master/PURL-TYPES.rst
+    pub fn purl_type(&self) -> &'static str {
+        match self {
+            Ecosystem::Npm => "npm",
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::PyPI => "pypi",
+            Ecosystem::Go => "golang",
+            Ecosystem::Maven => "maven",
+            Ecosystem::NuGet => "nuget",
+            Ecosystem::RubyGems => "gem",
+            Ecosystem::Packagist => "composer",
+            Ecosystem::Pub => "pub",
+            Ecosystem::Hex => "hex",
+            Ecosystem::CocoaPods => "cocoapods",
+            Ecosystem::SwiftURL => "swift",
+        }
+    }
+
     /// 表示用の名前を取得
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -96,8 +327,197 @@ impl std::fmt::Display for Ecosystem {
     }
 }
 
-/// ディレクトリ内のすべての依存関係ファイルをスキャン
-pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanError> {
+/// 再帰スキャンのデフォルトの深さ
+pub const DEFAULT_MAX_DEPTH: usize = 4;
+
+/// 再帰スキャンで常に辿らないディレクトリ
+pub const DEFAULT_IGNORES: &[&str] = &["node_modules", "target", "vendor", ".git"];
+
+/// スキャンの設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanOptions {
+    /// スキャン対象からの最大の深さ（0 ならトップレベルのみ）
+    pub max_depth: usize,
+    /// 辿らないディレクトリのパターン（ディレクトリ名または相対パス、"*" / "?" 使用可）
+    pub ignore: Vec<String>,
+    /// OSV API を使わず、取り込み済みのローカル OSV データベースと照合する
+    pub offline: bool,
+    /// 開発時のみの依存関係の検出の扱い
+    pub dev_findings: DevFindings,
+    /// OSV への照会が失敗したらスキャンを失敗させる（無効なら警告に残して続ける）
+    pub strict: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            ignore: DEFAULT_IGNORES.iter().map(|s| s.to_string()).collect(),
+            offline: false,
+            dev_findings: DevFindings::Include,
+            strict: false,
+        }
+    }
+}
+
+/// 開発時のみの依存関係（devDependencies、packages-dev など）の検出の扱い
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DevFindings {
+    /// 本番の依存関係と同じに扱う
+    #[default]
+    Include,
+    /// 本番の検出の後に並べる（並び順のみで、件数やゲート判定には含める）
+    Deprioritize,
+    /// 照会の対象から外す
+    Exclude,
+}
+
+impl DevFindings {
+    /// "include" / "deprioritize" / "exclude" から変換
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "include" => Some(Self::Include),
+            "deprioritize" => Some(Self::Deprioritize),
+            "exclude" => Some(Self::Exclude),
+            _ => None,
+        }
+    }
+}
+
+impl ScanOptions {
+    /// デフォルトの除外に追加のパターンを加えて作成
+    pub fn new(max_depth: Option<usize>, extra_ignore: &[String]) -> Self {
+        let mut options = Self::default();
+        if let Some(depth) = max_depth {
+            options.max_depth = depth;
+        }
+        for pattern in extra_ignore {
+            let pattern = pattern.trim().trim_end_matches('/');
+            if !pattern.is_empty() && !options.ignore.iter().any(|p| p == pattern) {
+                options.ignore.push(pattern.to_string());
+            }
+        }
+        options
+    }
+
+    /// ディレクトリを辿らないか（name はディレクトリ名、relative はスキャン対象からの相対パス）
+    fn is_ignored(&self, name: &str, relative: &str) -> bool {
+        self.ignore
+            .iter()
+            .any(|p| wildcard_match(p, name) || wildcard_match(p, relative))
+    }
+}
+
+/// サブプロジェクトごとのスキャン結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectScan {
+    /// スキャン対象からの相対パス（トップレベルは "."）
+    pub path: String,
+    pub results: Vec<ScanDependencies>,
+}
+
+/// ディレクトリ以下を再帰的に辿り、依存関係ファイルのあるサブプロジェクトごとにスキャン
+pub fn scan_directory(
+    dir_path: &Path,
+    options: &ScanOptions,
+) -> Result<Vec<ProjectScan>, ScanError> {
+    let mut projects = Vec::new();
+    walk(dir_path, dir_path, 0, options, &mut projects)?;
+
+    if projects.is_empty() {
+        return Err(ScanError::NoDependencyFiles);
+    }
+
+    Ok(projects)
+}
+
+fn walk(
+    root: &Path,
+    dir: &Path,
+    depth: usize,
+    options: &ScanOptions,
+    projects: &mut Vec<ProjectScan>,
+) -> Result<(), ScanError> {
+    let results = scan_project_files(dir);
+    if !results.is_empty() {
+        projects.push(ProjectScan {
+            path: relative_path(root, dir),
+            results,
+        });
+    }
+
+    if depth >= options.max_depth {
+        return Ok(());
+    }
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        // トップレベルが読めない場合のみエラーにする
+        Err(e) if depth == 0 => return Err(e.into()),
+        Err(e) => {
+            eprintln!("Warning: Failed to read {}: {}", dir.display(), e);
+            return Ok(());
+        }
+    };
+
+    let mut subdirs: Vec<_> = entries
+        .filter_map(|e| e.ok())
+        // シンボリックリンクは辿らない（循環防止）
+        .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .map(|e| e.path())
+        .collect();
+    subdirs.sort();
+
+    for sub in subdirs {
+        let name = sub
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if options.is_ignored(&name, &relative_path(root, &sub)) {
+            continue;
+        }
+        walk(root, &sub, depth + 1, options, projects)?;
+    }
+
+    Ok(())
+}
+
+/// スキャン対象からの相対パス（"/" 区切り、トップレベルは "."）
+fn relative_path(root: &Path, dir: &Path) -> String {
+    match dir.strip_prefix(root) {
+        Ok(rel) if !rel.as_os_str().is_empty() => rel.to_string_lossy().replace('\\', "/"),
+        _ => ".".to_string(),
+    }
+}
+
+/// "*"（任意の文字列）と "?"（任意の 1 文字）のみのワイルドカード照合
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((star_pi, star_ti)) = star {
+            pi = star_pi + 1;
+            ti = star_ti + 1;
+            star = Some((star_pi, star_ti + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+/// 1 つのディレクトリ直下の依存関係ファイルをスキャン
+fn scan_project_files(dir_path: &Path) -> Vec<ScanDependencies> {
     let mut results = Vec::new();
 
     // npm / pnpm
@@ -105,7 +525,7 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if package_lock.exists() {
         match npm::parse_package_lock(&package_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse package-lock.json: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", package_lock.display(), e),
         }
     }
 
@@ -113,7 +533,7 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if pnpm_lock.exists() {
         match npm::parse_pnpm_lock(&pnpm_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse pnpm-lock.yaml: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", pnpm_lock.display(), e),
         }
     }
 
@@ -122,25 +542,36 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if yarn_lock.exists() {
         match npm::parse_yarn_lock(&yarn_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse yarn.lock: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", yarn_lock.display(), e),
+        }
+    }
+
+    // bun
+    let bun_lock = dir_path.join("bun.lock");
+    if bun_lock.exists() {
+        match npm::parse_bun_lock(&bun_lock) {
+            Ok(deps) => results.push(deps),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", bun_lock.display(), e),
         }
     }
+    results.extend(npm::binary_bun_lock(dir_path));
 
     // Cargo
     let cargo_lock = dir_path.join("Cargo.lock");
     if cargo_lock.exists() {
         match cargo::parse_cargo_lock(&cargo_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse Cargo.lock: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", cargo_lock.display(), e),
         }
     }
+    results.extend(cargo::manifest_without_lock(dir_path));
 
     // pip
     let requirements = dir_path.join("requirements.txt");
     if requirements.exists() {
         match pip::parse_requirements(&requirements) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse requirements.txt: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", requirements.display(), e),
         }
     }
 
@@ -148,7 +579,7 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if poetry_lock.exists() {
         match pip::parse_poetry_lock(&poetry_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse poetry.lock: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", poetry_lock.display(), e),
         }
     }
 
@@ -156,16 +587,49 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if pipfile_lock.exists() {
         match pip::parse_pipfile_lock(&pipfile_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse Pipfile.lock: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", pipfile_lock.display(), e),
+        }
+    }
+
+    let uv_lock = dir_path.join("uv.lock");
+    if uv_lock.exists() {
+        match pip::parse_uv_lock(&uv_lock) {
+            Ok(deps) => results.push(deps),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", uv_lock.display(), e),
+        }
+    }
+
+    let pdm_lock = dir_path.join("pdm.lock");
+    if pdm_lock.exists() {
+        match pip::parse_pdm_lock(&pdm_lock) {
+            Ok(deps) => results.push(deps),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", pdm_lock.display(), e),
+        }
+    }
+
+    // ロックファイルがなければ pyproject.toml の直接依存だけをスキャン
+    let pyproject = dir_path.join("pyproject.toml");
+    if pyproject.exists() && !pip::has_lockfile(dir_path) {
+        match pip::parse_pyproject(&pyproject) {
+            Ok(deps) if !deps.dependencies.is_empty() => results.push(deps),
+            Ok(_) => {}
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", pyproject.display(), e),
         }
     }
 
     // Go
+    // go.mod が正（go.sum だけのときは go.sum に記録されたモジュールを使う）
+    let go_mod = dir_path.join("go.mod");
     let go_sum = dir_path.join("go.sum");
-    if go_sum.exists() {
+    if go_mod.exists() {
+        match go::parse_go_mod(&go_mod) {
+            Ok(deps) => results.push(deps),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", go_mod.display(), e),
+        }
+    } else if go_sum.exists() {
         match go::parse_go_sum(&go_sum) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse go.sum: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", go_sum.display(), e),
         }
     }
 
@@ -174,7 +638,7 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if gradle_lock.exists() {
         match maven::parse_gradle_lockfile(&gradle_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse gradle.lockfile: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", gradle_lock.display(), e),
         }
     }
 
@@ -182,7 +646,20 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if pom_xml.exists() {
         match maven::parse_pom_xml(&pom_xml) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse pom.xml: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", pom_xml.display(), e),
+        }
+    }
+
+    // Gradle のバージョンカタログ（ロックファイルがあればそちらが解決済みのバージョン）
+    let version_catalog = dir_path.join("gradle").join("libs.versions.toml");
+    if version_catalog.exists() && !gradle_lock.exists() {
+        match maven::parse_version_catalog(&version_catalog) {
+            Ok(deps) => results.push(deps),
+            Err(e) => eprintln!(
+                "Warning: Failed to parse {}: {}",
+                version_catalog.display(),
+                e
+            ),
         }
     }
 
@@ -191,7 +668,11 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if packages_lock.exists() {
         match nuget::parse_packages_lock(&packages_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse packages.lock.json: {}", e),
+            Err(e) => eprintln!(
+                "Warning: Failed to parse {}: {}",
+                packages_lock.display(),
+                e
+            ),
         }
     }
 
@@ -200,7 +681,7 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if gemfile_lock.exists() {
         match ruby::parse_gemfile_lock(&gemfile_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse Gemfile.lock: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", gemfile_lock.display(), e),
         }
     }
 
@@ -209,7 +690,11 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if composer_lock.exists() {
         match php::parse_composer_lock(&composer_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse composer.lock: {}", e),
+            Err(e) => eprintln!(
+                "Warning: Failed to parse {}: {}",
+                composer_lock.display(),
+                e
+            ),
         }
     }
 
@@ -218,7 +703,7 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if pubspec_lock.exists() {
         match dart::parse_pubspec_lock(&pubspec_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse pubspec.lock: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", pubspec_lock.display(), e),
         }
     }
 
@@ -227,7 +712,7 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if mix_lock.exists() {
         match elixir::parse_mix_lock(&mix_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse mix.lock: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", mix_lock.display(), e),
         }
     }
 
@@ -236,7 +721,7 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if podfile_lock.exists() {
         match cocoapods::parse_podfile_lock(&podfile_lock) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse Podfile.lock: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", podfile_lock.display(), e),
         }
     }
 
@@ -245,15 +730,15 @@ pub fn scan_directory(dir_path: &Path) -> Result<Vec<ScanDependencies>, ScanErro
     if package_resolved.exists() {
         match swift::parse_package_resolved(&package_resolved) {
             Ok(deps) => results.push(deps),
-            Err(e) => eprintln!("Warning: Failed to parse Package.resolved: {}", e),
+            Err(e) => eprintln!(
+                "Warning: Failed to parse {}: {}",
+                package_resolved.display(),
+                e
+            ),
         }
     }
 
-    if results.is_empty() {
-        return Err(ScanError::NoDependencyFiles);
-    }
-
-    Ok(results)
+    results
 }
 
 /// スキャンエラー
@@ -266,3 +751,124 @@ pub enum ScanError {
     #[error("Parse error: {0}")]
     Parse(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const CARGO_LOCK: &str = "[[package]]\nname = \"serde\"\nversion = \"1.0.0\"\n";
+    const GO_SUM: &str = "github.com/pkg/errors v0.9.1 h1:abc=\n";
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_scan_directory_recursive() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "services/api/package-lock.json",
+            r#"{"lockfileVersion": 3, "packages": {"": {}, "node_modules/lodash": {"version": "4.17.20"}}}"#,
+        );
+        write(root, "crates/core/Cargo.lock", CARGO_LOCK);
+        write(root, "tools/gen/go.sum", GO_SUM);
+        write(
+            root,
+            "services/api/node_modules/left-pad/package-lock.json",
+            r#"{"lockfileVersion": 3, "packages": {}}"#,
+        );
+        write(root, "target/debug/Cargo.lock", CARGO_LOCK);
+        write(root, "vendor/lib/go.sum", GO_SUM);
+
+        let projects = scan_directory(root, &ScanOptions::default()).unwrap();
+        let paths: Vec<&str> = projects.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, vec!["crates/core", "services/api", "tools/gen"]);
+        assert_eq!(projects[1].results[0].ecosystem, "npm");
+        assert_eq!(projects[1].results[0].dependencies[0].name, "lodash");
+    }
+
+    #[test]
+    fn test_scan_directory_depth_and_ignore() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "Cargo.lock", CARGO_LOCK);
+        write(root, "crates/core/Cargo.lock", CARGO_LOCK);
+        write(root, "examples/demo/go.sum", GO_SUM);
+
+        let top_only = scan_directory(root, &ScanOptions::new(Some(0), &[])).unwrap();
+        assert_eq!(top_only.len(), 1);
+        assert_eq!(top_only[0].path, ".");
+
+        let options = ScanOptions::new(None, &["examples/*".to_string()]);
+        let projects = scan_directory(root, &options).unwrap();
+        let paths: Vec<&str> = projects.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, vec![".", "crates/core"]);
+    }
+
+    #[test]
+    fn test_scan_directory_no_files() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "node_modules/x/Cargo.lock", CARGO_LOCK);
+        assert!(matches!(
+            scan_directory(dir.path(), &ScanOptions::default()),
+            Err(ScanError::NoDependencyFiles)
+        ));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("node_modules", "node_modules"));
+        assert!(wildcard_match("*.egg-info", "foo.egg-info"));
+        assert!(wildcard_match("examples/*", "examples/demo"));
+        assert!(wildcard_match("build?", "build2"));
+        assert!(!wildcard_match("build?", "build"));
+        assert!(!wildcard_match("target", "targets"));
+    }
+
+    #[test]
+    fn test_dependency_path() {
+        let dep = |name: &str, relation: DependencyRelation, parents: &[&str]| Dependency {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            ecosystem: "npm".to_string(),
+            relation,
+            parents: parents.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        };
+        let deps = vec![
+            dep("express", DependencyRelation::Direct, &[]),
+            dep("body-parser", DependencyRelation::Transitive, &["express"]),
+            dep(
+                "qs",
+                DependencyRelation::Transitive,
+                &["body-parser", "express"],
+            ),
+            dep("cycle-a", DependencyRelation::Transitive, &["cycle-b"]),
+            dep("cycle-b", DependencyRelation::Transitive, &["cycle-a"]),
+        ];
+
+        // 最短の経路を選ぶ
+        assert_eq!(
+            dependency_path(&deps, &deps[2]),
+            vec!["express@1.0.0", "qs@1.0.0"]
+        );
+        assert_eq!(
+            dependency_path(&deps, &deps[1]),
+            vec!["express@1.0.0", "body-parser@1.0.0"]
+        );
+        assert_eq!(dependency_path(&deps, &deps[0]), vec!["express@1.0.0"]);
+        // 直接依存に辿り着けなければ空
+        assert!(dependency_path(&deps, &deps[3]).is_empty());
+        // グラフの情報がない依存関係
+        let unknown = Dependency {
+            name: "lodash".to_string(),
+            ..Default::default()
+        };
+        assert!(dependency_path(std::slice::from_ref(&unknown), &unknown).is_empty());
+    }
+}
@@ -1,7 +1,11 @@
 //! npm / pnpm / yarn 依存関係パーサー
 
-use super::{Dependency, ScanDependencies, ScanError};
+use super::{
+    assign_relations, graph_dependencies, yaml, Dependency, DependencyRelation, LockNode,
+    ScanDependencies, ScanError,
+};
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fs;
@@ -13,40 +17,132 @@ pub fn parse_package_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
     let lock_file: PackageLockJson =
         serde_json::from_str(&content).map_err(|e| ScanError::Parse(e.to_string()))?;
 
-    let mut dependencies = Vec::new();
-
-    // v2/v3 format (packages)
-    if let Some(packages) = lock_file.packages {
-        for (name, info) in packages {
-            // Skip root package (empty name) and linked packages
-            if name.is_empty() || info.link.unwrap_or(false) {
-                continue;
-            }
-
-            // パッケージ名を抽出（node_modules/... から）
-            let package_name = extract_package_name(&name);
-            
-            if let Some(version) = info.version {
-                dependencies.push(Dependency {
-                    name: package_name,
-                    version,
-                    ecosystem: "npm".to_string(),
-                });
+    let dependencies = if let Some(packages) = lock_file.packages {
+        // v2/v3 format (packages)
+        collect_packages(&packages)
+    } else if let Some(deps) = lock_file.dependencies {
+        // v1 format (dependencies)
+        let mut dependencies = Vec::new();
+        let mut parents: HashMap<String, Vec<String>> = HashMap::new();
+        collect_dependencies_v1(&deps, &mut dependencies, &mut parents);
+        for dep in dependencies.iter_mut() {
+            if let Some(names) = parents.get(&dep.name) {
+                dep.parents = names.clone();
+                dep.parents.sort();
+                dep.parents.dedup();
             }
         }
-    }
-    // v1 format (dependencies)
-    else if let Some(deps) = lock_file.dependencies {
-        collect_dependencies_v1(&deps, &mut dependencies);
-    }
+        // v1 にはルートの依存の一覧がないので、どこからも必要とされていないものを直接依存とみなす
+        assign_relations(&mut dependencies, &[]);
+        dependencies
+    } else {
+        Vec::new()
+    };
 
     Ok(ScanDependencies {
         ecosystem: "npm".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
+/// v2/v3 形式の packages から依存関係と依存グラフを収集
+/// ルート（""）とワークスペース（node_modules 以外のパス）の依存を直接依存とし、
+/// 各エントリの dependencies を node_modules の探索順に解決して親子関係を求める
+fn collect_packages(packages: &HashMap<String, PackageLockPackage>) -> Vec<Dependency> {
+    let mut parents: HashMap<&str, Vec<String>> = HashMap::new();
+    let mut direct: HashSet<&str> = HashSet::new();
+
+    for (path, info) in packages {
+        let is_importer = !path.starts_with("node_modules/") && !path.contains("/node_modules/");
+        let owner = if is_importer {
+            None
+        } else {
+            Some(extract_package_name(path))
+        };
+        let mut children: Vec<&String> = info
+            .dependencies
+            .keys()
+            .chain(info.optional_dependencies.keys())
+            .chain(info.peer_dependencies.keys())
+            .collect();
+        if is_importer {
+            children.extend(info.dev_dependencies.keys());
+        }
+        for child in children {
+            let Some(resolved) = resolve_package_path(packages, path, child) else {
+                continue;
+            };
+            match owner {
+                Some(ref owner) => parents.entry(resolved).or_default().push(owner.clone()),
+                None => {
+                    direct.insert(resolved);
+                }
+            }
+        }
+    }
+
+    let mut paths: Vec<&String> = packages.keys().collect();
+    paths.sort();
+    let mut dependencies = Vec::new();
+    for path in paths {
+        let info = &packages[path];
+        // ルート・ワークスペース本体・リンクはインストールされるパッケージではない
+        if !path.contains("node_modules/") || info.link.unwrap_or(false) {
+            continue;
+        }
+        let Some(ref version) = info.version else {
+            continue;
+        };
+        let mut dep_parents = parents.remove(path.as_str()).unwrap_or_default();
+        dep_parents.sort();
+        dep_parents.dedup();
+        dependencies.push(Dependency {
+            name: extract_package_name(path),
+            version: version.clone(),
+            ecosystem: "npm".to_string(),
+            relation: if direct.contains(path.as_str()) {
+                DependencyRelation::Direct
+            } else {
+                DependencyRelation::Transitive
+            },
+            // devOptional は dev と optional の両方を省いたときだけ入らないので、開発専用とはみなさない
+            dev: info.dev.unwrap_or(false),
+            optional: info.optional.unwrap_or(false) || info.dev_optional.unwrap_or(false),
+            parents: dep_parents,
+            ..Default::default()
+        });
+    }
+    dependencies
+}
+
+/// Node.js のモジュール解決と同じく、from の node_modules から祖先へ向かって name を探す
+fn resolve_package_path<'a>(
+    packages: &'a HashMap<String, PackageLockPackage>,
+    from: &str,
+    name: &str,
+) -> Option<&'a str> {
+    let mut base = from.to_string();
+    loop {
+        let candidate = if base.is_empty() {
+            format!("node_modules/{}", name)
+        } else {
+            format!("{}/node_modules/{}", base, name)
+        };
+        if let Some((key, _)) = packages.get_key_value(&candidate) {
+            return Some(key.as_str());
+        }
+        if base.is_empty() {
+            return None;
+        }
+        base = match base.rfind("node_modules/") {
+            Some(pos) => base[..pos].trim_end_matches('/').to_string(),
+            None => String::new(),
+        };
+    }
+}
+
 /// パッケージ名を node_modules パスから抽出
 fn extract_package_name(path: &str) -> String {
     // "node_modules/@scope/package" -> "@scope/package"
@@ -62,9 +158,11 @@ fn extract_package_name(path: &str) -> String {
 }
 
 /// v1形式の依存関係を再帰的に収集
+/// requires から親パッケージ名も集める
 fn collect_dependencies_v1(
     deps: &HashMap<String, PackageLockDependency>,
     result: &mut Vec<Dependency>,
+    parents: &mut HashMap<String, Vec<String>>,
 ) {
     for (name, info) in deps {
         if let Some(ref version) = info.version {
@@ -72,103 +170,126 @@ fn collect_dependencies_v1(
                 name: name.clone(),
                 version: version.clone(),
                 ecosystem: "npm".to_string(),
+                dev: info.dev.unwrap_or(false),
+                optional: info.optional.unwrap_or(false),
+                ..Default::default()
             });
         }
+        for required in info.requires.keys() {
+            parents
+                .entry(required.clone())
+                .or_default()
+                .push(name.clone());
+        }
 
         // ネストされた依存関係も収集
         if let Some(ref nested) = info.dependencies {
-            collect_dependencies_v1(nested, result);
+            collect_dependencies_v1(nested, result, parents);
         }
     }
 }
 
 /// pnpm-lock.yaml をパース
+/// lockfileVersion 5.x（"/name/1.0.0_peer"）、6.x（"/name@1.0.0(peer)"）、9.x（packages + snapshots）に対応。
+/// importers（ワークスペース）を含む各プロジェクトの依存を直接依存とし、そこから辿れるものを推移的依存とする
 pub fn parse_pnpm_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
     let content = fs::read_to_string(path)?;
-    let mut dependencies = Vec::new();
+    let lock: PnpmLock = yaml::from_str(&content)?;
+    let v5 = lock.major_version() < 6;
 
-    // Simple YAML parsing without external crate
-    // pnpm-lock.yaml format varies by version, we'll handle common formats
-    
-    let mut in_packages_section = false;
-    let mut current_package: Option<String> = None;
-    let mut current_version: Option<String> = None;
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-
-        // Check for packages section (v6+) or lockfileVersion
-        if trimmed.starts_with("packages:") {
-            in_packages_section = true;
+    // パッケージ本体（v9 では依存関係は snapshots 側にある）
+    let mut packages: Vec<LockNode> = Vec::new();
+    for (key, info) in &lock.packages {
+        let Some((name, version)) = info.resolve(key, v5) else {
             continue;
-        }
-
-        // New section starts
-        if !trimmed.is_empty() && !line.starts_with(' ') && !line.starts_with('\t') {
-            if trimmed != "packages:" {
-                in_packages_section = false;
-            }
-        }
-
-        if in_packages_section {
-            // Package entry like "  /@scope/package@1.0.0:" or "  /package@1.0.0:"
-            if line.starts_with("  /") || line.starts_with("  '/'") {
-                // Save previous package if exists
-                if let (Some(name), Some(version)) = (current_package.take(), current_version.take()) {
-                    dependencies.push(Dependency {
-                        name,
-                        version,
-                        ecosystem: "npm".to_string(),
-                    });
-                }
+        };
+        let node = LockNode::find_or_insert(&mut packages, name, version);
+        node.dev = node.dev.or(info.dev);
+        node.optional |= info.optional;
+        node.dependencies.extend(info.edges(v5));
+    }
+    for (key, info) in &lock.snapshots {
+        let Some((name, version)) = info.resolve(key, v5) else {
+            continue;
+        };
+        let node = LockNode::find_or_insert(&mut packages, name, version);
+        node.optional |= info.optional;
+        node.dependencies.extend(info.edges(v5));
+    }
 
-                // Parse new package
-                let clean_line = trimmed.trim_start_matches('/').trim_start_matches("'/").trim_end_matches(':').trim_end_matches("':");
-                if let Some((name, version)) = parse_pnpm_package_spec(clean_line) {
-                    current_package = Some(name);
-                    current_version = Some(version);
-                }
-            }
-            // Version field within package (older format)
-            else if trimmed.starts_with("version:") {
-                if current_package.is_some() && current_version.is_none() {
-                    let version = trimmed
-                        .strip_prefix("version:")
-                        .map(|s| s.trim().trim_matches('\'').trim_matches('"'))
-                        .unwrap_or("");
-                    if !version.is_empty() {
-                        current_version = Some(version.to_string());
-                    }
+    // 各プロジェクトの直接依存（単一プロジェクトの v5 / v6 はトップレベルに書かれる）
+    let mut direct: Vec<((String, String), bool)> = Vec::new();
+    for importer in std::iter::once(&lock.root).chain(lock.importers.values()) {
+        for (dev, deps) in [
+            (false, &importer.dependencies),
+            (false, &importer.optional_dependencies),
+            (true, &importer.dev_dependencies),
+        ] {
+            for (name, dep) in deps {
+                if let Some(target) = pnpm_reference(name, dep.version(), v5) {
+                    direct.push((target, dev));
                 }
             }
         }
-
-        // Handle dependencies section (older pnpm format)
-        if trimmed.starts_with("dependencies:") || trimmed.starts_with("devDependencies:") {
-            continue;
-        }
     }
 
-    // Don't forget the last package
-    if let (Some(name), Some(version)) = (current_package, current_version) {
-        dependencies.push(Dependency {
-            name,
-            version,
-            ecosystem: "npm".to_string(),
-        });
-    }
-
-    // Deduplicate
-    dependencies.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
-    dependencies.dedup_by(|a, b| a.name == b.name && a.version == b.version);
+    let dependencies = graph_dependencies("npm", &packages, &direct);
 
     Ok(ScanDependencies {
         ecosystem: "npm".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
+/// 依存先の参照を (パッケージ名, バージョン) に解決
+/// "4.17.21" / "18.2.0(react@18.2.0)" / "1.0.0_react@18.0.0" はその名前のバージョン、
+/// "/alias/1.0.0" や "npm:alias@1.0.0" などのパスは別名、"link:" はワークスペース内のプロジェクト（対象外）
+fn pnpm_reference(name: &str, reference: &str, v5: bool) -> Option<(String, String)> {
+    if reference.starts_with("link:") || reference.starts_with("file:") {
+        return None;
+    }
+    if let Some(alias) = reference.strip_prefix("npm:") {
+        return parse_pnpm_package_spec(alias);
+    }
+    if reference.starts_with('/') {
+        return parse_pnpm_key(reference, v5);
+    }
+    let version = strip_pnpm_peers(reference);
+    // レジストリ以外（tarball の URL など）は v9 では "name@url" の形で参照される
+    if version.contains(':') {
+        return None;
+    }
+    Some((name.to_string(), version.to_string()))
+}
+
+/// packages / snapshots のキーをパース
+/// v5: "/@scope/name/1.0.0_peer@2.0.0"、v6: "/@scope/name@1.0.0(peer@2.0.0)"、v9: "@scope/name@1.0.0(peer@2.0.0)"
+fn parse_pnpm_key(key: &str, v5: bool) -> Option<(String, String)> {
+    let key = key.strip_prefix('/').unwrap_or(key);
+    if !v5 {
+        let (name, version) = parse_pnpm_package_spec(key)?;
+        let valid = !name.is_empty() && !version.is_empty() && !version.contains(':');
+        return valid.then_some((name, version));
+    }
+    let mut parts = key.split('/');
+    let mut name = parts.next()?.to_string();
+    if name.starts_with('@') {
+        name = format!("{}/{}", name, parts.next()?);
+    }
+    let version = strip_pnpm_peers(parts.next()?);
+    if version.is_empty() || parts.next().is_some() {
+        return None;
+    }
+    Some((name, version.to_string()))
+}
+
+/// ピア依存の接尾辞を取り除く（"1.0.0(react@18.0.0)" / "1.0.0_react@18.0.0" -> "1.0.0"）
+fn strip_pnpm_peers(version: &str) -> &str {
+    version.split(['(', '_']).next().unwrap_or(version).trim()
+}
+
 /// pnpm パッケージスペックをパース
 /// "@scope/package@1.0.0" -> ("@scope/package", "1.0.0")
 /// "package@1.0.0" -> ("package", "1.0.0")
@@ -198,20 +319,21 @@ fn parse_pnpm_package_spec(spec: &str) -> Option<(String, String)> {
 /// yarn.lock をパース (v1 クラシック形式と v2/berry 形式の両方に対応)
 pub fn parse_yarn_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
     let content = fs::read_to_string(path)?;
-    
-    // Detect format: v2/berry uses "__metadata:" at the top
-    let is_berry = content.contains("__metadata:");
-    
-    let dependencies = if is_berry {
-        parse_yarn_berry(&content)
+
+    // Berry (v2 以降) は YAML で、先頭に __metadata がある
+    let is_berry = content.lines().any(|line| line.starts_with("__metadata:"));
+
+    let (dependencies, warnings) = if is_berry {
+        parse_yarn_berry(path, &content)?
     } else {
-        parse_yarn_classic(&content)
+        (parse_yarn_classic(&content), Vec::new())
     };
 
     Ok(ScanDependencies {
         ecosystem: "npm".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings,
     })
 }
 
@@ -248,6 +370,7 @@ fn parse_yarn_classic(content: &str) -> Vec<Dependency> {
                             name,
                             version: version.clone(),
                             ecosystem: "npm".to_string(),
+                            ..Default::default()
                         });
                     }
                 }
@@ -284,6 +407,7 @@ fn parse_yarn_classic(content: &str) -> Vec<Dependency> {
                     name,
                     version: version.clone(),
                     ecosystem: "npm".to_string(),
+                    ..Default::default()
                 });
             }
         }
@@ -295,78 +419,333 @@ fn parse_yarn_classic(content: &str) -> Vec<Dependency> {
 /// yarn v2/berry 形式をパース
 /// Format:
 /// ```yaml
-/// "lodash@npm:^4.17.21":
+/// "lodash@npm:^4.17.21, lodash@npm:^4.17.0":
 ///   version: 4.17.21
 ///   resolution: "lodash@npm:4.17.21"
+///   dependencies:
+///     foo: "npm:^1.0.0"
 /// ```
-fn parse_yarn_berry(content: &str) -> Vec<Dependency> {
-    let mut dependencies = Vec::new();
-    let mut seen: HashSet<(String, String)> = HashSet::new();
-    
-    let mut current_names: Vec<String> = Vec::new();
-    let mut current_version: Option<String> = None;
+/// resolution のプロトコルで区別し、npm: はレジストリのパッケージ、workspace: はプロジェクト自身
+/// （その依存を直接依存とする）、patch: は元になった npm のパッケージとして扱う。
+/// portal: / link: などローカルのディレクトリや git / tarball は OSV で照合できないので警告して除外する。
+/// 開発用の依存はロックファイルに書かれないため、各ワークスペースの package.json から判別する
+fn parse_yarn_berry(
+    path: &Path,
+    content: &str,
+) -> Result<(Vec<Dependency>, Vec<String>), ScanError> {
+    let lock: BTreeMap<String, serde_json::Value> = yaml::from_str(content)?;
+    let source_file = path.to_string_lossy().to_string();
+    let lock_dir = path.parent().unwrap_or(Path::new("."));
 
-    for line in content.lines() {
-        // Skip metadata and comments
-        if line.starts_with("__metadata:") || line.starts_with('#') {
+    let mut entries: Vec<YarnBerryEntry> = Vec::new();
+    // "name@range" の記述子 -> entries の添字
+    let mut descriptors: HashMap<String, usize> = HashMap::new();
+    for (key, value) in lock {
+        if key == "__metadata" {
             continue;
         }
+        let entry: YarnBerryEntry =
+            serde_json::from_value(value).map_err(|e| ScanError::Parse(e.to_string()))?;
+        for descriptor in key.split(',') {
+            descriptors.insert(descriptor.trim().to_string(), entries.len());
+        }
+        entries.push(entry);
+    }
 
-        // Package declaration line (quoted, ends with ":")
-        // "lodash@npm:^4.17.21, lodash@npm:^4.17.0":
-        if !line.starts_with(' ') && !line.is_empty() && line.ends_with(':') {
-            // Save previous package
-            if let Some(version) = current_version.take() {
-                for name in current_names.drain(..) {
-                    let key = (name.clone(), version.clone());
-                    if !seen.contains(&key) {
-                        seen.insert(key);
-                        dependencies.push(Dependency {
-                            name,
-                            version: version.clone(),
-                            ecosystem: "npm".to_string(),
-                        });
-                    }
+    let mut warnings = Vec::new();
+    let mut packages: Vec<LockNode> = Vec::new();
+    let mut direct: Vec<((String, String), bool)> = Vec::new();
+    let resolved: Vec<YarnBerryResolution> = entries
+        .iter()
+        .map(|entry| yarn_berry_resolution(&entry.resolution))
+        .collect();
+
+    for (entry, resolution) in entries.iter().zip(&resolved) {
+        let edges: Vec<((String, String), bool)> = entry
+            .dependencies
+            .iter()
+            .filter_map(|(name, range)| {
+                let range = yaml_scalar(range)?;
+                let index = descriptors
+                    .get(&format!("{}@{}", name, range))
+                    .or_else(|| descriptors.get(&format!("{}@npm:{}", name, range)))?;
+                let YarnBerryResolution::Package(target) = &resolved[*index] else {
+                    return None;
+                };
+                let optional = entry
+                    .dependencies_meta
+                    .get(name)
+                    .is_some_and(|m| m.optional);
+                Some((target.clone(), optional))
+            })
+            .collect();
+
+        match resolution {
+            YarnBerryResolution::Package((name, version)) => {
+                let node = LockNode::find_or_insert(&mut packages, name.clone(), version.clone());
+                node.dependencies
+                    .extend(edges.iter().map(|(target, _)| target.clone()));
+            }
+            YarnBerryResolution::Workspace(workspace) => {
+                let dev_dependencies = package_json_dev_dependencies(&lock_dir.join(workspace));
+                for (target, _) in &edges {
+                    let dev = dev_dependencies.contains(&target.0);
+                    direct.push((target.clone(), dev));
                 }
             }
-            current_names.clear();
+            YarnBerryResolution::Skipped(protocol) => {
+                warnings.push(format!(
+                    "{}: skipped {} dependency {}",
+                    source_file, protocol, entry.resolution
+                ));
+            }
+        }
+        for (target, optional) in &edges {
+            if *optional {
+                LockNode::find_or_insert(&mut packages, target.0.clone(), target.1.clone())
+                    .optional = true;
+            }
+        }
+    }
 
-            // Parse package names
-            let specs = line.trim_matches('"').trim_end_matches(':').trim_matches('"');
-            for spec in specs.split(", ") {
-                if let Some(name) = extract_yarn_berry_package_name(spec.trim().trim_matches('"')) {
-                    if !current_names.contains(&name) {
-                        current_names.push(name);
-                    }
-                }
+    Ok((graph_dependencies("npm", &packages, &direct), warnings))
+}
+
+/// yarn berry の resolution の解決結果
+enum YarnBerryResolution {
+    /// レジストリのパッケージ (名前, バージョン)
+    Package((String, String)),
+    /// ワークスペース内のプロジェクト（ロックファイルからの相対パス）
+    Workspace(String),
+    /// 照合できないプロトコル
+    Skipped(String),
+}
+
+/// resolution ("lodash@npm:4.17.21" / "app@workspace:." / "resolve@patch:resolve@npm%3A1.22.8#~builtin<compat/resolve>::...")
+/// をプロトコルごとに解決
+fn yarn_berry_resolution(resolution: &str) -> YarnBerryResolution {
+    let Some((name, range)) = split_yarn_descriptor(resolution) else {
+        return YarnBerryResolution::Skipped("unknown".to_string());
+    };
+    let (protocol, reference) = range.split_once(':').unwrap_or(("", range));
+    match protocol {
+        "npm" => match split_yarn_descriptor(reference) {
+            // 別名 ("npm:string-width@4.2.3") は実際のパッケージ名で照合する
+            Some((real_name, version)) => {
+                YarnBerryResolution::Package((real_name.to_string(), version.to_string()))
             }
+            None => YarnBerryResolution::Package((name.to_string(), reference.to_string())),
+        },
+        "workspace" => YarnBerryResolution::Workspace(reference.to_string()),
+        // パッチを当てたパッケージは元のパッケージのバージョンで照合する
+        "patch" => {
+            let source = reference.split('#').next().unwrap_or(reference);
+            yarn_berry_resolution(&source.replace("%3A", ":").replace("%40", "@"))
         }
-        // Version line
-        else if line.trim().starts_with("version:") {
-            let version_part = line.trim().strip_prefix("version:").unwrap_or("").trim();
-            let version = version_part.trim_matches('"').trim_matches('\'');
-            if !version.is_empty() {
-                current_version = Some(version.to_string());
+        "" => YarnBerryResolution::Skipped("unknown".to_string()),
+        _ => YarnBerryResolution::Skipped(protocol.to_string()),
+    }
+}
+
+/// "name@range" をパッケージ名と範囲に分ける（スコープ付きの名前の先頭の @ は区切りとみなさない）
+/// "lodash@npm:^4.17.21" -> ("lodash", "npm:^4.17.21")
+fn split_yarn_descriptor(descriptor: &str) -> Option<(&str, &str)> {
+    let descriptor = descriptor.trim().trim_matches('"');
+    let at_pos = descriptor
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| *c == '@')
+        .map(|(i, _)| i)?;
+    Some((&descriptor[..at_pos], &descriptor[at_pos + 1..]))
+}
+
+/// YAML のスカラー値を文字列として取り出す（"1" などの整数は数値として読まれるため）
+fn yaml_scalar(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// package.json の devDependencies に書かれたパッケージ名（読めなければ空）
+fn package_json_dev_dependencies(dir: &Path) -> HashSet<String> {
+    fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<PackageJson>(&content).ok())
+        .map(|manifest| manifest.dev_dependencies.into_keys().collect())
+        .unwrap_or_default()
+}
+
+/// bun.lock（Bun 1.2 以降のテキスト形式）をパース
+/// 末尾のカンマを許す JSON で、workspaces の依存を直接依存、packages をインストールされるパッケージとする
+pub fn parse_bun_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
+    let content = fs::read_to_string(path)?;
+    let lock: BunLock = serde_json::from_str(&strip_trailing_commas(&content))
+        .map_err(|e| ScanError::Parse(e.to_string()))?;
+    let source_file = path.to_string_lossy().to_string();
+
+    // packages のキー -> (名前, バージョン)。ワークスペースや照合できないものは含めない
+    let mut resolved: HashMap<&str, (String, String)> = HashMap::new();
+    let mut warnings = Vec::new();
+    for (key, entry) in &lock.packages {
+        let Some(spec) = entry.first().and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let Some((name, version)) = split_yarn_descriptor(spec) else {
+            continue;
+        };
+        match version.split_once(':') {
+            None => {
+                resolved.insert(key, (name.to_string(), version.to_string()));
             }
+            Some(("workspace", _)) => {}
+            Some((protocol, _)) => warnings.push(format!(
+                "{}: skipped {} dependency {}",
+                source_file, protocol, spec
+            )),
         }
     }
 
-    // Don't forget the last package
-    if let Some(version) = current_version {
-        for name in current_names {
-            let key = (name.clone(), version.clone());
-            if !seen.contains(&key) {
-                seen.insert(key);
-                dependencies.push(Dependency {
-                    name,
-                    version: version.clone(),
-                    ecosystem: "npm".to_string(),
-                });
+    let mut packages: Vec<LockNode> = Vec::new();
+    for (key, entry) in &lock.packages {
+        let Some((name, version)) = resolved.get(key.as_str()) else {
+            continue;
+        };
+        let meta: BunPackageMeta = entry
+            .iter()
+            .find(|v| v.is_object())
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let edges: Vec<((String, String), bool)> = meta
+            .dependencies
+            .keys()
+            .map(|dep| (dep, false))
+            .chain(meta.optional_dependencies.keys().map(|dep| (dep, true)))
+            .filter_map(|(dep, optional)| {
+                Some((bun_resolve(&resolved, key, dep)?.clone(), optional))
+            })
+            .collect();
+        let node = LockNode::find_or_insert(&mut packages, name.clone(), version.clone());
+        node.dependencies
+            .extend(edges.iter().map(|(target, _)| target.clone()));
+        for (target, optional) in edges {
+            if optional {
+                LockNode::find_or_insert(&mut packages, target.0, target.1).optional = true;
             }
         }
     }
 
-    dependencies
+    let mut direct: Vec<((String, String), bool)> = Vec::new();
+    for workspace in lock.workspaces.values() {
+        // ワークスペース固有のバージョンは "ワークスペース名/依存名" に置かれる
+        let scope = workspace.name.as_deref().unwrap_or("");
+        for (dev, optional, deps) in [
+            (false, false, &workspace.dependencies),
+            (false, true, &workspace.optional_dependencies),
+            (true, false, &workspace.dev_dependencies),
+        ] {
+            for dep in deps.keys() {
+                let Some(target) = bun_resolve(&resolved, scope, dep) else {
+                    continue;
+                };
+                if optional {
+                    LockNode::find_or_insert(&mut packages, target.0.clone(), target.1.clone())
+                        .optional = true;
+                }
+                direct.push((target.clone(), dev));
+            }
+        }
+    }
+
+    Ok(ScanDependencies {
+        ecosystem: "npm".to_string(),
+        source_file,
+        dependencies: graph_dependencies("npm", &packages, &direct),
+        warnings,
+    })
+}
+
+/// bun.lockb（バイナリ形式）しかない場合は読めないので、テキスト形式の生成を促す警告を返す
+pub fn binary_bun_lock(dir: &Path) -> Option<ScanDependencies> {
+    let lockb = dir.join("bun.lockb");
+    if !lockb.exists() || dir.join("bun.lock").exists() {
+        return None;
+    }
+    let source_file = lockb.to_string_lossy().to_string();
+    Some(ScanDependencies {
+        ecosystem: "npm".to_string(),
+        warnings: vec![format!(
+            "{}: binary lockfile is not supported; run `bun install --save-text-lockfile` to scan its dependencies",
+            source_file
+        )],
+        source_file,
+        dependencies: Vec::new(),
+    })
+}
+
+/// packages のキー from から依存名 dep を node_modules と同じ規則で解決する
+/// ("a/b" の依存 c は "a/b/c" -> "a/c" -> "c" の順に探す)
+fn bun_resolve<'a>(
+    resolved: &'a HashMap<&str, (String, String)>,
+    from: &str,
+    dep: &str,
+) -> Option<&'a (String, String)> {
+    let mut scope: Vec<String> = Vec::new();
+    let mut segments = from.split('/').filter(|s| !s.is_empty());
+    while let Some(segment) = segments.next() {
+        // スコープ付きの名前 ("@types/node") は 2 つのセグメントで 1 つのパッケージ
+        if segment.starts_with('@') {
+            if let Some(name) = segments.next() {
+                scope.push(format!("{}/{}", segment, name));
+                continue;
+            }
+        }
+        scope.push(segment.to_string());
+    }
+    (0..=scope.len()).rev().find_map(|depth| {
+        let mut key = scope[..depth].join("/");
+        if !key.is_empty() {
+            key.push('/');
+        }
+        key.push_str(dep);
+        resolved.get(key.as_str())
+    })
+}
+
+/// 末尾のカンマ（"}" や "]" の直前）を取り除く。文字列の中はそのまま残す
+fn strip_trailing_commas(content: &str) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    let mut escaped = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            output.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                output.push(c);
+            }
+            ',' => {
+                let rest = chars.clone().find(|c| !c.is_whitespace());
+                if !matches!(rest, Some('}') | Some(']')) {
+                    output.push(c);
+                }
+            }
+            _ => output.push(c),
+        }
+    }
+    output
 }
 
 /// yarn classic パッケージ名を抽出
@@ -390,39 +769,163 @@ fn extract_yarn_package_name(spec: &str) -> Option<String> {
     None
 }
 
-/// yarn berry パッケージ名を抽出
-/// "lodash@npm:^4.17.21" -> "lodash"
-/// "@types/node@npm:^18.0.0" -> "@types/node"
-fn extract_yarn_berry_package_name(spec: &str) -> Option<String> {
-    let spec = spec.trim_matches('"').trim_matches('\'');
-    
-    // Berry format: "package@npm:version" or "@scope/package@npm:version"
-    // First, try to find @npm: or @workspace: etc.
-    
-    if spec.starts_with('@') {
-        // Scoped package: @scope/name@npm:version
-        // Find @npm: or similar after the scope
-        if let Some(protocol_pos) = spec[1..].find("@npm:") {
-            return Some(spec[..protocol_pos + 1].to_string());
-        }
-        if let Some(protocol_pos) = spec[1..].find("@workspace:") {
-            return Some(spec[..protocol_pos + 1].to_string());
-        }
-        // Fallback: find second @
-        if let Some(at_pos) = spec[1..].find('@') {
-            return Some(spec[..at_pos + 1].to_string());
+// --- YAML Types ---
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PnpmLock {
+    #[serde(default)]
+    lockfile_version: serde_json::Value,
+    /// ワークスペースの各プロジェクト（v5.3 以降。"." がルート）
+    #[serde(default)]
+    importers: BTreeMap<String, PnpmImporter>,
+    /// 単一プロジェクトの v5 / v6 はトップレベルに依存が書かれる
+    #[serde(flatten)]
+    root: PnpmImporter,
+    #[serde(default)]
+    packages: BTreeMap<String, PnpmPackage>,
+    /// v9: ピア依存ごとの依存関係
+    #[serde(default)]
+    snapshots: BTreeMap<String, PnpmPackage>,
+}
+
+impl PnpmLock {
+    /// lockfileVersion の整数部（'6.0' のように文字列のこともある）
+    fn major_version(&self) -> u32 {
+        let version = match &self.lockfile_version {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(n) => n.to_string(),
+            _ => String::new(),
+        };
+        version
+            .split('.')
+            .next()
+            .and_then(|major| major.trim().parse().ok())
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PnpmImporter {
+    #[serde(default)]
+    dependencies: BTreeMap<String, PnpmImporterDependency>,
+    #[serde(default)]
+    dev_dependencies: BTreeMap<String, PnpmImporterDependency>,
+    #[serde(default)]
+    optional_dependencies: BTreeMap<String, PnpmImporterDependency>,
+}
+
+/// v5 はバージョン文字列、v6 以降は { specifier, version }
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PnpmImporterDependency {
+    Version(String),
+    Spec { version: String },
+}
+
+impl PnpmImporterDependency {
+    fn version(&self) -> &str {
+        match self {
+            Self::Version(version) | Self::Spec { version } => version,
         }
-    } else {
-        // Non-scoped: name@npm:version
-        if let Some(at_pos) = spec.find('@') {
-            return Some(spec[..at_pos].to_string());
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PnpmPackage {
+    /// tarball / git の依存はキーから名前が分からないので name / version を持つ
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    dev: Option<bool>,
+    #[serde(default)]
+    optional: bool,
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    optional_dependencies: BTreeMap<String, String>,
+}
+
+impl PnpmPackage {
+    fn resolve(&self, key: &str, v5: bool) -> Option<(String, String)> {
+        match (&self.name, &self.version) {
+            (Some(name), Some(version)) => Some((name.clone(), version.clone())),
+            _ => parse_pnpm_key(key, v5),
         }
     }
-    None
+
+    fn edges(&self, v5: bool) -> Vec<(String, String)> {
+        self.dependencies
+            .iter()
+            .chain(&self.optional_dependencies)
+            .filter_map(|(name, reference)| pnpm_reference(name, reference, v5))
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct YarnBerryEntry {
+    #[serde(default)]
+    resolution: String,
+    /// 依存名 -> 範囲（"npm:^1.0.0" や "^1.0.0"、数値として読まれることもある）
+    #[serde(default)]
+    dependencies: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    dependencies_meta: BTreeMap<String, YarnDependencyMeta>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct YarnDependencyMeta {
+    #[serde(default)]
+    optional: bool,
 }
 
 // --- JSON Types ---
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageJson {
+    #[serde(default)]
+    dev_dependencies: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BunLock {
+    #[serde(default)]
+    workspaces: BTreeMap<String, BunWorkspace>,
+    /// キー -> ["name@version", レジストリ, { dependencies, ... }, integrity]
+    /// キーは node_modules の配置に対応し、バージョンが衝突したものは "parent/name" になる
+    #[serde(default)]
+    packages: BTreeMap<String, Vec<serde_json::Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BunWorkspace {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    dev_dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    optional_dependencies: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BunPackageMeta {
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    optional_dependencies: BTreeMap<String, String>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct PackageLockJson {
@@ -436,19 +939,37 @@ struct PackageLockJson {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct PackageLockPackage {
     version: Option<String>,
     #[serde(default)]
     link: Option<bool>,
     #[serde(default)]
-    #[allow(dead_code)]
     dev: Option<bool>,
+    #[serde(default)]
+    dev_optional: Option<bool>,
+    #[serde(default)]
+    optional: Option<bool>,
+    #[serde(default)]
+    dependencies: HashMap<String, String>,
+    #[serde(default)]
+    dev_dependencies: HashMap<String, String>,
+    #[serde(default)]
+    optional_dependencies: HashMap<String, String>,
+    #[serde(default)]
+    peer_dependencies: HashMap<String, String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct PackageLockDependency {
     version: Option<String>,
     #[serde(default)]
+    dev: Option<bool>,
+    #[serde(default)]
+    optional: Option<bool>,
+    #[serde(default)]
+    requires: HashMap<String, String>,
+    #[serde(default)]
     dependencies: Option<HashMap<String, PackageLockDependency>>,
 }
 
@@ -496,15 +1017,16 @@ mod tests {
     }
 
     #[test]
-    fn test_extract_yarn_berry_package_name() {
+    fn test_split_yarn_descriptor() {
         assert_eq!(
-            extract_yarn_berry_package_name("lodash@npm:^4.17.21"),
-            Some("lodash".to_string())
+            split_yarn_descriptor("lodash@npm:^4.17.21"),
+            Some(("lodash", "npm:^4.17.21"))
         );
         assert_eq!(
-            extract_yarn_berry_package_name("@types/node@npm:^18.0.0"),
-            Some("@types/node".to_string())
+            split_yarn_descriptor("\"@types/node@npm:^18.0.0\""),
+            Some(("@types/node", "npm:^18.0.0"))
         );
+        assert_eq!(split_yarn_descriptor("lodash"), None);
     }
 
     #[test]
@@ -560,4 +1082,542 @@ lodash@^4.17.21:
         let lodash = result.dependencies.iter().find(|d| d.name == "lodash").unwrap();
         assert_eq!(lodash.version, "4.17.21");
     }
+
+    #[test]
+    fn test_parse_yarn_berry_protocols() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"name": "app", "devDependencies": {"typescript": "^5.3.0"}}"#,
+        )
+        .unwrap();
+        let lock = dir.path().join("yarn.lock");
+        fs::write(
+            &lock,
+            r#"# This file is generated by running "yarn install" inside your project.
+
+__metadata:
+  version: 8
+  cacheKey: 10c0
+
+"@acme/ui@workspace:^, @acme/ui@workspace:packages/ui":
+  version: 0.0.0-use.local
+  resolution: "@acme/ui@workspace:packages/ui"
+  dependencies:
+    react: "npm:^18.2.0"
+  languageName: unknown
+  linkType: soft
+
+"app@workspace:.":
+  version: 0.0.0-use.local
+  resolution: "app@workspace:."
+  dependencies:
+    "@acme/ui": "workspace:^"
+    fsevents: "npm:^2.3.2"
+    local-tool: "portal:../tool"
+    resolve: "npm:^1.22.1"
+    string-width-cjs: "npm:string-width@^4.2.0"
+    typescript: "npm:^5.3.0"
+  dependenciesMeta:
+    fsevents:
+      optional: true
+  languageName: unknown
+  linkType: soft
+
+"fsevents@npm:^2.3.2":
+  version: 2.3.3
+  resolution: "fsevents@npm:2.3.3"
+  languageName: node
+  linkType: hard
+
+"is-core-module@npm:^2.13.0":
+  version: 2.13.1
+  resolution: "is-core-module@npm:2.13.1"
+  languageName: node
+  linkType: hard
+
+"js-tokens@npm:^3.0.0 || ^4.0.0":
+  version: 4.0.0
+  resolution: "js-tokens@npm:4.0.0"
+  languageName: node
+  linkType: hard
+
+"local-tool@portal:../tool::locator=app%40workspace%3A.":
+  version: 0.0.0-use.local
+  resolution: "local-tool@portal:../tool::locator=app%40workspace%3A."
+  languageName: node
+  linkType: soft
+
+"loose-envify@npm:^1.1.0":
+  version: 1.4.0
+  resolution: "loose-envify@npm:1.4.0"
+  dependencies:
+    js-tokens: "npm:^3.0.0 || ^4.0.0"
+  languageName: node
+  linkType: hard
+
+"react@npm:^18.2.0":
+  version: 18.2.0
+  resolution: "react@npm:18.2.0"
+  dependencies:
+    loose-envify: "npm:^1.1.0"
+  languageName: node
+  linkType: hard
+
+"resolve@patch:resolve@npm%3A^1.22.1#optional!builtin<compat/resolve>":
+  version: 1.22.8
+  resolution: "resolve@patch:resolve@npm%3A1.22.8#optional!builtin<compat/resolve>::version=1.22.8&hash=c3c19d"
+  dependencies:
+    is-core-module: "npm:^2.13.0"
+  languageName: node
+  linkType: hard
+
+"resolve@npm:^1.22.1":
+  version: 1.22.8
+  resolution: "resolve@npm:1.22.8"
+  dependencies:
+    is-core-module: "npm:^2.13.0"
+  languageName: node
+  linkType: hard
+
+"string-width-cjs@npm:string-width@^4.2.0":
+  version: 4.2.3
+  resolution: "string-width@npm:4.2.3"
+  languageName: node
+  linkType: hard
+
+"typescript@npm:^5.3.0":
+  version: 5.3.3
+  resolution: "typescript@npm:5.3.3"
+  languageName: node
+  linkType: hard
+"#,
+        )
+        .unwrap();
+
+        let result = parse_yarn_lock(&lock).unwrap();
+        let deps = &result.dependencies;
+        let find = |name: &str| deps.iter().find(|d| d.name == name).unwrap();
+        // ワークスペースとポータルは含めず、パッチは元のパッケージにまとめる
+        assert_eq!(deps.len(), 8);
+        assert!(deps.iter().all(|d| d.name != "app" && d.name != "@acme/ui"));
+        assert!(deps.iter().all(|d| d.name != "local-tool"));
+        assert_eq!(deps.iter().filter(|d| d.name == "resolve").count(), 1);
+        assert_eq!(find("resolve").version, "1.22.8");
+        assert_eq!(find("string-width").version, "4.2.3");
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("skipped portal dependency local-tool@portal:../tool"));
+
+        assert_eq!(find("react").relation, DependencyRelation::Direct);
+        assert_eq!(find("js-tokens").relation, DependencyRelation::Transitive);
+        assert_eq!(
+            crate::scanner::dependency_path(deps, find("js-tokens")),
+            vec!["react@18.2.0", "loose-envify@1.4.0", "js-tokens@4.0.0"]
+        );
+        assert!(find("typescript").dev);
+        assert!(!find("js-tokens").dev);
+        assert!(find("fsevents").optional);
+        assert!(!find("react").optional);
+    }
+
+    #[test]
+    fn test_parse_bun_lock() {
+        let content = r#"{
+  "lockfileVersion": 1,
+  "workspaces": {
+    "": {
+      "name": "app",
+      "dependencies": {
+        "@acme/ui": "workspace:*",
+        "react": "^18.2.0",
+      },
+      "devDependencies": {
+        "typescript": "^5.3.0",
+      },
+    },
+    "packages/ui": {
+      "name": "@acme/ui",
+      "dependencies": {
+        "semver": "^6.3.1",
+      },
+    },
+  },
+  "packages": {
+    "@acme/ui": ["@acme/ui@workspace:packages/ui"],
+    "@acme/ui/semver": ["semver@6.3.1", "", { "bin": { "semver": "bin/semver.js" } }, "sha512-a"],
+    "js-tokens": ["js-tokens@4.0.0", "", {}, "sha512-b"],
+    "loose-envify": ["loose-envify@1.4.0", "", { "dependencies": { "js-tokens": "^3.0.0 || ^4.0.0" } }, "sha512-c"],
+    "react": ["react@18.2.0", "", { "dependencies": { "loose-envify": "^1.1.0", "semver": "^7.5.0" } }, "sha512-d"],
+    "react/semver": ["semver@7.5.4", "", {}, "sha512-e"],
+    "typescript": ["typescript@5.3.3", "", { "bin": { "tsc": "bin/tsc" } }, "sha512-f"],
+    "widget": ["widget@github:acme/widget#1a2b3c", {}, "acme-widget-1a2b3c"],
+  }
+}
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let result = parse_bun_lock(file.path()).unwrap();
+        let deps = &result.dependencies;
+        let find = |name: &str, version: &str| {
+            deps.iter()
+                .find(|d| d.name == name && d.version == version)
+                .unwrap()
+        };
+        assert_eq!(deps.len(), 6);
+        assert!(deps
+            .iter()
+            .all(|d| d.name != "@acme/ui" && d.name != "widget"));
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("skipped github dependency widget@github:acme/widget"));
+
+        // 入れ子のキーは親から優先して解決する
+        assert_eq!(find("semver", "7.5.4").parents, vec!["react"]);
+        assert_eq!(find("semver", "6.3.1").relation, DependencyRelation::Direct);
+        assert_eq!(find("react", "18.2.0").relation, DependencyRelation::Direct);
+        assert_eq!(
+            crate::scanner::dependency_path(deps, find("js-tokens", "4.0.0")),
+            vec!["react@18.2.0", "loose-envify@1.4.0", "js-tokens@4.0.0"]
+        );
+        assert!(find("typescript", "5.3.3").dev);
+        assert!(!find("semver", "7.5.4").dev);
+    }
+
+    #[test]
+    fn test_strip_trailing_commas() {
+        assert_eq!(
+            strip_trailing_commas(r#"{"a": [1, 2,], "b": "x,}", }"#),
+            r#"{"a": [1, 2], "b": "x,}" }"#
+        );
+    }
+
+    #[test]
+    fn test_parse_package_lock_graph() {
+        let content = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": {
+      "dependencies": {"express": "^4.18.0"},
+      "devDependencies": {"jest": "^29.0.0"}
+    },
+    "node_modules/express": {
+      "version": "4.18.2",
+      "dependencies": {"qs": "6.11.0", "debug": "2.6.9"}
+    },
+    "node_modules/qs": {"version": "6.11.0"},
+    "node_modules/debug": {"version": "4.3.4", "dev": true},
+    "node_modules/express/node_modules/debug": {"version": "2.6.9"},
+    "node_modules/jest": {
+      "version": "29.7.0",
+      "dev": true,
+      "dependencies": {"debug": "^4.3.4"}
+    }
+  }
+}"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let result = parse_package_lock(file.path()).unwrap();
+        let find = |name: &str, version: &str| {
+            result
+                .dependencies
+                .iter()
+                .find(|d| d.name == name && d.version == version)
+                .unwrap()
+        };
+
+        let express = find("express", "4.18.2");
+        assert_eq!(express.relation, DependencyRelation::Direct);
+        assert!(!express.dev);
+        assert_eq!(find("jest", "29.7.0").relation, DependencyRelation::Direct);
+        assert!(find("jest", "29.7.0").dev);
+
+        // ネストされた node_modules から解決する
+        let nested = find("debug", "2.6.9");
+        assert_eq!(nested.relation, DependencyRelation::Transitive);
+        assert_eq!(nested.parents, vec!["express"]);
+        assert!(!nested.dev);
+        let hoisted = find("debug", "4.3.4");
+        assert_eq!(hoisted.parents, vec!["jest"]);
+        assert!(hoisted.dev);
+
+        assert_eq!(
+            crate::scanner::dependency_path(&result.dependencies, find("qs", "6.11.0")),
+            vec!["express@4.18.2", "qs@6.11.0"]
+        );
+    }
+
+    #[test]
+    fn test_parse_package_lock_v1_graph() {
+        let content = r#"{
+  "lockfileVersion": 1,
+  "requires": true,
+  "dependencies": {
+    "express": {"version": "4.18.2", "requires": {"qs": "6.11.0"}},
+    "qs": {"version": "6.11.0"},
+    "mocha": {"version": "10.0.0", "dev": true}
+  }
+}"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let result = parse_package_lock(file.path()).unwrap();
+        let find = |name: &str| result.dependencies.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(find("express").relation, DependencyRelation::Direct);
+        assert_eq!(find("qs").relation, DependencyRelation::Transitive);
+        assert_eq!(find("qs").parents, vec!["express"]);
+        assert!(find("mocha").dev);
+    }
+
+    #[test]
+    fn test_parse_pnpm_lock_graph() {
+        let content = r#"lockfileVersion: '6.0'
+
+dependencies:
+  express:
+    specifier: ^4.18.0
+    version: 4.18.2
+
+devDependencies:
+  jest:
+    specifier: ^29.0.0
+    version: 29.7.0
+
+packages:
+
+  /express@4.18.2:
+    resolution: {integrity: sha512-abc}
+    dependencies:
+      qs: 6.11.0
+    dev: false
+
+  /jest@29.7.0:
+    resolution: {integrity: sha512-def}
+    dev: true
+
+  /qs@6.11.0:
+    resolution: {integrity: sha512-ghi}
+    dev: false
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let result = parse_pnpm_lock(file.path()).unwrap();
+        assert_eq!(result.dependencies.len(), 3);
+        let find = |name: &str| result.dependencies.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(find("express").relation, DependencyRelation::Direct);
+        assert!(find("jest").dev);
+        assert_eq!(find("qs").relation, DependencyRelation::Transitive);
+        assert_eq!(find("qs").parents, vec!["express"]);
+        assert!(!find("qs").dev);
+    }
+
+    fn parse_pnpm(content: &str) -> Vec<Dependency> {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        parse_pnpm_lock(file.path()).unwrap().dependencies
+    }
+
+    #[test]
+    fn test_parse_pnpm_lock_v5() {
+        let deps = parse_pnpm(
+            r#"lockfileVersion: 5.4
+
+specifiers:
+  react-dom: ^18.2.0
+  '@types/react': ^18.0.0
+
+dependencies:
+  react-dom: 18.2.0_react@18.2.0
+
+devDependencies:
+  '@types/react': 18.0.28
+
+packages:
+
+  /@types/react/18.0.28:
+    resolution: {integrity: sha512-a}
+    dev: true
+
+  /loose-envify/1.4.0:
+    resolution: {integrity: sha512-b}
+    dev: false
+
+  /react-dom/18.2.0_react@18.2.0:
+    resolution: {integrity: sha512-c}
+    peerDependencies:
+      react: ^18.2.0
+    dependencies:
+      loose-envify: 1.4.0
+      react: 18.2.0
+    dev: false
+
+  /react/18.2.0:
+    resolution: {integrity: sha512-d}
+    dependencies:
+      loose-envify: 1.4.0
+    dev: false
+"#,
+        );
+        let names: Vec<(&str, &str)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("@types/react", "18.0.28"),
+                ("loose-envify", "1.4.0"),
+                ("react", "18.2.0"),
+                ("react-dom", "18.2.0"),
+            ]
+        );
+        assert!(deps[0].dev);
+        assert_eq!(deps[0].relation, DependencyRelation::Direct);
+        assert_eq!(deps[1].parents, vec!["react", "react-dom"]);
+        assert_eq!(deps[2].relation, DependencyRelation::Transitive);
+        assert_eq!(deps[3].relation, DependencyRelation::Direct);
+    }
+
+    #[test]
+    fn test_parse_pnpm_lock_v6_peer_suffix() {
+        let deps = parse_pnpm(
+            r#"lockfileVersion: '6.0'
+
+dependencies:
+  '@tanstack/react-query':
+    specifier: ^5.0.0
+    version: 5.8.4(react@18.2.0)
+  react:
+    specifier: ^18.2.0
+    version: 18.2.0
+
+packages:
+
+  /@tanstack/query-core@5.8.3:
+    resolution: {integrity: sha512-a}
+    dev: false
+
+  /@tanstack/react-query@5.8.4(react@18.2.0):
+    resolution: {integrity: sha512-b}
+    peerDependencies:
+      react: ^18.0.0
+    dependencies:
+      '@tanstack/query-core': 5.8.3
+      react: 18.2.0
+    dev: false
+
+  /react@18.2.0:
+    resolution: {integrity: sha512-c}
+    dev: false
+
+  github.com/user/tool/abc123:
+    resolution: {tarball: https://codeload.github.com/user/tool/tar.gz/abc123}
+    name: tool
+    version: 0.3.0
+    dev: false
+"#,
+        );
+        let find = |name: &str| deps.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(deps.len(), 4);
+        assert_eq!(find("@tanstack/react-query").version, "5.8.4");
+        assert_eq!(
+            find("@tanstack/react-query").relation,
+            DependencyRelation::Direct
+        );
+        assert_eq!(
+            find("@tanstack/query-core").parents,
+            vec!["@tanstack/react-query"]
+        );
+        assert_eq!(find("react").parents, vec!["@tanstack/react-query"]);
+        assert_eq!(find("tool").version, "0.3.0");
+    }
+
+    #[test]
+    fn test_parse_pnpm_lock_v9_workspace() {
+        let deps = parse_pnpm(
+            r#"lockfileVersion: '9.0'
+
+settings:
+  autoInstallPeers: true
+  excludeLinksFromLockfile: false
+
+importers:
+
+  .:
+    devDependencies:
+      vitest:
+        specifier: ^1.0.0
+        version: 1.0.4
+
+  packages/web:
+    dependencies:
+      '@acme/ui':
+        specifier: workspace:*
+        version: link:../ui
+      next:
+        specifier: 14.0.3
+        version: 14.0.3(react@18.2.0)
+
+  packages/ui:
+    dependencies:
+      react:
+        specifier: ^18.2.0
+        version: 18.2.0
+
+packages:
+
+  next@14.0.3:
+    resolution: {integrity: sha512-a}
+    peerDependencies:
+      react: ^18.2.0
+
+  postcss@8.4.31:
+    resolution: {integrity: sha512-b}
+
+  react@18.2.0:
+    resolution: {integrity: sha512-c}
+
+  tinypool@0.8.1:
+    resolution: {integrity: sha512-d}
+
+  vitest@1.0.4:
+    resolution: {integrity: sha512-e}
+
+snapshots:
+
+  next@14.0.3(react@18.2.0):
+    dependencies:
+      postcss: 8.4.31
+      react: 18.2.0
+
+  postcss@8.4.31: {}
+
+  react@18.2.0: {}
+
+  tinypool@0.8.1: {}
+
+  vitest@1.0.4:
+    dependencies:
+      tinypool: 0.8.1
+"#,
+        );
+        let find = |name: &str| deps.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(deps.len(), 5);
+        // ワークスペース内のリンクは依存関係に含めない
+        assert!(deps.iter().all(|d| d.name != "@acme/ui"));
+        assert_eq!(find("next").version, "14.0.3");
+        assert_eq!(find("next").relation, DependencyRelation::Direct);
+        assert_eq!(find("react").relation, DependencyRelation::Direct);
+        assert_eq!(find("postcss").relation, DependencyRelation::Transitive);
+        assert_eq!(find("postcss").parents, vec!["next"]);
+        assert!(!find("postcss").dev);
+        // 開発用の直接依存からしか辿れない
+        assert!(find("vitest").dev);
+        assert!(find("tinypool").dev);
+        assert_eq!(
+            crate::scanner::dependency_path(&deps, find("postcss")),
+            vec!["next@14.0.3", "postcss@8.4.31"]
+        );
+    }
 }
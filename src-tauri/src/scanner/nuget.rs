@@ -27,6 +27,7 @@ pub fn parse_packages_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
                     name: name.clone(),
                     version: version.clone(),
                     ecosystem: "NuGet".to_string(),
+                    ..Default::default()
                 });
             }
         }
@@ -40,6 +41,7 @@ pub fn parse_packages_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
         ecosystem: "NuGet".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
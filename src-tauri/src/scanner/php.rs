@@ -1,7 +1,8 @@
 //! Packagist (Composer) 依存関係パーサー
 
-use super::{Dependency, ScanDependencies, ScanError};
+use super::{assign_relations, Dependency, ScanDependencies, ScanError};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
@@ -11,30 +12,62 @@ pub fn parse_composer_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
     let lock_file: ComposerLock =
         serde_json::from_str(&content).map_err(|e| ScanError::Parse(e.to_string()))?;
 
-    let mut dependencies = Vec::new();
-
-    // Parse regular packages
-    for package in lock_file.packages {
-        dependencies.push(Dependency {
-            name: package.name,
-            version: normalize_composer_version(&package.version),
-            ecosystem: "Packagist".to_string(),
-        });
-    }
-
-    // Parse dev packages
-    for package in lock_file.packages_dev {
-        dependencies.push(Dependency {
-            name: package.name,
-            version: normalize_composer_version(&package.version),
-            ecosystem: "Packagist".to_string(),
-        });
-    }
+    let dev_names: Vec<&str> = lock_file
+        .packages_dev
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect();
+    let all: Vec<&ComposerPackage> = lock_file
+        .packages
+        .iter()
+        .chain(lock_file.packages_dev.iter())
+        .collect();
+
+    let mut dependencies: Vec<Dependency> = all
+        .iter()
+        .map(|package| {
+            let mut parents: Vec<String> = all
+                .iter()
+                .filter(|p| {
+                    p.require
+                        .keys()
+                        .any(|r| r.eq_ignore_ascii_case(&package.name))
+                })
+                .map(|p| p.name.clone())
+                .collect();
+            parents.sort();
+            Dependency {
+                name: package.name.clone(),
+                version: normalize_composer_version(&package.version),
+                ecosystem: "Packagist".to_string(),
+                dev: dev_names.contains(&package.name.as_str()),
+                parents,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    // 隣の composer.json があれば require / require-dev を直接依存とする
+    let direct = path
+        .parent()
+        .map(|dir| dir.join("composer.json"))
+        .and_then(|manifest| fs::read_to_string(manifest).ok())
+        .and_then(|content| serde_json::from_str::<ComposerJson>(&content).ok())
+        .map(|manifest| {
+            manifest
+                .require
+                .into_keys()
+                .chain(manifest.require_dev.into_keys())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    assign_relations(&mut dependencies, &direct);
 
     Ok(ScanDependencies {
         ecosystem: "Packagist".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
@@ -58,6 +91,16 @@ struct ComposerLock {
 struct ComposerPackage {
     name: String,
     version: String,
+    #[serde(default)]
+    require: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComposerJson {
+    #[serde(default)]
+    require: HashMap<String, String>,
+    #[serde(default, rename = "require-dev")]
+    require_dev: HashMap<String, String>,
 }
 
 #[cfg(test)]
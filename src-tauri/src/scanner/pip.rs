@@ -1,6 +1,12 @@
 //! pip (Python) 依存関係パーサー
 
-use super::{Dependency, ScanDependencies, ScanError};
+use super::purl::normalize_pypi_name;
+use super::{
+    assign_relations, graph_dependencies, Dependency, DependencyRelation, LockNode,
+    ScanDependencies, ScanError,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
@@ -14,37 +20,97 @@ use std::path::Path;
 ///
 /// 包括的な脆弱性スキャンには、`pip freeze` や `poetry.lock`、
 /// `Pipfile.lock` などロックファイル形式の使用を推奨します。
+///
+/// `-r other.txt` で読み込まれるファイルも含め、`==` で固定されていない依存は
+/// バージョンに指定子（">=2.0,<3" など）をそのまま残す
 pub fn parse_requirements(path: &Path) -> Result<ScanDependencies, ScanError> {
-    let content = fs::read_to_string(path)?;
     let mut dependencies = Vec::new();
+    let mut warnings = Vec::new();
+    let mut visited = Vec::new();
+    collect_requirements(path, &mut visited, &mut dependencies, &mut warnings)?;
 
+    Ok(ScanDependencies {
+        ecosystem: "PyPI".to_string(),
+        source_file: path.to_string_lossy().to_string(),
+        dependencies,
+        warnings,
+    })
+}
+
+/// requirements ファイルを読み、`-r` / `--requirement` で指定されたファイルを再帰的にたどる
+fn collect_requirements(
+    path: &Path,
+    visited: &mut Vec<std::path::PathBuf>,
+    dependencies: &mut Vec<Dependency>,
+    warnings: &mut Vec<String>,
+) -> Result<(), ScanError> {
+    let canonical = path.canonicalize()?;
+    if visited.contains(&canonical) {
+        return Ok(());
+    }
+    visited.push(canonical);
+    let content = fs::read_to_string(path)?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+
+    // 行末の "\" は次の行に続く（pip-compile の --hash など）
+    let content = content.replace("\\\r\n", " ").replace("\\\n", " ");
     for line in content.lines() {
-        let trimmed = line.trim();
+        // Remove comments ("#" から行末まで。URL の断片 "#egg=" は空白を挟まない)
+        let trimmed = match line.find(" #") {
+            Some(pos) => &line[..pos],
+            None => line,
+        }
+        .trim();
 
         // Skip comments and empty lines
         if trimmed.is_empty() || trimmed.starts_with('#') {
             continue;
         }
 
-        // Skip options like -r, -e, --index-url, etc.
+        if let Some(include) = requirement_include(trimmed) {
+            let include_path = dir.join(include);
+            if include_path.exists() {
+                collect_requirements(&include_path, visited, dependencies, warnings)?;
+            } else {
+                warnings.push(format!(
+                    "{}: included requirements file not found: {}",
+                    path.display(),
+                    include
+                ));
+            }
+            continue;
+        }
+
+        // Skip options like -e, -c, --index-url, etc.
         if trimmed.starts_with('-') {
             continue;
         }
 
-        // Parse package specification
-        if let Some(dep) = parse_requirement_line(trimmed) {
-            dependencies.push(dep);
+        // "--hash=sha256:..." などの行ごとのオプションを除いてパース
+        let requirement = trimmed.split(" --").next().unwrap_or(trimmed);
+        if let Some(dep) = parse_requirement_line(requirement) {
+            if !dependencies
+                .iter()
+                .any(|d| d.name == dep.name && d.version == dep.version)
+            {
+                dependencies.push(dep);
+            }
         }
     }
+    Ok(())
+}
 
-    Ok(ScanDependencies {
-        ecosystem: "PyPI".to_string(),
-        source_file: path.to_string_lossy().to_string(),
-        dependencies,
-    })
+/// "-r other.txt" / "--requirement=other.txt" の読み込み先
+fn requirement_include(line: &str) -> Option<&str> {
+    let rest = line
+        .strip_prefix("-r")
+        .or_else(|| line.strip_prefix("--requirement"))?;
+    let rest = rest.strip_prefix('=').unwrap_or(rest).trim();
+    (!rest.is_empty()).then_some(rest)
 }
 
 /// 単一の requirements 行をパース
+/// "==" で 1 つのバージョンに固定されていればそのバージョン、それ以外は指定子全体をバージョンとする
 fn parse_requirement_line(line: &str) -> Option<Dependency> {
     // Remove environment markers (;)
     let line = line.split(';').next().unwrap_or(line).trim();
@@ -63,42 +129,37 @@ fn parse_requirement_line(line: &str) -> Option<Dependency> {
 
     // Parse different version specifiers
     // ==, >=, <=, ~=, !=, >, <
-    let version_ops = ["==", ">=", "<=", "~=", "!=", ">", "<"];
-    
-    for op in version_ops {
-        if let Some(pos) = line.find(op) {
-            let name = line[..pos].trim().to_lowercase();
-            let version_part = &line[pos + op.len()..];
-            
-            // Handle version ranges like ">=1.0,<2.0"
-            let version = version_part
-                .split(',')
-                .next()
-                .unwrap_or(version_part)
-                .trim();
-
-            // For ==, use exact version; for others, note it's a constraint
-            if !name.is_empty() && !version.is_empty() {
-                return Some(Dependency {
-                    name,
-                    version: if op == "==" {
-                        version.to_string()
-                    } else {
-                        format!("{}{}", op, version)
-                    },
-                    ecosystem: "PyPI".to_string(),
-                });
-            }
+    if let Some(pos) = line.find(['=', '>', '<', '~', '!']) {
+        let name = normalize_pypi_name(&line[..pos]);
+        let specifier: String = line[pos..].split_whitespace().collect();
+
+        // Handle version ranges like ">=1.0,<2.0" (the whole specifier is kept)
+        let has_version = !specifier
+            .trim_start_matches(['=', '>', '<', '~', '!'])
+            .is_empty();
+        let version = match specifier.strip_prefix("==") {
+            Some(pinned) if !pinned.contains([',', '*']) => pinned.to_string(),
+            _ => specifier,
+        };
+        if !name.is_empty() && !name.contains(' ') && has_version {
+            return Some(Dependency {
+                name,
+                version,
+                ecosystem: "PyPI".to_string(),
+                ..Default::default()
+            });
         }
+        return None;
     }
 
     // No version specifier - package name only
-    let name = line.trim().to_lowercase();
+    let name = normalize_pypi_name(line);
     if !name.is_empty() && !name.contains(' ') {
         return Some(Dependency {
             name,
             version: "*".to_string(), // Any version
             ecosystem: "PyPI".to_string(),
+            ..Default::default()
         });
     }
 
@@ -108,71 +169,84 @@ fn parse_requirement_line(line: &str) -> Option<Dependency> {
 /// poetry.lock をパース
 pub fn parse_poetry_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
     let content = fs::read_to_string(path)?;
-    let mut dependencies = Vec::new();
-
-    // poetry.lock is TOML format
-    // [[package]]
-    // name = "package-name"
-    // version = "1.0.0"
-
-    let mut current_name: Option<String> = None;
-    let mut current_version: Option<String> = None;
-    let mut in_package_section = false;
+    let lock: PoetryLock = toml::from_str(&content).map_err(|e| ScanError::Parse(e.to_string()))?;
 
-    for line in content.lines() {
-        let trimmed = line.trim();
-
-        if trimmed == "[[package]]" {
-            // Save previous package
-            if let (Some(name), Some(version)) = (current_name.take(), current_version.take()) {
-                dependencies.push(Dependency {
-                    name,
-                    version,
-                    ecosystem: "PyPI".to_string(),
-                });
-            }
-            in_package_section = true;
-            continue;
-        }
-
-        // New section
-        if trimmed.starts_with('[') && !trimmed.starts_with("[[package]]") {
-            if let (Some(name), Some(version)) = (current_name.take(), current_version.take()) {
-                dependencies.push(Dependency {
-                    name,
-                    version,
-                    ecosystem: "PyPI".to_string(),
-                });
+    let mut dependencies: Vec<Dependency> = lock
+        .package
+        .iter()
+        .map(|package| {
+            let name = normalize_pypi_name(&package.name);
+            let mut parents: Vec<String> = lock
+                .package
+                .iter()
+                .filter(|p| {
+                    p.dependencies
+                        .keys()
+                        .any(|d| normalize_pypi_name(d) == name)
+                })
+                .map(|p| normalize_pypi_name(&p.name))
+                .collect();
+            parents.sort();
+            parents.dedup();
+            Dependency {
+                name,
+                version: package.version.clone(),
+                ecosystem: "PyPI".to_string(),
+                // Poetry 1.x は category = "dev"、groups のある形式では main 以外のグループのみ
+                dev: package.category.as_deref() == Some("dev")
+                    || (!package.groups.is_empty() && !package.groups.iter().any(|g| g == "main")),
+                optional: package.optional,
+                parents,
+                ..Default::default()
             }
-            in_package_section = false;
-            continue;
-        }
+        })
+        .collect();
 
-        if in_package_section {
-            if let Some(name) = parse_toml_string(trimmed, "name") {
-                current_name = Some(name.to_lowercase());
-            } else if let Some(version) = parse_toml_string(trimmed, "version") {
-                current_version = Some(version);
-            }
-        }
-    }
-
-    // Last package
-    if let (Some(name), Some(version)) = (current_name, current_version) {
-        dependencies.push(Dependency {
-            name,
-            version,
-            ecosystem: "PyPI".to_string(),
-        });
-    }
+    // 隣の pyproject.toml があれば、そこに書かれたものを直接依存とする
+    let direct = path
+        .parent()
+        .map(|dir| dir.join("pyproject.toml"))
+        .and_then(|manifest| fs::read_to_string(manifest).ok())
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .map(|manifest| poetry_direct_dependencies(&manifest))
+        .unwrap_or_default();
+    assign_relations(&mut dependencies, &direct);
 
     Ok(ScanDependencies {
         ecosystem: "PyPI".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
+/// pyproject.toml の [tool.poetry] に書かれた依存（dependencies / dev-dependencies / group.*.dependencies）
+fn poetry_direct_dependencies(manifest: &toml::Table) -> Vec<String> {
+    let Some(poetry) = manifest
+        .get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.as_table())
+    else {
+        return Vec::new();
+    };
+    let groups = poetry
+        .get("group")
+        .and_then(|g| g.as_table())
+        .into_iter()
+        .flat_map(|groups| groups.values())
+        .filter_map(|group| group.get("dependencies"));
+    poetry
+        .get("dependencies")
+        .into_iter()
+        .chain(poetry.get("dev-dependencies"))
+        .chain(groups)
+        .filter_map(|deps| deps.as_table())
+        .flat_map(|deps| deps.keys())
+        .filter(|name| *name != "python")
+        .map(|name| normalize_pypi_name(name))
+        .collect()
+}
+
 /// Pipfile.lock をパース（JSON形式）
 pub fn parse_pipfile_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
     let content = fs::read_to_string(path)?;
@@ -198,9 +272,10 @@ pub fn parse_pipfile_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
             if let Some(version) = info.get("version").and_then(|v| v.as_str()) {
                 let version = version.trim_start_matches("==");
                 dependencies.push(Dependency {
-                    name: name.to_lowercase(),
+                    name: normalize_pypi_name(name),
                     version: version.to_string(),
                     ecosystem: "PyPI".to_string(),
+                    ..Default::default()
                 });
             }
         }
@@ -212,9 +287,11 @@ pub fn parse_pipfile_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
             if let Some(version) = info.get("version").and_then(|v| v.as_str()) {
                 let version = version.trim_start_matches("==");
                 dependencies.push(Dependency {
-                    name: name.to_lowercase(),
+                    name: normalize_pypi_name(name),
                     version: version.to_string(),
                     ecosystem: "PyPI".to_string(),
+                    dev: true,
+                    ..Default::default()
                 });
             }
         }
@@ -224,26 +301,450 @@ pub fn parse_pipfile_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
         ecosystem: "PyPI".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
-/// TOML string 値をパース
-fn parse_toml_string(line: &str, key: &str) -> Option<String> {
-    let pattern = format!("{} = ", key);
-    if line.starts_with(&pattern) {
-        let value_part = &line[pattern.len()..];
-        let value = value_part
-            .trim()
-            .trim_start_matches('"')
-            .trim_end_matches('"');
-        return Some(value.to_string());
+/// uv.lock をパース
+/// source が registry のパッケージを対象とし、プロジェクト自身やワークスペースのメンバー（editable / virtual）の依存を直接依存とする。
+/// ローカルのディレクトリや git / URL のパッケージは OSV で照合できないので警告して除外し、その依存は直接依存として扱う
+pub fn parse_uv_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
+    let content = fs::read_to_string(path)?;
+    let lock: UvLock = toml::from_str(&content).map_err(|e| ScanError::Parse(e.to_string()))?;
+    let source_file = path.to_string_lossy().to_string();
+
+    // 依存先 { name, version? } をレジストリのパッケージに解決する
+    let resolve = |dep: &UvDependency| -> Option<(String, String)> {
+        let name = normalize_pypi_name(&dep.name);
+        let package = lock.package.iter().find(|p| {
+            normalize_pypi_name(&p.name) == name
+                && dep
+                    .version
+                    .as_ref()
+                    .is_none_or(|v| p.version.as_ref() == Some(v))
+        })?;
+        package.source.registry.as_ref()?;
+        Some((name, package.version.clone()?))
+    };
+
+    let mut warnings = Vec::new();
+    let mut packages: Vec<LockNode> = Vec::new();
+    let mut direct: Vec<((String, String), bool)> = Vec::new();
+    let mut optional: Vec<(String, String)> = Vec::new();
+    for package in &lock.package {
+        if let (Some(_), Some(version)) = (&package.source.registry, &package.version) {
+            let node = LockNode::find_or_insert(
+                &mut packages,
+                normalize_pypi_name(&package.name),
+                version.clone(),
+            );
+            node.dependencies.extend(
+                package
+                    .dependencies
+                    .iter()
+                    .chain(package.optional_dependencies.values().flatten())
+                    .filter_map(resolve),
+            );
+            node.checksum = package
+                .sdist
+                .as_ref()
+                .and_then(|s| s.hash.as_deref())
+                .and_then(|hash| hash.strip_prefix("sha256:"))
+                .map(str::to_string);
+            continue;
+        }
+        if package.source.editable.is_none() && package.source.r#virtual.is_none() {
+            warnings.push(format!(
+                "{}: skipped {} dependency {} {}",
+                source_file,
+                package.source.kind(),
+                package.name,
+                package.version.as_deref().unwrap_or_default()
+            ));
+        }
+        direct.extend(
+            package
+                .dependencies
+                .iter()
+                .filter_map(resolve)
+                .map(|t| (t, false)),
+        );
+        for target in package
+            .optional_dependencies
+            .values()
+            .flatten()
+            .filter_map(resolve)
+        {
+            optional.push(target.clone());
+            direct.push((target, false));
+        }
+        for target in package
+            .dev_dependencies
+            .values()
+            .flatten()
+            .filter_map(resolve)
+        {
+            direct.push((target, true));
+        }
     }
-    None
+    let required: Vec<(String, String)> = lock
+        .package
+        .iter()
+        .filter(|p| p.source.registry.is_none())
+        .flat_map(|p| &p.dependencies)
+        .filter_map(resolve)
+        .collect();
+    for target in optional.iter().filter(|t| !required.contains(t)) {
+        LockNode::find_or_insert(&mut packages, target.0.clone(), target.1.clone()).optional = true;
+    }
+
+    Ok(ScanDependencies {
+        ecosystem: "PyPI".to_string(),
+        source_file,
+        dependencies: graph_dependencies("PyPI", &packages, &direct),
+        warnings,
+    })
+}
+
+/// pdm.lock をパース
+/// groups に "default" を含まないパッケージを開発用とし、隣の pyproject.toml に書かれたものを直接依存とする。
+/// ローカルのパスや git / URL のパッケージは OSV で照合できないので警告して除外する
+pub fn parse_pdm_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
+    let content = fs::read_to_string(path)?;
+    let lock: PdmLock = toml::from_str(&content).map_err(|e| ScanError::Parse(e.to_string()))?;
+    let source_file = path.to_string_lossy().to_string();
+
+    let mut warnings = Vec::new();
+    let mut dependencies: Vec<Dependency> = Vec::new();
+    for package in &lock.package {
+        if let Some(kind) = package.local_source() {
+            warnings.push(format!(
+                "{}: skipped {} dependency {} {}",
+                source_file, kind, package.name, package.version
+            ));
+            continue;
+        }
+        let name = normalize_pypi_name(&package.name);
+        let mut parents: Vec<String> = lock
+            .package
+            .iter()
+            .filter(|p| {
+                p.dependencies
+                    .iter()
+                    .any(|d| requirement_name(d).as_deref() == Some(name.as_str()))
+            })
+            .map(|p| normalize_pypi_name(&p.name))
+            .collect();
+        parents.sort();
+        parents.dedup();
+        dependencies.push(Dependency {
+            name,
+            version: package.version.clone(),
+            ecosystem: "PyPI".to_string(),
+            dev: !package.groups.is_empty() && !package.groups.iter().any(|g| g == "default"),
+            checksum: package
+                .files
+                .iter()
+                .find(|f| f.file.ends_with(".tar.gz"))
+                .and_then(|f| f.hash.strip_prefix("sha256:"))
+                .map(str::to_string),
+            parents,
+            ..Default::default()
+        });
+    }
+
+    let direct: Vec<String> = path
+        .parent()
+        .and_then(|dir| read_pyproject(&dir.join("pyproject.toml")))
+        .map(|manifest| {
+            pep621_requirements(&manifest)
+                .iter()
+                .filter_map(|r| requirement_name(&r.spec))
+                .collect()
+        })
+        .unwrap_or_default();
+    assign_relations(&mut dependencies, &direct);
+
+    Ok(ScanDependencies {
+        ecosystem: "PyPI".to_string(),
+        source_file,
+        dependencies,
+        warnings,
+    })
+}
+
+/// ロックファイルのない pyproject.toml から PEP 621 の直接依存をパース
+/// [project] の dependencies / optional-dependencies と、開発用の [dependency-groups]
+/// （および [tool.pdm] / [tool.uv] の dev-dependencies）を対象とする
+pub fn parse_pyproject(path: &Path) -> Result<ScanDependencies, ScanError> {
+    let content = fs::read_to_string(path)?;
+    let manifest: toml::Table =
+        toml::from_str(&content).map_err(|e| ScanError::Parse(e.to_string()))?;
+    let source_file = path.to_string_lossy().to_string();
+
+    let dependencies: Vec<Dependency> = pep621_requirements(&manifest)
+        .into_iter()
+        .filter_map(|requirement| {
+            Some(Dependency {
+                relation: DependencyRelation::Direct,
+                dev: requirement.dev,
+                optional: requirement.optional,
+                ..parse_requirement_line(&requirement.spec)?
+            })
+        })
+        .collect();
+    let warnings = if dependencies.is_empty() {
+        Vec::new()
+    } else {
+        vec![format!(
+            "{}: no lockfile found; only direct dependencies are scanned",
+            source_file
+        )]
+    };
+
+    Ok(ScanDependencies {
+        ecosystem: "PyPI".to_string(),
+        source_file,
+        dependencies,
+        warnings,
+    })
+}
+
+/// ディレクトリ（またはワークスペースのルートとなる親）に Python のロックファイルがあるか
+/// ある場合は pyproject.toml を直接スキャンしない
+pub fn has_lockfile(dir: &Path) -> bool {
+    dir.join("requirements.txt").exists()
+        || dir.ancestors().any(|d| {
+            ["uv.lock", "pdm.lock", "poetry.lock", "Pipfile.lock"]
+                .iter()
+                .any(|lock| d.join(lock).exists())
+        })
+}
+
+/// pyproject.toml に書かれた PEP 508 の依存指定
+struct Requirement {
+    spec: String,
+    dev: bool,
+    optional: bool,
+}
+
+fn read_pyproject(path: &Path) -> Option<toml::Table> {
+    fs::read_to_string(path).ok()?.parse::<toml::Table>().ok()
+}
+
+/// PEP 621 の依存と開発用の依存グループを集める
+fn pep621_requirements(manifest: &toml::Table) -> Vec<Requirement> {
+    let project = manifest.get("project");
+    let tool = manifest.get("tool");
+    let specs = |value: Option<&toml::Value>| -> Vec<String> {
+        value
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            // { include-group = "..." } は他のグループを参照するだけなので除く
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect()
+    };
+    let groups = |value: Option<&toml::Value>| -> Vec<String> {
+        value
+            .and_then(|v| v.as_table())
+            .into_iter()
+            .flat_map(|groups| groups.values())
+            .flat_map(|group| specs(Some(group)))
+            .collect()
+    };
+
+    let mut requirements = Vec::new();
+    let mut push = |specs: Vec<String>, dev: bool, optional: bool| {
+        requirements.extend(specs.into_iter().map(|spec| Requirement {
+            spec,
+            dev,
+            optional,
+        }));
+    };
+    push(
+        specs(project.and_then(|p| p.get("dependencies"))),
+        false,
+        false,
+    );
+    push(
+        groups(project.and_then(|p| p.get("optional-dependencies"))),
+        false,
+        true,
+    );
+    push(groups(manifest.get("dependency-groups")), true, false);
+    push(
+        groups(
+            tool.and_then(|t| t.get("pdm"))
+                .and_then(|p| p.get("dev-dependencies")),
+        ),
+        true,
+        false,
+    );
+    push(
+        specs(
+            tool.and_then(|t| t.get("uv"))
+                .and_then(|u| u.get("dev-dependencies")),
+        ),
+        true,
+        false,
+    );
+    requirements
+}
+
+/// PEP 508 の依存指定からパッケージ名を取り出して正規化する
+/// "Foo_Bar[extra]>=1.0; python_version < '3.11'" -> "foo-bar"
+fn requirement_name(spec: &str) -> Option<String> {
+    let name: String = spec
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    (!name.is_empty()).then(|| normalize_pypi_name(&name))
+}
+
+// --- TOML Types ---
+
+#[derive(Debug, Deserialize)]
+struct PoetryLock {
+    #[serde(default)]
+    package: Vec<PoetryPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetryPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    category: Option<String>,
+    #[serde(default)]
+    groups: Vec<String>,
+    #[serde(default)]
+    optional: bool,
+    #[serde(default)]
+    dependencies: toml::Table,
+}
+
+#[derive(Debug, Deserialize)]
+struct UvLock {
+    #[serde(default)]
+    package: Vec<UvPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct UvPackage {
+    name: String,
+    /// 動的なバージョンのプロジェクトなどでは省略される
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    source: UvSource,
+    #[serde(default)]
+    dependencies: Vec<UvDependency>,
+    #[serde(default)]
+    optional_dependencies: BTreeMap<String, Vec<UvDependency>>,
+    #[serde(default)]
+    dev_dependencies: BTreeMap<String, Vec<UvDependency>>,
+    #[serde(default)]
+    sdist: Option<UvSdist>,
+}
+
+/// source = { registry = "..." } / { editable = "." } / { git = "..." } など
+#[derive(Debug, Default, Deserialize)]
+struct UvSource {
+    #[serde(default)]
+    registry: Option<String>,
+    #[serde(default)]
+    editable: Option<String>,
+    #[serde(default)]
+    r#virtual: Option<String>,
+    #[serde(default)]
+    directory: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    git: Option<String>,
+}
+
+impl UvSource {
+    fn kind(&self) -> &'static str {
+        if self.git.is_some() {
+            "git"
+        } else if self.directory.is_some() || self.path.is_some() {
+            "path"
+        } else {
+            "url"
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UvDependency {
+    name: String,
+    /// 同じ名前の複数バージョンがロックされている場合のみ書かれる
+    #[serde(default)]
+    version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UvSdist {
+    #[serde(default)]
+    hash: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PdmLock {
+    #[serde(default)]
+    package: Vec<PdmPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PdmPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    groups: Vec<String>,
+    /// PEP 508 形式の依存指定
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    git: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    files: Vec<PdmFile>,
+}
+
+impl PdmPackage {
+    /// レジストリ以外から取得するパッケージの種類
+    fn local_source(&self) -> Option<&'static str> {
+        if self.git.is_some() {
+            Some("git")
+        } else if self.path.is_some() {
+            Some("path")
+        } else if self.url.is_some() {
+            Some("url")
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PdmFile {
+    #[serde(default)]
+    file: String,
+    #[serde(default)]
+    hash: String,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scanner::DependencyRelation;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -255,6 +756,7 @@ mod tests {
                 name: "requests".to_string(),
                 version: "2.28.0".to_string(),
                 ecosystem: "PyPI".to_string(),
+                ..Default::default()
             })
         );
 
@@ -262,17 +764,25 @@ mod tests {
             parse_requirement_line("django>=3.0,<4.0"),
             Some(Dependency {
                 name: "django".to_string(),
-                version: ">=3.0".to_string(),
+                version: ">=3.0,<4.0".to_string(),
                 ecosystem: "PyPI".to_string(),
+                ..Default::default()
             })
         );
 
+        // PEP 503 で正規化する
+        assert_eq!(
+            parse_requirement_line("Foo_Bar.baz==1.0").unwrap().name,
+            "foo-bar-baz"
+        );
+
         assert_eq!(
             parse_requirement_line("flask"),
             Some(Dependency {
                 name: "flask".to_string(),
                 version: "*".to_string(),
                 ecosystem: "PyPI".to_string(),
+                ..Default::default()
             })
         );
     }
@@ -294,4 +804,281 @@ flask
         assert_eq!(result.ecosystem, "PyPI");
         assert_eq!(result.dependencies.len(), 3);
     }
+
+    #[test]
+    fn test_parse_requirements_includes_and_hashes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("requirements.txt"),
+            r#"-r base.txt
+--requirement=missing.txt
+-c constraints.txt
+#
+django==4.2.7 \
+    --hash=sha256:8e0f1c2c2786b5c0e39fe1afce24c926040fad47c8ea8ad30aaf1188df29fc41 \
+    --hash=sha256:e1d37c51ad26186de355cbcec16613ebdabfa9689bbade9c538835205a8abbe9
+    # via -r requirements.in
+requests>=2.31,<3  # pinned by renovate
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("base.txt"),
+            "-r requirements.txt\nurllib3==2.0.7 --hash=sha256:aaaa\n",
+        )
+        .unwrap();
+
+        let result = parse_requirements(&dir.path().join("requirements.txt")).unwrap();
+        let find = |name: &str| result.dependencies.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(result.dependencies.len(), 3);
+        assert_eq!(find("urllib3").version, "2.0.7");
+        assert_eq!(find("django").version, "4.2.7");
+        assert_eq!(find("requests").version, ">=2.31,<3");
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].ends_with("included requirements file not found: missing.txt"));
+    }
+
+    #[test]
+    fn test_parse_poetry_lock_graph() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("poetry.lock"),
+            r#"[[package]]
+name = "Requests"
+version = "2.31.0"
+category = "main"
+
+[package.dependencies]
+urllib3 = ">=1.21.1,<3"
+
+[[package]]
+name = "urllib3"
+version = "2.0.7"
+category = "main"
+
+[[package]]
+name = "pytest"
+version = "7.4.0"
+category = "dev"
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            r#"[tool.poetry.dependencies]
+python = "^3.11"
+requests = "^2.31"
+
+[tool.poetry.group.test.dependencies]
+pytest = "^7.4"
+"#,
+        )
+        .unwrap();
+
+        let result = parse_poetry_lock(&dir.path().join("poetry.lock")).unwrap();
+        assert_eq!(result.dependencies.len(), 3);
+        let find = |name: &str| result.dependencies.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(find("requests").relation, DependencyRelation::Direct);
+        assert_eq!(find("urllib3").relation, DependencyRelation::Transitive);
+        assert_eq!(find("urllib3").parents, vec!["requests"]);
+        assert!(find("pytest").dev);
+        assert_eq!(find("pytest").relation, DependencyRelation::Direct);
+    }
+
+    #[test]
+    fn test_parse_uv_lock() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(
+            br#"version = 1
+requires-python = ">=3.12"
+
+[[package]]
+name = "anyio"
+version = "4.4.0"
+source = { registry = "https://pypi.org/simple" }
+dependencies = [
+    { name = "idna" },
+]
+sdist = { url = "https://files.pythonhosted.org/anyio-4.4.0.tar.gz", hash = "sha256:5aadc6a1", size = 163930 }
+
+[[package]]
+name = "idna"
+version = "3.7"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "internal-sdk"
+version = "0.3.0"
+source = { git = "https://github.com/acme/internal-sdk?rev=v0.3.0#0c1d2e3f" }
+
+[[package]]
+name = "myapp"
+version = "0.1.0"
+source = { editable = "." }
+dependencies = [
+    { name = "anyio" },
+    { name = "internal-sdk" },
+    { name = "typing-extensions" },
+]
+
+[package.optional-dependencies]
+cli = [
+    { name = "rich" },
+]
+
+[package.dev-dependencies]
+dev = [
+    { name = "pytest" },
+]
+
+[[package]]
+name = "pytest"
+version = "8.2.2"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "rich"
+version = "13.7.1"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "typing_extensions"
+version = "4.12.2"
+source = { registry = "https://pypi.org/simple" }
+"#,
+        )
+        .unwrap();
+
+        let result = parse_uv_lock(file.path()).unwrap();
+        let deps = &result.dependencies;
+        let find = |name: &str| deps.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(deps.len(), 5);
+        assert!(deps
+            .iter()
+            .all(|d| d.name != "myapp" && d.name != "internal-sdk"));
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].ends_with("skipped git dependency internal-sdk 0.3.0"));
+
+        assert_eq!(find("typing-extensions").version, "4.12.2");
+        assert_eq!(
+            find("typing-extensions").relation,
+            DependencyRelation::Direct
+        );
+        assert_eq!(find("idna").relation, DependencyRelation::Transitive);
+        assert_eq!(find("idna").parents, vec!["anyio"]);
+        assert_eq!(find("anyio").checksum.as_deref(), Some("5aadc6a1"));
+        assert!(find("pytest").dev);
+        assert!(!find("idna").dev);
+        assert!(find("rich").optional);
+        assert!(!find("anyio").optional);
+    }
+
+    #[test]
+    fn test_parse_pdm_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("pdm.lock"),
+            r#"[metadata]
+groups = ["default", "test"]
+strategy = ["cross_platform", "inherit_metadata"]
+lock_version = "4.4.1"
+
+[[package]]
+name = "Flask"
+version = "3.0.3"
+groups = ["default"]
+dependencies = [
+    "Werkzeug>=3.0.0",
+    "importlib-metadata>=3.6.0; python_version < \"3.10\"",
+]
+files = [
+    {file = "flask-3.0.3-py3-none-any.whl", hash = "sha256:aaaa"},
+    {file = "flask-3.0.3.tar.gz", hash = "sha256:bbbb"},
+]
+
+[[package]]
+name = "werkzeug"
+version = "3.0.3"
+groups = ["default"]
+
+[[package]]
+name = "pytest"
+version = "8.2.2"
+groups = ["test"]
+
+[[package]]
+name = "shared-utils"
+version = "0.1.0"
+path = "../shared"
+groups = ["default"]
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            r#"[project]
+name = "web"
+dependencies = ["flask>=3.0", "shared-utils @ file:///${PROJECT_ROOT}/../shared"]
+
+[tool.pdm.dev-dependencies]
+test = ["pytest>=8"]
+"#,
+        )
+        .unwrap();
+
+        let result = parse_pdm_lock(&dir.path().join("pdm.lock")).unwrap();
+        let deps = &result.dependencies;
+        let find = |name: &str| deps.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(deps.len(), 3);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].ends_with("skipped path dependency shared-utils 0.1.0"));
+        assert_eq!(find("flask").relation, DependencyRelation::Direct);
+        assert_eq!(find("flask").checksum.as_deref(), Some("bbbb"));
+        assert_eq!(find("werkzeug").relation, DependencyRelation::Transitive);
+        assert_eq!(find("werkzeug").parents, vec!["flask"]);
+        assert!(find("pytest").dev);
+        assert_eq!(find("pytest").relation, DependencyRelation::Direct);
+    }
+
+    #[test]
+    fn test_parse_pyproject_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("pyproject.toml");
+        fs::write(
+            &manifest,
+            r#"[project]
+name = "svc"
+dependencies = [
+    "Django_Rest_Framework==3.15.1",
+    "requests[socks]>=2.31; python_version >= '3.8'",
+]
+
+[project.optional-dependencies]
+postgres = ["psycopg==3.1.19"]
+
+[dependency-groups]
+dev = ["pytest==8.2.2", { include-group = "lint" }]
+lint = ["ruff==0.4.8"]
+"#,
+        )
+        .unwrap();
+
+        assert!(!has_lockfile(dir.path()));
+        let result = parse_pyproject(&manifest).unwrap();
+        let deps = &result.dependencies;
+        let find = |name: &str| deps.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(deps.len(), 5);
+        assert!(deps
+            .iter()
+            .all(|d| d.relation == DependencyRelation::Direct));
+        assert_eq!(find("django-rest-framework").version, "3.15.1");
+        assert_eq!(find("requests").version, ">=2.31");
+        assert!(find("psycopg").optional);
+        assert!(find("pytest").dev);
+        assert!(find("ruff").dev);
+        assert!(result.warnings[0].contains("no lockfile found"));
+
+        fs::write(dir.path().join("uv.lock"), "version = 1\n").unwrap();
+        assert!(has_lockfile(dir.path()));
+    }
 }
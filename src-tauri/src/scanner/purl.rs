@@ -0,0 +1,383 @@
+//! Package URL (purl) の生成と解析
+//! https://github.com/package-url/purl-spec
+
+use super::{Dependency, Ecosystem};
+
+/// 依存関係から purl を生成
+/// "pkg:npm/%40types/node@18.11.18" のような形式
+pub fn to_purl(dep: &Dependency) -> Option<String> {
+    let ecosystem = Ecosystem::from_osv_name(&dep.ecosystem)?;
+    let (namespace, name, subpath) = split_name(ecosystem, &dep.name)?;
+
+    let mut purl = format!("pkg:{}/", ecosystem.purl_type());
+    if let Some(ns) = namespace {
+        let segments: Vec<String> = ns
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| urlencoding::encode(s).into_owned())
+            .collect();
+        purl.push_str(&segments.join("/"));
+        purl.push('/');
+    }
+    purl.push_str(&urlencoding::encode(&name));
+
+    if is_exact_version(&dep.version) {
+        purl.push('@');
+        purl.push_str(&urlencoding::encode(&dep.version));
+    }
+
+    if let Some(sub) = subpath {
+        purl.push('#');
+        purl.push_str(&sub);
+    }
+
+    Some(purl)
+}
+
+/// パッケージ名を (namespace, name, subpath) に分割
+fn split_name(
+    ecosystem: Ecosystem,
+    full_name: &str,
+) -> Option<(Option<String>, String, Option<String>)> {
+    let full_name = full_name.trim();
+    if full_name.is_empty() {
+        return None;
+    }
+
+    let split_last = |s: &str, sep: char| -> (Option<String>, String) {
+        match s.rfind(sep) {
+            Some(pos) => (Some(s[..pos].to_string()), s[pos + 1..].to_string()),
+            None => (None, s.to_string()),
+        }
+    };
+
+    let result = match ecosystem {
+        // "@scope/name" -> namespace "@scope"
+        Ecosystem::Npm => {
+            if full_name.starts_with('@') {
+                let (ns, name) = split_last(full_name, '/');
+                (ns, name, None)
+            } else {
+                (None, full_name.to_string(), None)
+            }
+        }
+        // "group:artifact"
+        Ecosystem::Maven => {
+            let (ns, name) = split_last(full_name, ':');
+            (ns, name, None)
+        }
+        // "vendor/name"（purl spec では小文字）
+        Ecosystem::Packagist => {
+            let (ns, name) = split_last(&full_name.to_lowercase(), '/');
+            (ns, name, None)
+        }
+        // "github.com/owner/repo"
+        Ecosystem::Go | Ecosystem::SwiftURL => {
+            let trimmed = full_name
+                .trim_start_matches("https://")
+                .trim_start_matches("http://")
+                .trim_end_matches(".git");
+            let (ns, name) = split_last(trimmed, '/');
+            (ns, name, None)
+        }
+        // "Firebase/Core" -> name "Firebase", subpath "Core"
+        Ecosystem::CocoaPods => match full_name.split_once('/') {
+            Some((pod, sub)) => (None, pod.to_string(), Some(sub.to_string())),
+            None => (None, full_name.to_string(), None),
+        },
+        Ecosystem::PyPI => (None, normalize_pypi_name(full_name), None),
+        Ecosystem::Cargo
+        | Ecosystem::NuGet
+        | Ecosystem::RubyGems
+        | Ecosystem::Pub
+        | Ecosystem::Hex => (None, full_name.to_string(), None),
+    };
+
+    Some(result)
+}
+
+/// PEP 503 の正規化（小文字化し、連続する "-", "_", "." を "-" に置換）
+pub fn normalize_pypi_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    let mut prev_sep = false;
+    for c in name.trim().chars() {
+        if c == '-' || c == '_' || c == '.' {
+            if !prev_sep {
+                normalized.push('-');
+            }
+            prev_sep = true;
+        } else {
+            normalized.extend(c.to_lowercase());
+            prev_sep = false;
+        }
+    }
+    normalized
+}
+
+/// purl 解析エラー
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PurlError {
+    #[error("invalid purl: {0}")]
+    Invalid(String),
+    #[error("unsupported purl type '{purl_type}': {purl}")]
+    UnsupportedType { purl_type: String, purl: String },
+    #[error("purl has no version: {0}")]
+    MissingVersion(String),
+}
+
+/// purl を解析して依存関係に変換
+/// "pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1" -> Maven "org.apache.logging.log4j:log4j-core"
+pub fn from_purl(purl: &str) -> Result<Dependency, PurlError> {
+    let invalid = || PurlError::Invalid(purl.to_string());
+
+    let rest = purl.trim().strip_prefix("pkg:").ok_or_else(invalid)?;
+    let rest = rest.trim_start_matches('/');
+
+    // "#subpath" と "?qualifiers" を切り離す
+    let (rest, subpath) = match rest.split_once('#') {
+        Some((r, sub)) => (r, Some(sub.trim_matches('/'))),
+        None => (rest, None),
+    };
+    let rest = rest.split_once('?').map_or(rest, |(r, _)| r);
+
+    let (purl_type, path) = rest.split_once('/').ok_or_else(invalid)?;
+    let purl_type = purl_type.to_lowercase();
+
+    // バージョンの "@" は最後のセグメントにのみ現れる（npm の "@scope" と区別）
+    let last_slash = path.rfind('/').map_or(0, |i| i + 1);
+    let (path, version) = match path[last_slash..].find('@') {
+        Some(at) => (&path[..last_slash + at], Some(&path[last_slash + at + 1..])),
+        None => (path, None),
+    };
+
+    let mut segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(decode)
+        .collect();
+    let name = segments.pop().ok_or_else(invalid)?;
+    let namespace = segments.join("/");
+
+    let ecosystem = Ecosystem::all()
+        .into_iter()
+        .find(|e| e.purl_type() == purl_type)
+        .ok_or_else(|| PurlError::UnsupportedType {
+            purl_type: purl_type.clone(),
+            purl: purl.to_string(),
+        })?;
+
+    let version = version
+        .map(decode)
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| PurlError::MissingVersion(purl.to_string()))?;
+
+    let join = |sep: &str| {
+        if namespace.is_empty() {
+            name.clone()
+        } else {
+            format!("{}{}{}", namespace, sep, name)
+        }
+    };
+    let full_name = match ecosystem {
+        Ecosystem::Maven => join(":"),
+        Ecosystem::Npm | Ecosystem::Packagist | Ecosystem::Go | Ecosystem::SwiftURL => join("/"),
+        Ecosystem::PyPI => normalize_pypi_name(&name),
+        Ecosystem::CocoaPods => match subpath.filter(|s| !s.is_empty()) {
+            Some(sub) => format!("{}/{}", name, decode(sub)),
+            None => name.clone(),
+        },
+        Ecosystem::Cargo
+        | Ecosystem::NuGet
+        | Ecosystem::RubyGems
+        | Ecosystem::Pub
+        | Ecosystem::Hex => name.clone(),
+    };
+
+    Ok(Dependency {
+        name: full_name,
+        version,
+        ecosystem: ecosystem.osv_name().to_string(),
+        ..Default::default()
+    })
+}
+
+/// パーセントエンコーディングを復号（不正な場合はそのまま）
+fn decode(s: &str) -> String {
+    urlencoding::decode(s)
+        .map(|d| d.into_owned())
+        .unwrap_or_else(|_| s.to_string())
+}
+
+/// 範囲指定や "*" ではない具体的なバージョンか
+fn is_exact_version(version: &str) -> bool {
+    !version.is_empty() && version != "*" && !version.starts_with(['>', '<', '=', '~', '^', '!'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dep(name: &str, version: &str, ecosystem: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            version: version.to_string(),
+            ecosystem: ecosystem.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_to_purl() {
+        let cases = [
+            (dep("lodash", "4.17.21", "npm"), "pkg:npm/lodash@4.17.21"),
+            (
+                dep("@types/node", "18.11.18", "npm"),
+                "pkg:npm/%40types/node@18.11.18",
+            ),
+            (
+                dep("serde", "1.0.193", "crates.io"),
+                "pkg:cargo/serde@1.0.193",
+            ),
+            (
+                dep("Django_REST.framework", "3.14.0", "PyPI"),
+                "pkg:pypi/django-rest-framework@3.14.0",
+            ),
+            (
+                dep("github.com/gin-gonic/gin", "v1.9.1", "Go"),
+                "pkg:golang/github.com/gin-gonic/gin@v1.9.1",
+            ),
+            (
+                dep("com.google.guava:guava", "31.1-jre", "Maven"),
+                "pkg:maven/com.google.guava/guava@31.1-jre",
+            ),
+            (
+                dep("Newtonsoft.Json", "13.0.1", "NuGet"),
+                "pkg:nuget/Newtonsoft.Json@13.0.1",
+            ),
+            (dep("rails", "7.0.4", "RubyGems"), "pkg:gem/rails@7.0.4"),
+            (
+                dep("laravel/framework", "9.52.0", "Packagist"),
+                "pkg:composer/laravel/framework@9.52.0",
+            ),
+            (dep("args", "2.3.1", "Pub"), "pkg:pub/args@2.3.1"),
+            (dep("phoenix", "1.6.15", "Hex"), "pkg:hex/phoenix@1.6.15"),
+            (
+                dep("Firebase/Core", "10.0.0", "CocoaPods"),
+                "pkg:cocoapods/Firebase@10.0.0#Core",
+            ),
+            (
+                dep("alamofire", "5.6.4", "SwiftURL"),
+                "pkg:swift/alamofire@5.6.4",
+            ),
+        ];
+        for (d, expected) in cases {
+            assert_eq!(to_purl(&d).as_deref(), Some(expected), "{:?}", d);
+        }
+    }
+
+    #[test]
+    fn test_to_purl_without_exact_version() {
+        assert_eq!(
+            to_purl(&dep("django", ">=3.0", "PyPI")).as_deref(),
+            Some("pkg:pypi/django")
+        );
+        assert_eq!(
+            to_purl(&dep("flask", "*", "PyPI")).as_deref(),
+            Some("pkg:pypi/flask")
+        );
+        assert_eq!(
+            to_purl(&dep("pkg", "1.0.0+build.1", "npm")).as_deref(),
+            Some("pkg:npm/pkg@1.0.0%2Bbuild.1")
+        );
+    }
+
+    #[test]
+    fn test_to_purl_unknown_ecosystem() {
+        assert_eq!(to_purl(&dep("foo", "1.0", "Unknown")), None);
+    }
+
+    #[test]
+    fn test_from_purl() {
+        let cases = [
+            (
+                "pkg:npm/%40types/node@18.11.18",
+                dep("@types/node", "18.11.18", "npm"),
+            ),
+            (
+                "pkg:npm/@angular/core@16.0.0",
+                dep("@angular/core", "16.0.0", "npm"),
+            ),
+            (
+                "pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1?type=jar",
+                dep("org.apache.logging.log4j:log4j-core", "2.14.1", "Maven"),
+            ),
+            (
+                "pkg:pypi/Django_REST.framework@3.14.0",
+                dep("django-rest-framework", "3.14.0", "PyPI"),
+            ),
+            (
+                "pkg:golang/github.com/gin-gonic/gin@v1.9.1",
+                dep("github.com/gin-gonic/gin", "v1.9.1", "Go"),
+            ),
+            (
+                "pkg:cargo/serde@1.0.193",
+                dep("serde", "1.0.193", "crates.io"),
+            ),
+            ("pkg:gem/rails@7.0.4", dep("rails", "7.0.4", "RubyGems")),
+            (
+                "pkg:composer/laravel/framework@9.52.0",
+                dep("laravel/framework", "9.52.0", "Packagist"),
+            ),
+            (
+                "pkg:cocoapods/Firebase@10.0.0#Core",
+                dep("Firebase/Core", "10.0.0", "CocoaPods"),
+            ),
+            (
+                "pkg:NuGet/Newtonsoft.Json@13.0.1",
+                dep("Newtonsoft.Json", "13.0.1", "NuGet"),
+            ),
+        ];
+        for (purl, expected) in cases {
+            assert_eq!(from_purl(purl), Ok(expected), "{}", purl);
+        }
+    }
+
+    #[test]
+    fn test_from_purl_roundtrip() {
+        for d in [
+            dep("@types/node", "18.11.18", "npm"),
+            dep("com.google.guava:guava", "31.1-jre", "Maven"),
+            dep("pkg", "1.0.0+build.1", "npm"),
+        ] {
+            assert_eq!(from_purl(&to_purl(&d).unwrap()), Ok(d));
+        }
+    }
+
+    #[test]
+    fn test_from_purl_errors() {
+        assert_eq!(
+            from_purl("pkg:deb/debian/openssl@3.0.11"),
+            Err(PurlError::UnsupportedType {
+                purl_type: "deb".to_string(),
+                purl: "pkg:deb/debian/openssl@3.0.11".to_string(),
+            })
+        );
+        assert!(matches!(
+            from_purl("pkg:npm/lodash"),
+            Err(PurlError::MissingVersion(_))
+        ));
+        assert!(matches!(
+            from_purl("npm/lodash@1.0.0"),
+            Err(PurlError::Invalid(_))
+        ));
+        assert!(matches!(from_purl("pkg:npm"), Err(PurlError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_normalize_pypi_name() {
+        assert_eq!(normalize_pypi_name("Foo_Bar"), "foo-bar");
+        assert_eq!(normalize_pypi_name("foo-bar"), "foo-bar");
+        assert_eq!(normalize_pypi_name("zope.interface"), "zope-interface");
+        assert_eq!(normalize_pypi_name("a__-.b"), "a-b");
+    }
+}
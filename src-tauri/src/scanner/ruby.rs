@@ -1,6 +1,6 @@
 //! RubyGems 依存関係パーサー
 
-use super::{Dependency, ScanDependencies, ScanError};
+use super::{assign_relations, Dependency, ScanDependencies, ScanError};
 use std::fs;
 use std::path::Path;
 
@@ -10,6 +10,10 @@ pub fn parse_gemfile_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
     let mut dependencies = Vec::new();
 
     let mut in_specs_section = false;
+    let mut in_dependencies_section = false;
+    let mut direct: Vec<String> = Vec::new();
+    // (依存元, 依存先) の組
+    let mut edges: Vec<(String, String)> = Vec::new();
 
     for line in content.lines() {
         // Detect GEM section with specs
@@ -26,6 +30,16 @@ pub fn parse_gemfile_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
         // New section starts (no leading spaces or different section)
         if !line.starts_with(' ') && !line.is_empty() {
             in_specs_section = false;
+            in_dependencies_section = line == "DEPENDENCIES";
+            continue;
+        }
+
+        // Gemfile に書かれた gem: "  rails (~> 7.0.4)" / "  my_gem!"
+        if in_dependencies_section && line.starts_with("  ") && !line.starts_with("   ") {
+            let name = gem_name(line).trim_end_matches('!');
+            if !name.is_empty() {
+                direct.push(name.to_string());
+            }
             continue;
         }
 
@@ -37,15 +51,38 @@ pub fn parse_gemfile_lock(path: &Path) -> Result<ScanDependencies, ScanError> {
                 dependencies.push(dep);
             }
         }
+        // その gem の依存: "      actionpack (= 7.0.4)"
+        else if in_specs_section && line.starts_with("      ") {
+            if let Some(parent) = dependencies.last() {
+                edges.push((parent.name.clone(), gem_name(line).to_string()));
+            }
+        }
     }
 
+    for dep in dependencies.iter_mut() {
+        dep.parents = edges
+            .iter()
+            .filter(|(_, child)| *child == dep.name)
+            .map(|(parent, _)| parent.clone())
+            .collect();
+        dep.parents.sort();
+        dep.parents.dedup();
+    }
+    assign_relations(&mut dependencies, &direct);
+
     Ok(ScanDependencies {
         ecosystem: "RubyGems".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
+/// バージョン制約を除いた gem 名（"  rack (~> 2.2)" -> "rack"）
+fn gem_name(line: &str) -> &str {
+    line.trim().split(" (").next().unwrap_or_default().trim()
+}
+
 /// gem spec 行をパース
 /// "rails (7.0.4)" -> Dependency { name: "rails", version: "7.0.4" }
 fn parse_gem_spec(line: &str) -> Option<Dependency> {
@@ -60,6 +97,7 @@ fn parse_gem_spec(line: &str) -> Option<Dependency> {
                     name: name.to_string(),
                     version: version.to_string(),
                     ecosystem: "RubyGems".to_string(),
+                    ..Default::default()
                 });
             }
         }
@@ -70,6 +108,7 @@ fn parse_gem_spec(line: &str) -> Option<Dependency> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scanner::DependencyRelation;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -106,6 +145,12 @@ BUNDLED WITH
         let names: Vec<&str> = result.dependencies.iter().map(|d| d.name.as_str()).collect();
         assert!(names.contains(&"rails"));
         assert!(names.contains(&"rack"));
+
+        let find = |name: &str| result.dependencies.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(find("rails").relation, DependencyRelation::Direct);
+        assert_eq!(find("actionpack").relation, DependencyRelation::Transitive);
+        assert_eq!(find("actionpack").parents, vec!["actioncable"]);
+        assert_eq!(find("rack").parents, vec!["actionpack"]);
     }
 
     #[test]
@@ -0,0 +1,307 @@
+//! 既存の SBOM（CycloneDX / SPDX JSON）からの依存関係の取り込み
+//! コンポーネントの purl を解析して各エコシステムの依存関係に変換する
+
+use super::purl::{self, PurlError};
+use super::{Dependency, ScanDependencies, ScanError};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// SBOM の取り込み結果
+#[derive(Debug, Clone)]
+pub struct SbomImport {
+    /// エコシステムごとの依存関係
+    pub scans: Vec<ScanDependencies>,
+    /// 取り込めなかったコンポーネント（未対応の purl type など）
+    pub skipped: Vec<String>,
+}
+
+/// CycloneDX JSON
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDxBom {
+    bom_format: Option<String>,
+    #[serde(default)]
+    components: Vec<CycloneDxComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CycloneDxComponent {
+    name: Option<String>,
+    version: Option<String>,
+    purl: Option<String>,
+    #[serde(default)]
+    components: Vec<CycloneDxComponent>,
+}
+
+/// SPDX JSON
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxDocument {
+    spdx_version: Option<String>,
+    #[serde(default)]
+    document_describes: Vec<String>,
+    #[serde(default)]
+    packages: Vec<SpdxPackage>,
+    #[serde(default)]
+    relationships: Vec<SpdxRelationship>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxPackage {
+    #[serde(rename = "SPDXID")]
+    spdx_id: Option<String>,
+    name: Option<String>,
+    version_info: Option<String>,
+    #[serde(default)]
+    external_refs: Vec<SpdxExternalRef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxExternalRef {
+    reference_type: String,
+    reference_locator: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxRelationship {
+    spdx_element_id: String,
+    related_spdx_element: String,
+    relationship_type: String,
+}
+
+/// CycloneDX / SPDX の JSON ファイルをパース
+pub fn parse_sbom(path: &Path) -> Result<SbomImport, ScanError> {
+    let content = fs::read_to_string(path)?;
+    let value: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| ScanError::Parse(e.to_string()))?;
+
+    // (purl, 表示名) の一覧
+    let entries = if value.get("bomFormat").is_some() {
+        let bom: CycloneDxBom =
+            serde_json::from_value(value).map_err(|e| ScanError::Parse(e.to_string()))?;
+        if bom.bom_format.as_deref() != Some("CycloneDX") {
+            return Err(ScanError::Parse("Unknown SBOM bomFormat".to_string()));
+        }
+        let mut entries = Vec::new();
+        collect_cyclonedx(&bom.components, &mut entries);
+        entries
+    } else if value.get("spdxVersion").is_some() {
+        let doc: SpdxDocument =
+            serde_json::from_value(value).map_err(|e| ScanError::Parse(e.to_string()))?;
+        if !doc
+            .spdx_version
+            .as_deref()
+            .unwrap_or("")
+            .starts_with("SPDX-")
+        {
+            return Err(ScanError::Parse("Unknown spdxVersion".to_string()));
+        }
+        collect_spdx(&doc)
+    } else {
+        return Err(ScanError::Parse(
+            "Not a CycloneDX or SPDX JSON document".to_string(),
+        ));
+    };
+
+    let source_file = path.to_string_lossy().to_string();
+    let mut scans: Vec<ScanDependencies> = Vec::new();
+    let mut skipped = Vec::new();
+    let mut seen: HashSet<(String, String, String)> = HashSet::new();
+
+    for (purl, label) in entries {
+        let Some(purl) = purl else {
+            skipped.push(format!("{}: no purl", label));
+            continue;
+        };
+        let dep: Dependency = match purl::from_purl(&purl) {
+            Ok(dep) => dep,
+            Err(e @ PurlError::UnsupportedType { .. }) => {
+                skipped.push(e.to_string());
+                continue;
+            }
+            Err(e) => {
+                skipped.push(format!("{}: {}", label, e));
+                continue;
+            }
+        };
+
+        let key = (dep.ecosystem.clone(), dep.name.clone(), dep.version.clone());
+        if !seen.insert(key) {
+            continue;
+        }
+
+        match scans.iter_mut().find(|s| s.ecosystem == dep.ecosystem) {
+            Some(scan) => scan.dependencies.push(dep),
+            None => scans.push(ScanDependencies {
+                ecosystem: dep.ecosystem.clone(),
+                source_file: source_file.clone(),
+                dependencies: vec![dep],
+                warnings: Vec::new(),
+            }),
+        }
+    }
+
+    Ok(SbomImport { scans, skipped })
+}
+
+/// CycloneDX のコンポーネントを入れ子も含めて収集
+/// metadata.component（SBOM の対象自身）は含めない
+fn collect_cyclonedx(
+    components: &[CycloneDxComponent],
+    entries: &mut Vec<(Option<String>, String)>,
+) {
+    for component in components {
+        entries.push((
+            component.purl.clone(),
+            label(&component.name, &component.version),
+        ));
+        collect_cyclonedx(&component.components, entries);
+    }
+}
+
+/// SPDX のパッケージを収集
+/// ドキュメントが DESCRIBES するパッケージ（SBOM の対象自身）は purl がなくても報告しない
+fn collect_spdx(doc: &SpdxDocument) -> Vec<(Option<String>, String)> {
+    let mut described: HashSet<&str> = doc.document_describes.iter().map(|s| s.as_str()).collect();
+    for rel in &doc.relationships {
+        if rel.relationship_type == "DESCRIBES" && rel.spdx_element_id == "SPDXRef-DOCUMENT" {
+            described.insert(rel.related_spdx_element.as_str());
+        }
+    }
+
+    let mut entries = Vec::new();
+    for package in &doc.packages {
+        let purl = package
+            .external_refs
+            .iter()
+            .find(|r| r.reference_type == "purl")
+            .map(|r| r.reference_locator.clone());
+        let is_root = package
+            .spdx_id
+            .as_deref()
+            .is_some_and(|id| described.contains(id));
+        if purl.is_none() && is_root {
+            continue;
+        }
+        entries.push((purl, label(&package.name, &package.version_info)));
+    }
+    entries
+}
+
+fn label(name: &Option<String>, version: &Option<String>) -> String {
+    match (name, version) {
+        (Some(n), Some(v)) => format!("{}@{}", n, v),
+        (Some(n), None) => n.clone(),
+        _ => "(unnamed component)".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn write_temp(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_parse_cyclonedx() {
+        let file = write_temp(
+            r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "metadata": { "component": { "type": "container", "name": "app-image" } },
+  "components": [
+    { "type": "library", "name": "lodash", "version": "4.17.20", "purl": "pkg:npm/lodash@4.17.20" },
+    { "type": "library", "name": "log4j-core", "version": "2.14.1",
+      "purl": "pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1",
+      "components": [
+        { "type": "library", "name": "log4j-api", "version": "2.14.1",
+          "purl": "pkg:maven/org.apache.logging.log4j/log4j-api@2.14.1" }
+      ] },
+    { "type": "library", "name": "lodash", "version": "4.17.20", "purl": "pkg:npm/lodash@4.17.20" },
+    { "type": "library", "name": "openssl", "version": "3.0.11", "purl": "pkg:deb/debian/openssl@3.0.11" },
+    { "type": "library", "name": "vendored-lib", "version": "1.0" }
+  ]
+}"#,
+        );
+        let result = parse_sbom(file.path()).unwrap();
+
+        assert_eq!(result.scans.len(), 2);
+        assert_eq!(result.scans[0].ecosystem, "npm");
+        assert_eq!(result.scans[0].dependencies.len(), 1);
+        assert_eq!(result.scans[1].ecosystem, "Maven");
+        let names: Vec<&str> = result.scans[1]
+            .dependencies
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "org.apache.logging.log4j:log4j-core",
+                "org.apache.logging.log4j:log4j-api"
+            ]
+        );
+
+        assert_eq!(result.skipped.len(), 2);
+        assert!(result.skipped[0].contains("unsupported purl type 'deb'"));
+        assert_eq!(result.skipped[1], "vendored-lib@1.0: no purl");
+    }
+
+    #[test]
+    fn test_parse_spdx() {
+        let file = write_temp(
+            r#"{
+  "spdxVersion": "SPDX-2.3",
+  "SPDXID": "SPDXRef-DOCUMENT",
+  "name": "app",
+  "packages": [
+    { "SPDXID": "SPDXRef-Root", "name": "app" },
+    { "SPDXID": "SPDXRef-Package-1", "name": "requests", "versionInfo": "2.25.0",
+      "externalRefs": [
+        { "referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl",
+          "referenceLocator": "pkg:pypi/requests@2.25.0" }
+      ] },
+    { "SPDXID": "SPDXRef-Package-2", "name": "busybox", "versionInfo": "1.36.1",
+      "externalRefs": [
+        { "referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl",
+          "referenceLocator": "pkg:apk/alpine/busybox@1.36.1" }
+      ] }
+  ],
+  "relationships": [
+    { "spdxElementId": "SPDXRef-DOCUMENT", "relationshipType": "DESCRIBES", "relatedSpdxElement": "SPDXRef-Root" }
+  ]
+}"#,
+        );
+        let result = parse_sbom(file.path()).unwrap();
+
+        assert_eq!(result.scans.len(), 1);
+        assert_eq!(
+            result.scans[0].dependencies,
+            vec![Dependency {
+                name: "requests".to_string(),
+                version: "2.25.0".to_string(),
+                ecosystem: "PyPI".to_string(),
+                ..Default::default()
+            }]
+        );
+        assert_eq!(result.skipped.len(), 1);
+        assert!(result.skipped[0].contains("'apk'"));
+    }
+
+    #[test]
+    fn test_parse_sbom_unknown_document() {
+        let file = write_temp(r#"{ "lockfileVersion": 3 }"#);
+        assert!(matches!(parse_sbom(file.path()), Err(ScanError::Parse(_))));
+    }
+}
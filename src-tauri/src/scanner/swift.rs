@@ -33,6 +33,7 @@ fn parse_v2(resolved: PackageResolvedV2, path: &Path) -> Result<ScanDependencies
                 name,
                 version,
                 ecosystem: "SwiftURL".to_string(),
+                ..Default::default()
             });
         }
     }
@@ -41,6 +42,7 @@ fn parse_v2(resolved: PackageResolvedV2, path: &Path) -> Result<ScanDependencies
         ecosystem: "SwiftURL".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
@@ -56,6 +58,7 @@ fn parse_v1(resolved: PackageResolvedV1, path: &Path) -> Result<ScanDependencies
                     name,
                     version,
                     ecosystem: "SwiftURL".to_string(),
+                    ..Default::default()
                 });
             }
         }
@@ -65,6 +68,7 @@ fn parse_v1(resolved: PackageResolvedV1, path: &Path) -> Result<ScanDependencies
         ecosystem: "SwiftURL".to_string(),
         source_file: path.to_string_lossy().to_string(),
         dependencies,
+        warnings: Vec::new(),
     })
 }
 
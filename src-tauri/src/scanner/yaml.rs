@@ -0,0 +1,73 @@
+//! YAML 形式のロックファイルの読み込み
+//! yaml-rust2 で読んだ文書を JSON の値に変換し、serde の型として取り出す
+
+use super::ScanError;
+use serde::de::DeserializeOwned;
+use yaml_rust2::{Yaml, YamlLoader};
+
+/// YAML の最初の文書を型に変換（空の文書は null として扱う）
+pub fn from_str<T: DeserializeOwned>(content: &str) -> Result<T, ScanError> {
+    let documents =
+        YamlLoader::load_from_str(content).map_err(|e| ScanError::Parse(e.to_string()))?;
+    let value = documents
+        .into_iter()
+        .next()
+        .map(to_json)
+        .unwrap_or_default();
+    serde_json::from_value(value).map_err(|e| ScanError::Parse(e.to_string()))
+}
+
+/// YAML の値を JSON の値に変換
+/// 小数は "1.10" と "1.1" を区別できるよう元の表記のまま文字列にし、マップのキーは文字列にそろえる
+fn to_json(value: Yaml) -> serde_json::Value {
+    match value {
+        Yaml::Real(s) | Yaml::String(s) => serde_json::Value::String(s),
+        Yaml::Integer(n) => n.into(),
+        Yaml::Boolean(b) => b.into(),
+        Yaml::Array(items) => items.into_iter().map(to_json).collect(),
+        Yaml::Hash(entries) => entries
+            .into_iter()
+            .filter_map(|(key, value)| Some((scalar_key(key)?, to_json(value))))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        Yaml::Alias(_) | Yaml::Null | Yaml::BadValue => serde_json::Value::Null,
+    }
+}
+
+/// マップのキーを文字列に変換（配列やマップのキーは読み飛ばす）
+fn scalar_key(key: Yaml) -> Option<String> {
+    match key {
+        Yaml::Real(s) | Yaml::String(s) => Some(s),
+        Yaml::Integer(n) => Some(n.to_string()),
+        Yaml::Boolean(b) => Some(b.to_string()),
+        Yaml::Null => Some("null".to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_from_str_keeps_scalars() {
+        let value: serde_json::Value = from_str(
+            "lockfileVersion: '6.0'\nversion: 1.10\ncount: 3\nflag: true\nlist: [a, 2]\n1: one\n",
+        )
+        .unwrap();
+        assert_eq!(value["lockfileVersion"], "6.0");
+        assert_eq!(value["version"], "1.10");
+        assert_eq!(value["count"], 3);
+        assert_eq!(value["flag"], true);
+        assert_eq!(value["list"], serde_json::json!(["a", 2]));
+        assert_eq!(value["1"], "one");
+    }
+
+    #[test]
+    fn test_from_str_empty_and_invalid() {
+        let empty: Option<BTreeMap<String, String>> = from_str("").unwrap();
+        assert!(empty.is_none());
+        assert!(from_str::<serde_json::Value>("key: [unclosed").is_err());
+    }
+}
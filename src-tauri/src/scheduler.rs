@@ -0,0 +1,169 @@
+//! 監視対象プロジェクトの定期スキャン
+//! 時期が来たプロジェクトを再スキャンし、前回の実行と比べて新しく見つかった high / critical の検出を通知する
+
+use crate::commands::vuln_commands::{self, severity_order};
+use crate::db::{self, models::ScanFinding};
+use crate::scanner::ScanOptions;
+use std::time::Duration;
+
+/// 再スキャンの時期を確認する間隔
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// 監視対象の既定の再スキャン間隔（時間）
+pub const DEFAULT_INTERVAL_HOURS: i64 = 24;
+
+/// 通知の本文に並べる検出の最大数
+const MAX_LISTED_FINDINGS: usize = 3;
+
+/// 再スキャンのオプション
+/// OSV への照会に失敗したらスキャンを失敗として記録する（検出の欠けた実行と比べて誤った通知を出さない）
+fn rescan_options() -> ScanOptions {
+    ScanOptions {
+        strict: true,
+        ..ScanOptions::default()
+    }
+}
+
+/// 再スキャンで新しく見つかった重大な検出
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchAlert {
+    pub directory: String,
+    pub run_id: i64,
+    pub findings: Vec<ScanFinding>,
+}
+
+/// 時期が来た監視対象を再スキャンし、通知すべき検出を返す
+/// 失敗したプロジェクトはエラーを記録して次の時期まで待つ
+pub async fn rescan_due(db_path: &str) -> Vec<WatchAlert> {
+    // Connection は Sync ではないので、await をまたいで借用しない
+    let due = match db::get_connection(db_path)
+        .and_then(|conn| db::vuln_queries::get_due_watched_projects(&conn))
+    {
+        Ok(due) => due,
+        Err(e) => {
+            eprintln!("Failed to load watched projects: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut alerts = Vec::new();
+    for project in due {
+        let scan =
+            vuln_commands::run_scan(db_path, project.directory.clone(), &rescan_options()).await;
+        let Ok(conn) = db::get_connection(db_path) else {
+            continue;
+        };
+        let (run_id, error) = match scan {
+            Ok(result) => (result.run_id, None),
+            Err(e) => (None, Some(e.message)),
+        };
+        if let Err(e) = db::vuln_queries::mark_watched_project_scanned(
+            &conn,
+            project.id,
+            run_id,
+            error.as_deref(),
+        ) {
+            eprintln!(
+                "Failed to update watched project {}: {}",
+                project.directory, e
+            );
+        }
+
+        let Some(run_id) = run_id else {
+            continue;
+        };
+        // 初回のスキャンは比較対象がないので通知しない
+        if let Ok(diff) = vuln_commands::load_scan_diff(&conn, None, run_id) {
+            let findings = new_critical_findings(diff.new);
+            if !findings.is_empty() {
+                alerts.push(WatchAlert {
+                    directory: project.directory,
+                    run_id,
+                    findings,
+                });
+            }
+        }
+    }
+    alerts
+}
+
+/// 新規の検出のうち、抑制されていない high / critical のもの
+pub fn new_critical_findings(new: Vec<ScanFinding>) -> Vec<ScanFinding> {
+    new.into_iter()
+        .filter(|f| !f.suppressed && severity_order(&f.severity) >= severity_order("high"))
+        .collect()
+}
+
+/// デスクトップ通知のタイトルと本文
+pub fn notification(alert: &WatchAlert) -> (String, String) {
+    let title = format!("新しい脆弱性が {} 件見つかりました", alert.findings.len());
+    let mut lines = vec![alert.directory.clone()];
+    for f in alert.findings.iter().take(MAX_LISTED_FINDINGS) {
+        lines.push(format!(
+            "[{}] {} {}@{}",
+            f.severity, f.vuln_id, f.package_name, f.installed_version
+        ));
+    }
+    if alert.findings.len() > MAX_LISTED_FINDINGS {
+        lines.push(format!(
+            "ほか {} 件",
+            alert.findings.len() - MAX_LISTED_FINDINGS
+        ));
+    }
+    (title, lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(vuln_id: &str, severity: &str, suppressed: bool) -> ScanFinding {
+        ScanFinding {
+            project: ".".to_string(),
+            source_file: "/work/app/Cargo.lock".to_string(),
+            ecosystem: "crates.io".to_string(),
+            package_name: "openssl".to_string(),
+            installed_version: "0.10.0".to_string(),
+            vuln_id: vuln_id.to_string(),
+            canonical_id: vuln_id.to_string(),
+            severity: severity.to_string(),
+            suppressed,
+        }
+    }
+
+    #[test]
+    fn test_rescan_fails_on_query_errors() {
+        assert!(rescan_options().strict);
+        assert!(!rescan_options().offline);
+    }
+
+    #[test]
+    fn test_new_critical_findings() {
+        let findings = new_critical_findings(vec![
+            finding("RUSTSEC-1", "critical", false),
+            finding("RUSTSEC-2", "medium", false),
+            finding("RUSTSEC-3", "HIGH", false),
+            finding("RUSTSEC-4", "critical", true),
+        ]);
+        let ids: Vec<&str> = findings.iter().map(|f| f.vuln_id.as_str()).collect();
+        assert_eq!(ids, vec!["RUSTSEC-1", "RUSTSEC-3"]);
+    }
+
+    #[test]
+    fn test_notification_text() {
+        let alert = WatchAlert {
+            directory: "/work/app".to_string(),
+            run_id: 3,
+            findings: (1..=5)
+                .map(|i| finding(&format!("RUSTSEC-{}", i), "high", false))
+                .collect(),
+        };
+        let (title, body) = notification(&alert);
+        assert_eq!(title, "新しい脆弱性が 5 件見つかりました");
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines[0], "/work/app");
+        assert_eq!(lines[1], "[high] RUSTSEC-1 openssl@0.10.0");
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[4], "ほか 2 件");
+    }
+}
@@ -0,0 +1,166 @@
+//! Maven バージョンの比較
+//! https://maven.apache.org/pom.html#version-order-specification
+//! ComparableVersion の入れ子リスト（"-" 区切り）は平坦化して扱う簡易実装
+
+use std::cmp::Ordering;
+
+/// バージョンを構成する要素
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Int(u64),
+    Qualifier(String),
+}
+
+/// 既知の修飾子の順序（"" は正式リリース）
+const QUALIFIERS: &[&str] = &["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+
+fn qualifier_rank(qualifier: &str) -> (usize, &str) {
+    match QUALIFIERS.iter().position(|q| *q == qualifier) {
+        Some(rank) => (rank, ""),
+        // 未知の修飾子は既知のものより後で、辞書順
+        None => (QUALIFIERS.len(), qualifier),
+    }
+}
+
+/// 修飾子の別名を正規化
+fn normalize_qualifier(qualifier: &str, followed_by_digit: bool) -> String {
+    match qualifier {
+        "a" if followed_by_digit => "alpha".to_string(),
+        "b" if followed_by_digit => "beta".to_string(),
+        "m" if followed_by_digit => "milestone".to_string(),
+        "cr" => "rc".to_string(),
+        "ga" | "final" | "release" => String::new(),
+        q => q.to_string(),
+    }
+}
+
+/// バージョン文字列を要素列に分解
+/// "." / "-" および数字と英字の境界で区切り、末尾の 0 や空の修飾子は除去する
+fn parse(version: &str) -> Option<Vec<Item>> {
+    let version = version.trim().to_lowercase();
+    if version.is_empty()
+        || version
+            .chars()
+            .any(|c| c.is_whitespace() || "[](),".contains(c))
+    {
+        return None;
+    }
+
+    let mut tokens: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for c in version.chars() {
+        if c == '.' || c == '-' || c == '_' {
+            tokens.push(std::mem::take(&mut current));
+            continue;
+        }
+        let boundary = current
+            .chars()
+            .last()
+            .is_some_and(|last| last.is_ascii_digit() != c.is_ascii_digit());
+        if boundary {
+            tokens.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+    tokens.push(current);
+
+    let mut items = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if token.chars().all(|c| c.is_ascii_digit()) && !token.is_empty() {
+            items.push(Item::Int(token.parse().ok()?));
+        } else {
+            let followed_by_digit = tokens
+                .get(i + 1)
+                .is_some_and(|next| next.starts_with(|c: char| c.is_ascii_digit()));
+            items.push(Item::Qualifier(normalize_qualifier(
+                token,
+                followed_by_digit,
+            )));
+        }
+    }
+
+    // 末尾の "null" 要素（0 と正式リリース修飾子）を除去: "1.0.0" == "1" == "1-ga"
+    while items
+        .last()
+        .is_some_and(|item| *item == Item::Int(0) || *item == Item::Qualifier(String::new()))
+    {
+        items.pop();
+    }
+
+    Some(items)
+}
+
+/// 要素を比較（None は要素が尽きた側）
+fn compare_item(a: Option<&Item>, b: Option<&Item>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (Some(Item::Int(a)), Some(Item::Int(b))) => a.cmp(b),
+        // 数値は修飾子より後
+        (Some(Item::Int(_)), Some(Item::Qualifier(_))) => Ordering::Greater,
+        (Some(Item::Qualifier(_)), Some(Item::Int(_))) => Ordering::Less,
+        (Some(Item::Qualifier(a)), Some(Item::Qualifier(b))) => {
+            qualifier_rank(a).cmp(&qualifier_rank(b))
+        }
+        // 要素が尽きた側は 0 / 正式リリースとして比較
+        (Some(Item::Int(a)), None) => a.cmp(&0),
+        (None, Some(Item::Int(b))) => 0.cmp(b),
+        (Some(Item::Qualifier(a)), None) => qualifier_rank(a).cmp(&qualifier_rank("")),
+        (None, Some(Item::Qualifier(b))) => qualifier_rank("").cmp(&qualifier_rank(b)),
+    }
+}
+
+/// 2 つのバージョンを比較
+pub fn compare(a: &str, b: &str) -> Option<Ordering> {
+    let a = parse(a)?;
+    let b = parse(b)?;
+    let len = a.len().max(b.len());
+    Some(
+        (0..len)
+            .map(|i| compare_item(a.get(i), b.get(i)))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maven_ordering() {
+        let ordered = [
+            "1.0-alpha1",
+            "1.0-alpha2",
+            "1.0-beta1",
+            "1.0-M1",
+            "1.0-RC1",
+            "1.0-SNAPSHOT",
+            "1.0",
+            "1.0-sp1",
+            "1.0-foo",
+            "1.0.1",
+            "1.1",
+            "2.14.1",
+            "2.15.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(
+                compare(pair[0], pair[1]),
+                Some(Ordering::Less),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_maven_equivalence() {
+        assert_eq!(compare("1.0.0", "1"), Some(Ordering::Equal));
+        assert_eq!(compare("1.0-ga", "1.0"), Some(Ordering::Equal));
+        assert_eq!(compare("1.0.Final", "1.0"), Some(Ordering::Equal));
+        assert_eq!(compare("1.0-CR1", "1.0-rc1"), Some(Ordering::Equal));
+        assert_eq!(compare("31.1-jre", "31.1-android"), Some(Ordering::Greater));
+        assert_eq!(compare("[1.0,2.0)", "1.0"), None);
+    }
+}
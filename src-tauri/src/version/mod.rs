@@ -0,0 +1,131 @@
+//! バージョン比較モジュール
+//! エコシステムごとのバージョン表記を比較し、OSV の影響範囲を評価する
+
+pub mod maven;
+pub mod pep440;
+pub mod range;
+pub mod rubygems;
+pub mod semver;
+pub mod upgrade;
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// バージョン表記の体系
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionScheme {
+    /// SemVer 2.0（npm, crates.io, Hex, Pub など）
+    Semver,
+    /// Go モジュール（"v" 接頭辞、疑似バージョン、+incompatible）
+    Go,
+    /// PEP 440（PyPI）
+    Pep440,
+    /// Maven ComparableVersion
+    Maven,
+    /// Gem::Version（RubyGems, CocoaPods）
+    RubyGems,
+    /// NuGet（4 桁までの数値部、プレリリースは大文字小文字を区別しない）
+    NuGet,
+}
+
+impl VersionScheme {
+    /// OSV のエコシステム名から比較方式を決定
+    pub fn for_ecosystem(ecosystem: &str) -> VersionScheme {
+        // "Debian:11" のようなサフィックスは無視
+        let base = ecosystem.split(':').next().unwrap_or(ecosystem);
+        match base {
+            "Go" => VersionScheme::Go,
+            "PyPI" => VersionScheme::Pep440,
+            "Maven" => VersionScheme::Maven,
+            "RubyGems" | "CocoaPods" => VersionScheme::RubyGems,
+            "NuGet" | "Packagist" => VersionScheme::NuGet,
+            _ => VersionScheme::Semver,
+        }
+    }
+
+    /// 2 つのバージョンを比較（どちらかを解釈できない場合は None）
+    pub fn compare(&self, a: &str, b: &str) -> Option<Ordering> {
+        match self {
+            VersionScheme::Semver => semver::compare(a, b, false),
+            VersionScheme::Go => semver::compare(strip_go(a), strip_go(b), false),
+            VersionScheme::NuGet => semver::compare(a, b, true),
+            VersionScheme::Pep440 => pep440::compare(a, b),
+            VersionScheme::Maven => maven::compare(a, b),
+            VersionScheme::RubyGems => rubygems::compare(a, b),
+        }
+    }
+}
+
+/// Go のバージョンから "+incompatible" を除去（"v" は semver 側で許容）
+fn strip_go(version: &str) -> &str {
+    version.trim().trim_end_matches("+incompatible")
+}
+
+/// インストール済みバージョンが脆弱性の影響範囲に含まれるか
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionMatch {
+    /// 影響範囲に含まれる
+    Affected,
+    /// 影響範囲外（誤検知の可能性）
+    NotAffected,
+    /// 判定できない（GIT 範囲のみ、バージョンを解釈できないなど）
+    #[default]
+    Unknown,
+    /// バージョンが固定されておらず、指定範囲のいずれかのバージョンが影響を受ける
+    Unpinned,
+}
+
+/// バージョンが固定されていない（requirements.txt の ">=2.0" など範囲で指定された）依存か
+pub fn is_unpinned(ecosystem: &str, version: &str) -> bool {
+    VersionScheme::for_ecosystem(ecosystem) == VersionScheme::Pep440
+        && pep440::parse_specifier(version).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_ecosystem() {
+        assert_eq!(VersionScheme::for_ecosystem("npm"), VersionScheme::Semver);
+        assert_eq!(
+            VersionScheme::for_ecosystem("crates.io"),
+            VersionScheme::Semver
+        );
+        assert_eq!(VersionScheme::for_ecosystem("PyPI"), VersionScheme::Pep440);
+        assert_eq!(VersionScheme::for_ecosystem("Go"), VersionScheme::Go);
+        assert_eq!(VersionScheme::for_ecosystem("Maven"), VersionScheme::Maven);
+        assert_eq!(
+            VersionScheme::for_ecosystem("RubyGems"),
+            VersionScheme::RubyGems
+        );
+        assert_eq!(VersionScheme::for_ecosystem("NuGet"), VersionScheme::NuGet);
+    }
+
+    #[test]
+    fn test_go_versions() {
+        let go = VersionScheme::Go;
+        assert_eq!(go.compare("v1.9.1", "1.9.1"), Some(Ordering::Equal));
+        assert_eq!(
+            go.compare("v2.0.0+incompatible", "v1.9.9"),
+            Some(Ordering::Greater)
+        );
+        // 疑似バージョンはベースとなるリリースより前、タイムスタンプ順
+        assert_eq!(
+            go.compare(
+                "v0.0.0-20191109021931-daa7c04131f5",
+                "v0.0.0-20200101000000-aaaaaaaaaaaa"
+            ),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            go.compare("v1.2.4-0.20191109021931-daa7c04131f5", "v1.2.3"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            go.compare("v1.2.4-0.20191109021931-daa7c04131f5", "v1.2.4"),
+            Some(Ordering::Less)
+        );
+    }
+}
@@ -0,0 +1,484 @@
+//! PEP 440 バージョンの比較
+//! https://peps.python.org/pep-0440/
+//! 正規化（"1.0-beta.2" -> "1.0b2", "1.0-1" -> "1.0.post1" など）にも対応
+
+use std::cmp::Ordering;
+
+/// 解析済みのバージョン
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub epoch: u64,
+    pub release: Vec<u64>,
+    /// (種別 0=a, 1=b, 2=rc, 番号)
+    pub pre: Option<(u8, u64)>,
+    pub post: Option<u64>,
+    pub dev: Option<u64>,
+    pub local: Vec<LocalSegment>,
+}
+
+/// ローカルバージョン（"+" 以降）のセグメント
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalSegment {
+    Numeric(u64),
+    Alpha(String),
+}
+
+impl Ord for LocalSegment {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (LocalSegment::Numeric(a), LocalSegment::Numeric(b)) => a.cmp(b),
+            // 数値のセグメントは英字のセグメントより後
+            (LocalSegment::Numeric(_), LocalSegment::Alpha(_)) => Ordering::Greater,
+            (LocalSegment::Alpha(_), LocalSegment::Numeric(_)) => Ordering::Less,
+            (LocalSegment::Alpha(a), LocalSegment::Alpha(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for LocalSegment {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Version {
+    /// プレリリースの比較キー
+    /// dev のみのリリース（"1.0.dev1"）は a / b / rc より前、正式リリースは最後
+    fn pre_key(&self) -> (i8, u64) {
+        match (self.pre, self.post, self.dev) {
+            (Some((kind, n)), _, _) => (kind as i8, n),
+            (None, None, Some(_)) => (-1, 0),
+            _ => (3, 0),
+        }
+    }
+
+    /// ポストリリースの比較キー（なしが先）
+    fn post_key(&self) -> (bool, u64) {
+        (self.post.is_some(), self.post.unwrap_or(0))
+    }
+
+    /// 開発リリースの比較キー（dev ありが先）
+    fn dev_key(&self) -> (bool, u64) {
+        (self.dev.is_none(), self.dev.unwrap_or(0))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| compare_release(&self.release, &other.release))
+            .then_with(|| self.pre_key().cmp(&other.pre_key()))
+            .then_with(|| self.post_key().cmp(&other.post_key()))
+            .then_with(|| self.dev_key().cmp(&other.dev_key()))
+            .then_with(|| self.local.cmp(&other.local))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// リリース部を比較（末尾の 0 は無視: "1.0" == "1.0.0"）
+fn compare_release(a: &[u64], b: &[u64]) -> Ordering {
+    let len = a.len().max(b.len());
+    (0..len)
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|o| *o != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// バージョン文字列を解析
+pub fn parse(version: &str) -> Option<Version> {
+    let lower = version.trim().to_lowercase();
+    let s = lower.strip_prefix('v').unwrap_or(&lower);
+
+    let (s, local) = match s.split_once('+') {
+        Some((v, local)) => (v, parse_local(local)?),
+        None => (s, Vec::new()),
+    };
+
+    let (epoch, s) = match s.split_once('!') {
+        Some((e, rest)) => (e.parse().ok()?, rest),
+        None => (0, s),
+    };
+
+    let mut chars = Scanner::new(s);
+
+    // リリース部 "N(.N)*"
+    let mut release = vec![chars.number()?];
+    while chars.peek() == Some('.') && chars.peek_at(1).is_some_and(|c| c.is_ascii_digit()) {
+        chars.next();
+        release.push(chars.number()?);
+    }
+
+    // プレリリース "[-_.]?(a|b|rc|alpha|beta|c|pre|preview)[-_.]?N?"
+    let mut pre = None;
+    let checkpoint = chars.pos;
+    chars.separator();
+    if let Some(kind) = chars.word(&[
+        ("alpha", 0),
+        ("beta", 1),
+        ("preview", 2),
+        ("pre", 2),
+        ("rc", 2),
+        ("a", 0),
+        ("b", 1),
+        ("c", 2),
+    ]) {
+        chars.separator();
+        pre = Some((kind, chars.number().unwrap_or(0)));
+    } else {
+        chars.pos = checkpoint;
+    }
+
+    // ポストリリース "[-_.]?(post|rev|r)[-_.]?N?" または "-N"
+    let mut post = None;
+    let checkpoint = chars.pos;
+    if chars.peek() == Some('-') && chars.peek_at(1).is_some_and(|c| c.is_ascii_digit()) {
+        chars.next();
+        post = Some(chars.number()?);
+    } else {
+        chars.separator();
+        if chars.word(&[("post", 0), ("rev", 0), ("r", 0)]).is_some() {
+            chars.separator();
+            post = Some(chars.number().unwrap_or(0));
+        } else {
+            chars.pos = checkpoint;
+        }
+    }
+
+    // 開発リリース "[-_.]?dev[-_.]?N?"
+    let mut dev = None;
+    let checkpoint = chars.pos;
+    chars.separator();
+    if chars.word(&[("dev", 0)]).is_some() {
+        chars.separator();
+        dev = Some(chars.number().unwrap_or(0));
+    } else {
+        chars.pos = checkpoint;
+    }
+
+    if !chars.is_done() {
+        return None;
+    }
+
+    Some(Version {
+        epoch,
+        release,
+        pre,
+        post,
+        dev,
+        local,
+    })
+}
+
+fn parse_local(local: &str) -> Option<Vec<LocalSegment>> {
+    local
+        .split(['.', '-', '_'])
+        .map(|seg| {
+            if seg.is_empty() || !seg.chars().all(|c| c.is_ascii_alphanumeric()) {
+                None
+            } else if seg.chars().all(|c| c.is_ascii_digit()) {
+                seg.parse().ok().map(LocalSegment::Numeric)
+            } else {
+                Some(LocalSegment::Alpha(seg.to_string()))
+            }
+        })
+        .collect()
+}
+
+/// 2 つのバージョンを比較
+pub fn compare(a: &str, b: &str) -> Option<Ordering> {
+    Some(parse(a)?.cmp(&parse(b)?))
+}
+
+/// バージョン指定子（">=2.0,<3.0" / "~=1.4" / "==1.4.*" / "*"）が表す範囲
+/// 下限・上限は (バージョン, 境界を含むか)。"!=" による除外は考慮しない
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpecifierRange {
+    pub lower: Option<(String, bool)>,
+    pub upper: Option<(String, bool)>,
+}
+
+impl SpecifierRange {
+    /// 範囲にバージョンが含まれるか
+    pub fn contains(&self, version: &str) -> bool {
+        let point = Some((version.to_string(), true));
+        self.overlaps(&SpecifierRange {
+            lower: point.clone(),
+            upper: point,
+        })
+    }
+
+    /// 2 つの範囲が重なるか（比較できない境界は重なるものとみなす）
+    pub fn overlaps(&self, other: &SpecifierRange) -> bool {
+        below(&self.lower, &other.upper) && below(&other.lower, &self.upper)
+    }
+
+    /// 下限を引き上げる
+    fn raise(&mut self, version: &str, inclusive: bool) -> Option<()> {
+        parse(version)?;
+        let replace = match &self.lower {
+            None => true,
+            Some((current, current_inclusive)) => match compare(version, current)? {
+                Ordering::Greater => true,
+                Ordering::Equal => *current_inclusive && !inclusive,
+                Ordering::Less => false,
+            },
+        };
+        if replace {
+            self.lower = Some((version.to_string(), inclusive));
+        }
+        Some(())
+    }
+
+    /// 上限を引き下げる
+    fn cap(&mut self, version: &str, inclusive: bool) -> Option<()> {
+        parse(version)?;
+        let replace = match &self.upper {
+            None => true,
+            Some((current, current_inclusive)) => match compare(version, current)? {
+                Ordering::Less => true,
+                Ordering::Equal => *current_inclusive && !inclusive,
+                Ordering::Greater => false,
+            },
+        };
+        if replace {
+            self.upper = Some((version.to_string(), inclusive));
+        }
+        Some(())
+    }
+}
+
+/// 下限が上限を超えていないか
+fn below(lower: &Option<(String, bool)>, upper: &Option<(String, bool)>) -> bool {
+    let (Some((lower, lower_inclusive)), Some((upper, upper_inclusive))) = (lower, upper) else {
+        return true;
+    };
+    match compare(lower, upper) {
+        Some(Ordering::Less) | None => true,
+        Some(Ordering::Equal) => *lower_inclusive && *upper_inclusive,
+        Some(Ordering::Greater) => false,
+    }
+}
+
+/// バージョン指定子をパース（バージョンを 1 つに固定したもの、解釈できないものは None）
+pub fn parse_specifier(spec: &str) -> Option<SpecifierRange> {
+    let spec = spec.trim();
+    let mut range = SpecifierRange::default();
+    if spec == "*" {
+        return Some(range);
+    }
+    if !spec.starts_with(['<', '>', '=', '~', '!']) {
+        return None;
+    }
+    for clause in spec.split(',') {
+        let clause = clause.trim();
+        let op_len = clause
+            .find(|c: char| !matches!(c, '<' | '>' | '=' | '~' | '!'))
+            .unwrap_or(clause.len());
+        let (op, version) = (&clause[..op_len], clause[op_len..].trim());
+        match op {
+            ">=" => range.raise(version, true)?,
+            ">" => range.raise(version, false)?,
+            "<=" => range.cap(version, true)?,
+            "<" => range.cap(version, false)?,
+            // "~=1.4.5" は ">=1.4.5, <1.5"
+            "~=" => {
+                range.raise(version, true)?;
+                range.cap(&bump_release(version, 1)?, false)?;
+            }
+            "==" | "===" => match version.strip_suffix(".*") {
+                // "==1.4.*" は ">=1.4, <1.5"
+                Some(prefix) => {
+                    range.raise(prefix, true)?;
+                    range.cap(&bump_release(prefix, 0)?, false)?;
+                }
+                None => {
+                    range.raise(version, true)?;
+                    range.cap(version, true)?;
+                }
+            },
+            "!=" => {}
+            _ => return None,
+        }
+    }
+    // "==2.0" は 1 つのバージョンに固定されている
+    if range.lower.is_some() && range.lower == range.upper {
+        return None;
+    }
+    Some(range)
+}
+
+/// リリース番号の末尾 drop 個を除き、残りの最後を 1 つ上げる（"1.4.5", 1 -> "1.5"）
+fn bump_release(version: &str, drop: usize) -> Option<String> {
+    let mut release = parse(version)?.release;
+    if release.len() <= drop {
+        return None;
+    }
+    release.truncate(release.len() - drop);
+    *release.last_mut()? += 1;
+    Some(
+        release
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("."),
+    )
+}
+
+/// 簡易的な文字列走査
+struct Scanner {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Scanner {
+    fn new(source: &str) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos >= self.chars.len()
+    }
+
+    /// 区切り文字 "-", "_", "." を 1 つ読み飛ばす
+    fn separator(&mut self) {
+        if matches!(self.peek(), Some('-' | '_' | '.')) {
+            self.pos += 1;
+        }
+    }
+
+    fn number(&mut self) -> Option<u64> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return None;
+        }
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok()
+    }
+
+    /// 候補の語のいずれかに一致すれば読み進めて値を返す（長い語を先に並べること）
+    fn word(&mut self, words: &[(&str, u8)]) -> Option<u8> {
+        for (word, value) in words {
+            let len = word.chars().count();
+            if self.pos + len <= self.chars.len()
+                && self.chars[self.pos..self.pos + len]
+                    .iter()
+                    .copied()
+                    .eq(word.chars())
+            {
+                self.pos += len;
+                return Some(*value);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pep440_ordering() {
+        // PEP 440 の「Summary of permitted suffixes and relative ordering」より
+        let ordered = [
+            "1.0.dev456",
+            "1.0a1",
+            "1.0a2.dev456",
+            "1.0a12.dev456",
+            "1.0a12",
+            "1.0b1.dev456",
+            "1.0b2",
+            "1.0b2.post345.dev456",
+            "1.0b2.post345",
+            "1.0rc1.dev456",
+            "1.0rc1",
+            "1.0",
+            "1.0+abc.5",
+            "1.0+abc.7",
+            "1.0+5",
+            "1.0.post456.dev34",
+            "1.0.post456",
+            "1.0.15",
+            "1.1.dev1",
+            "1!0.1",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(
+                compare(pair[0], pair[1]),
+                Some(Ordering::Less),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_pep440_normalization() {
+        assert_eq!(compare("1.0-beta.2", "1.0b2"), Some(Ordering::Equal));
+        assert_eq!(compare("1.0-1", "1.0.post1"), Some(Ordering::Equal));
+        assert_eq!(compare("1.0.0", "1.0"), Some(Ordering::Equal));
+        assert_eq!(compare("v2.0RC1", "2.0rc1"), Some(Ordering::Equal));
+        assert_eq!(compare("1.0-dev", "1.0.dev0"), Some(Ordering::Equal));
+        assert_eq!(compare(">=1.0", "1.0"), None);
+        assert_eq!(compare("1.0.x", "1.0"), None);
+    }
+
+    #[test]
+    fn test_parse_specifier() {
+        let range = parse_specifier(">=2.0, <3.0, !=2.5").unwrap();
+        assert_eq!(range.lower, Some(("2.0".to_string(), true)));
+        assert_eq!(range.upper, Some(("3.0".to_string(), false)));
+        assert!(range.contains("2.9.1"));
+        assert!(!range.contains("3.0"));
+
+        let compatible = parse_specifier("~=1.4.5").unwrap();
+        assert!(compatible.contains("1.4.9"));
+        assert!(!compatible.contains("1.5"));
+        let wildcard = parse_specifier("==1.4.*").unwrap();
+        assert!(wildcard.contains("1.4.2"));
+        assert!(!wildcard.contains("1.5.0"));
+        assert!(parse_specifier("*").unwrap().contains("0.1"));
+
+        // 固定されたバージョンや解釈できない指定は範囲ではない
+        assert_eq!(parse_specifier("2.28.0"), None);
+        assert_eq!(parse_specifier("==2.28.0"), None);
+        assert_eq!(parse_specifier(">=banana"), None);
+
+        let vulnerable = SpecifierRange {
+            lower: None,
+            upper: Some(("2.0".to_string(), false)),
+        };
+        assert!(!range.overlaps(&vulnerable));
+        assert!(parse_specifier(">1.5").unwrap().overlaps(&vulnerable));
+    }
+}
@@ -0,0 +1,448 @@
+//! OSV の影響範囲（affected[].ranges / versions）の評価
+//! https://ossf.github.io/osv-schema/#evaluation
+
+use super::pep440::{self, SpecifierRange};
+use super::{VersionMatch, VersionScheme};
+use crate::api::osv::{OsvAffected, OsvRange, OsvVulnerability};
+use crate::scanner::{purl, Dependency};
+use std::cmp::Ordering;
+
+/// イベントの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventKind {
+    Introduced,
+    Fixed,
+    LastAffected,
+}
+
+/// 脆弱性が依存関係のバージョンに該当するかを判定
+/// 依存関係と同じパッケージの affected エントリのみを評価する
+pub fn evaluate(vuln: &OsvVulnerability, dep: &Dependency) -> VersionMatch {
+    let mut result = VersionMatch::NotAffected;
+    let mut evaluated = false;
+
+    for affected in &vuln.affected {
+        let Some(ref package) = affected.package else {
+            continue;
+        };
+        if !same_package(&package.ecosystem, &package.name, dep) {
+            continue;
+        }
+        evaluated = true;
+
+        match evaluate_affected(affected, &dep.ecosystem, &dep.version) {
+            m @ (VersionMatch::Affected | VersionMatch::Unpinned) => return m,
+            VersionMatch::Unknown => result = VersionMatch::Unknown,
+            VersionMatch::NotAffected => {}
+        }
+    }
+
+    if evaluated {
+        result
+    } else {
+        VersionMatch::Unknown
+    }
+}
+
+/// 1 つの affected エントリを評価
+pub fn evaluate_affected(affected: &OsvAffected, ecosystem: &str, version: &str) -> VersionMatch {
+    let scheme = VersionScheme::for_ecosystem(ecosystem);
+    if scheme == VersionScheme::Pep440 {
+        if let Some(spec) = pep440::parse_specifier(version) {
+            return evaluate_specifier(affected, &spec);
+        }
+    }
+
+    // 明示的なバージョン一覧
+    if affected
+        .versions
+        .iter()
+        .any(|v| v == version || scheme.compare(v, version) == Some(Ordering::Equal))
+    {
+        return VersionMatch::Affected;
+    }
+
+    let mut result = if affected.versions.is_empty() && affected.ranges.is_empty() {
+        VersionMatch::Unknown
+    } else {
+        VersionMatch::NotAffected
+    };
+
+    for range in &affected.ranges {
+        let range_scheme = match range.range_type.as_str() {
+            "SEMVER" if scheme == VersionScheme::Go => VersionScheme::Go,
+            "SEMVER" => VersionScheme::Semver,
+            "ECOSYSTEM" => scheme,
+            // GIT 範囲はコミットハッシュなので評価できない
+            _ => {
+                if affected.versions.is_empty() {
+                    result = VersionMatch::Unknown;
+                }
+                continue;
+            }
+        };
+        match evaluate_range(range, range_scheme, version) {
+            m @ (VersionMatch::Affected | VersionMatch::Unpinned) => return m,
+            VersionMatch::Unknown => result = VersionMatch::Unknown,
+            VersionMatch::NotAffected => {}
+        }
+    }
+
+    result
+}
+
+/// バージョンを固定していない依存（">=2.0,<3.0" など）を評価
+/// 指定範囲のいずれかのバージョンが影響範囲と重なれば Unpinned（最悪の場合は脆弱）とする
+fn evaluate_specifier(affected: &OsvAffected, spec: &SpecifierRange) -> VersionMatch {
+    if affected.versions.iter().any(|v| spec.contains(v)) {
+        return VersionMatch::Unpinned;
+    }
+
+    let mut result = if affected.versions.is_empty() && affected.ranges.is_empty() {
+        VersionMatch::Unknown
+    } else {
+        VersionMatch::NotAffected
+    };
+    for range in &affected.ranges {
+        let intervals = match range.range_type.as_str() {
+            "ECOSYSTEM" => affected_intervals(range),
+            _ => None,
+        };
+        match intervals {
+            Some(intervals) if intervals.iter().any(|i| spec.overlaps(i)) => {
+                return VersionMatch::Unpinned;
+            }
+            Some(_) => {}
+            None if affected.versions.is_empty() => result = VersionMatch::Unknown,
+            None => {}
+        }
+    }
+    result
+}
+
+/// PEP 440 の範囲のイベントを、影響を受ける区間の一覧に変換
+fn affected_intervals(range: &OsvRange) -> Option<Vec<SpecifierRange>> {
+    let mut events: Vec<(EventKind, &str)> = Vec::new();
+    for event in &range.events {
+        if let Some(ref v) = event.introduced {
+            events.push((EventKind::Introduced, v));
+        }
+        if let Some(ref v) = event.fixed {
+            events.push((EventKind::Fixed, v));
+        }
+        if let Some(ref v) = event.last_affected {
+            events.push((EventKind::LastAffected, v));
+        }
+    }
+    if events
+        .iter()
+        .any(|(_, v)| *v != "0" && pep440::parse(v).is_none())
+    {
+        return None;
+    }
+    events.sort_by(|a, b| match (a.1 == "0", b.1 == "0") {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => pep440::compare(a.1, b.1).unwrap_or(Ordering::Equal),
+    });
+
+    let mut intervals = Vec::new();
+    let mut start: Option<Option<(String, bool)>> = None;
+    for (kind, version) in events {
+        match kind {
+            EventKind::Introduced if start.is_none() => {
+                start = Some((version != "0").then(|| (version.to_string(), true)));
+            }
+            EventKind::Fixed | EventKind::LastAffected => {
+                if let Some(lower) = start.take() {
+                    intervals.push(SpecifierRange {
+                        lower,
+                        upper: Some((version.to_string(), kind == EventKind::LastAffected)),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(lower) = start {
+        intervals.push(SpecifierRange { lower, upper: None });
+    }
+    Some(intervals)
+}
+
+/// 1 つの範囲を評価
+/// イベントをバージョン順に並べ、インストール済みバージョン以下のイベントで状態を更新する
+pub fn evaluate_range(range: &OsvRange, scheme: VersionScheme, version: &str) -> VersionMatch {
+    // バージョン自体を解釈できなければ判定不能
+    if scheme.compare(version, version).is_none() {
+        return VersionMatch::Unknown;
+    }
+
+    let mut events: Vec<(EventKind, &str)> = Vec::new();
+    let mut limits: Vec<&str> = Vec::new();
+    for event in &range.events {
+        if let Some(ref v) = event.introduced {
+            events.push((EventKind::Introduced, v));
+        }
+        if let Some(ref v) = event.fixed {
+            events.push((EventKind::Fixed, v));
+        }
+        if let Some(ref v) = event.last_affected {
+            events.push((EventKind::LastAffected, v));
+        }
+        if let Some(ref v) = event.limit {
+            if v != "*" {
+                limits.push(v);
+            }
+        }
+    }
+
+    // "introduced": "0" はすべてのバージョンより前
+    let cmp = |a: &str, b: &str| -> Option<Ordering> {
+        match (a == "0", b == "0") {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => scheme.compare(a, b),
+        }
+    };
+
+    if events
+        .iter()
+        .map(|(_, v)| *v)
+        .chain(limits.iter().copied())
+        .any(|v| cmp(v, v).is_none())
+    {
+        return VersionMatch::Unknown;
+    }
+    events.sort_by(|a, b| cmp(a.1, b.1).unwrap_or(Ordering::Equal));
+
+    let mut affected = false;
+    for (kind, event_version) in events {
+        let ord = cmp(version, event_version).unwrap_or(Ordering::Less);
+        match kind {
+            EventKind::Introduced if ord != Ordering::Less => affected = true,
+            EventKind::Fixed if ord != Ordering::Less => affected = false,
+            EventKind::LastAffected if ord == Ordering::Greater => affected = false,
+            _ => {}
+        }
+    }
+
+    // limit 以上のバージョンは範囲外
+    if affected
+        && !limits.is_empty()
+        && !limits
+            .iter()
+            .any(|l| cmp(version, l) == Some(Ordering::Less))
+    {
+        affected = false;
+    }
+
+    if affected {
+        VersionMatch::Affected
+    } else {
+        VersionMatch::NotAffected
+    }
+}
+
+/// OSV のパッケージが依存関係と同じか（PyPI は PEP 503 で正規化して比較）
+fn same_package(ecosystem: &str, name: &str, dep: &Dependency) -> bool {
+    let base = ecosystem.split(':').next().unwrap_or(ecosystem);
+    if base != dep.ecosystem {
+        return false;
+    }
+    if base == "PyPI" {
+        purl::normalize_pypi_name(name) == purl::normalize_pypi_name(&dep.name)
+    } else {
+        name == dep.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vuln(json: &str) -> OsvVulnerability {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn dep(name: &str, version: &str, ecosystem: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            version: version.to_string(),
+            ecosystem: ecosystem.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_introduced_fixed() {
+        let v = vuln(
+            r#"{"id": "GHSA-lodash", "affected": [{
+                "package": {"ecosystem": "npm", "name": "lodash"},
+                "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "4.17.21"}]}]
+            }]}"#,
+        );
+        assert_eq!(
+            evaluate(&v, &dep("lodash", "4.17.20", "npm")),
+            VersionMatch::Affected
+        );
+        assert_eq!(
+            evaluate(&v, &dep("lodash", "4.17.21", "npm")),
+            VersionMatch::NotAffected
+        );
+        assert_eq!(
+            evaluate(&v, &dep("lodash", "5.0.0", "npm")),
+            VersionMatch::NotAffected
+        );
+        // 別パッケージのエントリは評価しない
+        assert_eq!(
+            evaluate(&v, &dep("lodash-es", "4.17.20", "npm")),
+            VersionMatch::Unknown
+        );
+    }
+
+    #[test]
+    fn test_multiple_intervals_and_last_affected() {
+        let v = vuln(
+            r#"{"id": "PYSEC-x", "affected": [{
+                "package": {"ecosystem": "PyPI", "name": "Django"},
+                "ranges": [{"type": "ECOSYSTEM", "events": [
+                    {"introduced": "3.2"}, {"fixed": "3.2.19"},
+                    {"introduced": "4.0"}, {"last_affected": "4.1.7"}
+                ]}]
+            }]}"#,
+        );
+        assert_eq!(
+            evaluate(&v, &dep("django", "3.2.18", "PyPI")),
+            VersionMatch::Affected
+        );
+        assert_eq!(
+            evaluate(&v, &dep("django", "3.2.19", "PyPI")),
+            VersionMatch::NotAffected
+        );
+        assert_eq!(
+            evaluate(&v, &dep("django", "3.1", "PyPI")),
+            VersionMatch::NotAffected
+        );
+        assert_eq!(
+            evaluate(&v, &dep("django", "4.1.7", "PyPI")),
+            VersionMatch::Affected
+        );
+        assert_eq!(
+            evaluate(&v, &dep("django", "4.1.8", "PyPI")),
+            VersionMatch::NotAffected
+        );
+        assert_eq!(
+            evaluate(&v, &dep("django", "4.0rc1", "PyPI")),
+            VersionMatch::NotAffected
+        );
+    }
+
+    #[test]
+    fn test_limit_and_maven() {
+        let v = vuln(
+            r#"{"id": "GHSA-log4j", "affected": [{
+                "package": {"ecosystem": "Maven", "name": "org.apache.logging.log4j:log4j-core"},
+                "ranges": [{"type": "ECOSYSTEM", "events": [
+                    {"introduced": "2.0-beta9"}, {"fixed": "2.15.0"}, {"limit": "2.13.0"}
+                ]}]
+            }]}"#,
+        );
+        let name = "org.apache.logging.log4j:log4j-core";
+        assert_eq!(
+            evaluate(&v, &dep(name, "2.12.1", "Maven")),
+            VersionMatch::Affected
+        );
+        assert_eq!(
+            evaluate(&v, &dep(name, "2.14.1", "Maven")),
+            VersionMatch::NotAffected
+        );
+        assert_eq!(
+            evaluate(&v, &dep(name, "2.0-alpha1", "Maven")),
+            VersionMatch::NotAffected
+        );
+    }
+
+    #[test]
+    fn test_versions_list_and_unknown() {
+        let v = vuln(
+            r#"{"id": "GO-x", "affected": [{
+                "package": {"ecosystem": "Go", "name": "github.com/gin-gonic/gin"},
+                "versions": ["v1.9.0"],
+                "ranges": [{"type": "GIT", "repo": "https://github.com/gin-gonic/gin",
+                            "events": [{"introduced": "0"}, {"fixed": "abc123"}]}]
+            }]}"#,
+        );
+        let name = "github.com/gin-gonic/gin";
+        assert_eq!(
+            evaluate(&v, &dep(name, "v1.9.0", "Go")),
+            VersionMatch::Affected
+        );
+        assert_eq!(
+            evaluate(&v, &dep(name, "v1.9.1", "Go")),
+            VersionMatch::NotAffected
+        );
+
+        let git_only = vuln(
+            r#"{"id": "OSV-x", "affected": [{
+                "package": {"ecosystem": "crates.io", "name": "foo"},
+                "ranges": [{"type": "GIT", "events": [{"introduced": "0"}, {"fixed": "abc123"}]}]
+            }]}"#,
+        );
+        assert_eq!(
+            evaluate(&git_only, &dep("foo", "1.0.0", "crates.io")),
+            VersionMatch::Unknown
+        );
+    }
+
+    #[test]
+    fn test_unpinned_specifier() {
+        // 範囲指定の依存は、指定範囲と影響範囲が重なれば未固定として扱う
+        let v = vuln(
+            r#"{"id": "GHSA-y", "affected": [{
+                "package": {"ecosystem": "PyPI", "name": "flask"},
+                "ranges": [{"type": "ECOSYSTEM", "events": [
+                    {"introduced": "0"}, {"fixed": "2.2.5"},
+                    {"introduced": "2.3.0"}, {"fixed": "2.3.2"}
+                ]}]
+            }]}"#,
+        );
+        assert_eq!(
+            evaluate(&v, &dep("flask", ">=2.0", "PyPI")),
+            VersionMatch::Unpinned
+        );
+        assert_eq!(
+            evaluate(&v, &dep("flask", "*", "PyPI")),
+            VersionMatch::Unpinned
+        );
+        assert_eq!(
+            evaluate(&v, &dep("flask", "~=2.3.1", "PyPI")),
+            VersionMatch::Unpinned
+        );
+        assert_eq!(
+            evaluate(&v, &dep("flask", ">=2.2.5,<2.3", "PyPI")),
+            VersionMatch::NotAffected
+        );
+        assert_eq!(
+            evaluate(&v, &dep("flask", ">=2.3.2", "PyPI")),
+            VersionMatch::NotAffected
+        );
+
+        let listed = vuln(
+            r#"{"id": "PYSEC-z", "affected": [{
+                "package": {"ecosystem": "PyPI", "name": "flask"},
+                "versions": ["1.0.2"]
+            }]}"#,
+        );
+        assert_eq!(
+            evaluate(&listed, &dep("flask", "<2", "PyPI")),
+            VersionMatch::Unpinned
+        );
+        assert_eq!(
+            evaluate(&listed, &dep("flask", ">=1.1", "PyPI")),
+            VersionMatch::NotAffected
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! RubyGems バージョンの比較（Gem::Version 相当）
+//! https://guides.rubygems.org/patterns/#prerelease-gems
+//! 英字を含むセグメントはプレリリースとして数値セグメントより前に並ぶ
+
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Num(u64),
+    Str(String),
+}
+
+/// バージョン文字列をセグメントに分解（"1.0.0.pre1" -> [1, 0, 0, "pre", 1]）
+fn parse(version: &str) -> Option<Vec<Segment>> {
+    let version = version.trim();
+    if version.is_empty()
+        || !version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    {
+        return None;
+    }
+
+    // "-" は ".pre." として扱う（Gem::Version と同じ）
+    let version = version.replace('-', ".pre.");
+
+    let mut segments = Vec::new();
+    for part in version.split('.') {
+        if part.is_empty() {
+            return None;
+        }
+        let mut current = String::new();
+        for c in part.chars() {
+            if current
+                .chars()
+                .last()
+                .is_some_and(|last| last.is_ascii_digit() != c.is_ascii_digit())
+            {
+                segments.push(to_segment(&std::mem::take(&mut current))?);
+            }
+            current.push(c);
+        }
+        segments.push(to_segment(&current)?);
+    }
+
+    // 末尾の 0 を除去（"1.0.0" == "1"）。プレリリース部より前の 0 も除去する
+    let first_str = segments
+        .iter()
+        .position(|s| matches!(s, Segment::Str(_)))
+        .unwrap_or(segments.len());
+    let (release, pre) = segments.split_at(first_str);
+    let mut release = release.to_vec();
+    while release.len() > 1 && release.last() == Some(&Segment::Num(0)) {
+        release.pop();
+    }
+    release.extend(pre.iter().cloned());
+
+    Some(release)
+}
+
+fn to_segment(token: &str) -> Option<Segment> {
+    if token.chars().all(|c| c.is_ascii_digit()) {
+        token.parse().ok().map(Segment::Num)
+    } else {
+        Some(Segment::Str(token.to_string()))
+    }
+}
+
+/// 2 つのバージョンを比較
+pub fn compare(a: &str, b: &str) -> Option<Ordering> {
+    let a = parse(a)?;
+    let b = parse(b)?;
+    let len = a.len().max(b.len());
+    for i in 0..len {
+        let left = a.get(i).cloned().unwrap_or(Segment::Num(0));
+        let right = b.get(i).cloned().unwrap_or(Segment::Num(0));
+        let ord = match (&left, &right) {
+            (Segment::Num(x), Segment::Num(y)) => x.cmp(y),
+            (Segment::Str(x), Segment::Str(y)) => x.cmp(y),
+            // プレリリース（英字）は数値より前
+            (Segment::Num(_), Segment::Str(_)) => Ordering::Greater,
+            (Segment::Str(_), Segment::Num(_)) => Ordering::Less,
+        };
+        if ord != Ordering::Equal {
+            return Some(ord);
+        }
+    }
+    Some(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rubygems_ordering() {
+        let ordered = [
+            "1.0.0.a",
+            "1.0.0.a1",
+            "1.0.0.b1",
+            "1.0.0.pre",
+            "1.0.0.rc1",
+            "1.0.0",
+            "1.0.1",
+            "1.10",
+            "7.0.4",
+            "7.0.4.1",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(
+                compare(pair[0], pair[1]),
+                Some(Ordering::Less),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_rubygems_equivalence() {
+        assert_eq!(compare("1.0.0", "1"), Some(Ordering::Equal));
+        assert_eq!(compare("1.0.0-rc1", "1.0.0.pre.rc1"), Some(Ordering::Equal));
+        assert_eq!(compare("1.0.0rc1", "1.0.0.rc.1"), Some(Ordering::Equal));
+        assert_eq!(compare("~> 1.0", "1.0"), None);
+    }
+}
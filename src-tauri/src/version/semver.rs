@@ -0,0 +1,184 @@
+//! SemVer 2.0 の比較
+//! https://semver.org/#spec-item-11
+//! lockfile の表記ゆれに合わせて "v" 接頭辞、省略された minor / patch、4 桁目（NuGet）も受け付ける
+
+use std::cmp::Ordering;
+
+/// 解析済みのバージョン
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    /// 数値部（major, minor, patch[, revision]）
+    pub release: Vec<u64>,
+    /// プレリリース識別子（空なら正式リリース）
+    pub pre: Vec<Identifier>,
+}
+
+/// プレリリース識別子
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl Ord for Identifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Identifier::Numeric(a), Identifier::Numeric(b)) => a.cmp(b),
+            // 数値のみの識別子は英数字の識別子より前
+            (Identifier::Numeric(_), Identifier::AlphaNumeric(_)) => Ordering::Less,
+            (Identifier::AlphaNumeric(_), Identifier::Numeric(_)) => Ordering::Greater,
+            (Identifier::AlphaNumeric(a), Identifier::AlphaNumeric(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.release.len().max(other.release.len());
+        for i in 0..len {
+            let a = self.release.get(i).copied().unwrap_or(0);
+            let b = other.release.get(i).copied().unwrap_or(0);
+            match a.cmp(&b) {
+                Ordering::Equal => continue,
+                ord => return ord,
+            }
+        }
+
+        // プレリリースは正式リリースより前
+        match (self.pre.is_empty(), other.pre.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            // 識別子を順に比較し、すべて等しければ識別子の多い方が後
+            (false, false) => self.pre.cmp(&other.pre),
+        }
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// バージョン文字列を解析
+/// case_insensitive: プレリリース識別子を小文字に揃える（NuGet）
+pub fn parse(version: &str, case_insensitive: bool) -> Option<Version> {
+    let version = version.trim();
+    let version = version
+        .strip_prefix('v')
+        .or_else(|| version.strip_prefix('V'))
+        .or_else(|| version.strip_prefix('='))
+        .unwrap_or(version);
+
+    // ビルドメタデータは比較に使わない
+    let version = version.split_once('+').map_or(version, |(v, _)| v);
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+
+    let release: Vec<u64> = core
+        .split('.')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<_>>()?;
+    if release.is_empty() || release.len() > 4 {
+        return None;
+    }
+
+    let pre = match pre {
+        Some("") => return None,
+        Some(pre) => pre
+            .split('.')
+            .map(|id| parse_identifier(id, case_insensitive))
+            .collect::<Option<_>>()?,
+        None => Vec::new(),
+    };
+
+    Some(Version { release, pre })
+}
+
+fn parse_identifier(id: &str, case_insensitive: bool) -> Option<Identifier> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return None;
+    }
+    if id.chars().all(|c| c.is_ascii_digit()) {
+        return id.parse().ok().map(Identifier::Numeric);
+    }
+    let id = if case_insensitive {
+        id.to_ascii_lowercase()
+    } else {
+        id.to_string()
+    };
+    Some(Identifier::AlphaNumeric(id))
+}
+
+/// 2 つのバージョンを比較
+pub fn compare(a: &str, b: &str, case_insensitive: bool) -> Option<Ordering> {
+    Some(parse(a, case_insensitive)?.cmp(&parse(b, case_insensitive)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cmp(a: &str, b: &str) -> Option<Ordering> {
+        compare(a, b, false)
+    }
+
+    #[test]
+    fn test_semver_precedence() {
+        // semver.org の例: 1.0.0-alpha < 1.0.0-alpha.1 < 1.0.0-alpha.beta < 1.0.0-beta
+        // < 1.0.0-beta.2 < 1.0.0-beta.11 < 1.0.0-rc.1 < 1.0.0
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.10.0",
+            "2.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(
+                cmp(pair[0], pair[1]),
+                Some(Ordering::Less),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_semver_lenient() {
+        assert_eq!(cmp("v1.2.3", "1.2.3"), Some(Ordering::Equal));
+        assert_eq!(cmp("1.2", "1.2.0"), Some(Ordering::Equal));
+        assert_eq!(cmp("1.0.0+build.5", "1.0.0"), Some(Ordering::Equal));
+        assert_eq!(cmp("^1.2.3", "1.2.3"), None);
+        assert_eq!(cmp("1.x", "1.0.0"), None);
+    }
+
+    #[test]
+    fn test_nuget_versions() {
+        assert_eq!(compare("1.0.0.1", "1.0.0", true), Some(Ordering::Greater));
+        assert_eq!(
+            compare("1.0.0-Beta", "1.0.0-beta", true),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare("1.0.0-beta", "1.0.0-RC1", true),
+            Some(Ordering::Less)
+        );
+    }
+}
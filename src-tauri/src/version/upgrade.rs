@@ -0,0 +1,259 @@
+//! アップグレード先の算出
+//! 依存関係に該当する脆弱性の修正バージョンから、すべてを解消する最小のバージョンを選ぶ
+
+use super::range::evaluate_affected;
+use super::{VersionMatch, VersionScheme};
+use crate::api::osv::OsvAffected;
+use std::cmp::Ordering;
+
+/// 1 件の脆弱性について分かっている修正情報
+#[derive(Debug, Clone)]
+pub struct FixInfo<'a> {
+    pub vuln_id: &'a str,
+    /// 修正バージョン（OSV の fixed イベント）
+    pub fixed: Vec<String>,
+    /// 同じパッケージの affected エントリ（あれば候補の判定に使う）
+    pub affected: Vec<&'a OsvAffected>,
+}
+
+/// アップグレードの提案
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpgradeAdvice {
+    /// 解消できる脆弱性をすべて解消する最小のバージョン（修正がなければ None）
+    pub version: Option<String>,
+    /// version で解消される脆弱性
+    pub fixes: Vec<String>,
+    /// version では解消されない脆弱性（修正バージョンが公開されていないなど）
+    pub unfixed: Vec<String>,
+    /// メジャーバージョンをまたぐか
+    pub major_bump: bool,
+}
+
+/// インストール済みバージョンから、該当する脆弱性を解消する最小のバージョンを選ぶ
+/// 候補はインストール済みより新しい修正バージョンで、小さい順に調べる。
+/// どの候補でも解消できない脆弱性は unfixed に残し、残りをすべて解消する最初の候補を採用する
+pub fn advise(ecosystem: &str, installed: &str, vulns: &[FixInfo]) -> UpgradeAdvice {
+    let scheme = VersionScheme::for_ecosystem(ecosystem);
+
+    let mut candidates: Vec<&str> = Vec::new();
+    for fixed in vulns.iter().flat_map(|v| &v.fixed) {
+        if scheme.compare(fixed, installed) != Some(Ordering::Greater) {
+            continue;
+        }
+        if !candidates
+            .iter()
+            .any(|c| scheme.compare(c, fixed) == Some(Ordering::Equal))
+        {
+            candidates.push(fixed);
+        }
+    }
+    candidates.sort_by(|a, b| scheme.compare(a, b).unwrap_or(Ordering::Equal));
+
+    // 候補ごとに解消される脆弱性を調べ、最も多く解消する最小の候補を選ぶ
+    let mut best: Option<(&str, Vec<&str>)> = None;
+    for candidate in candidates {
+        let fixes: Vec<&str> = vulns
+            .iter()
+            .filter(|v| is_fixed_in(v, scheme, ecosystem, installed, candidate))
+            .map(|v| v.vuln_id)
+            .collect();
+        if best.as_ref().is_none_or(|(_, b)| fixes.len() > b.len()) {
+            let all = fixes.len() == vulns.len();
+            best = Some((candidate, fixes));
+            if all {
+                break;
+            }
+        }
+    }
+
+    match best {
+        Some((version, fixes)) => UpgradeAdvice {
+            version: Some(version.to_string()),
+            unfixed: vulns
+                .iter()
+                .map(|v| v.vuln_id)
+                .filter(|id| !fixes.contains(id))
+                .map(str::to_string)
+                .collect(),
+            fixes: fixes.into_iter().map(str::to_string).collect(),
+            major_bump: is_major_bump(scheme, installed, version),
+        },
+        None => UpgradeAdvice {
+            version: None,
+            fixes: Vec::new(),
+            unfixed: vulns.iter().map(|v| v.vuln_id.to_string()).collect(),
+            major_bump: false,
+        },
+    }
+}
+
+/// 候補のバージョンで脆弱性が解消されるか
+/// 影響範囲があればそれで判定し、判定できなければ「インストール済みより新しい修正バージョン以上」とみなす
+fn is_fixed_in(
+    vuln: &FixInfo,
+    scheme: VersionScheme,
+    ecosystem: &str,
+    installed: &str,
+    candidate: &str,
+) -> bool {
+    let results: Vec<VersionMatch> = vuln
+        .affected
+        .iter()
+        .map(|affected| evaluate_affected(affected, ecosystem, candidate))
+        .collect();
+    if results.contains(&VersionMatch::Affected) {
+        return false;
+    }
+    if !results.is_empty() && results.iter().all(|r| *r == VersionMatch::NotAffected) {
+        return true;
+    }
+    vuln.fixed.iter().any(|fixed| {
+        scheme.compare(fixed, installed) == Some(Ordering::Greater)
+            && scheme.compare(fixed, candidate) != Some(Ordering::Greater)
+    })
+}
+
+/// メジャーバージョンをまたぐか
+/// SemVer / Go の 0.x はマイナーバージョンの変更も互換性のない変更として扱う
+pub fn is_major_bump(scheme: VersionScheme, from: &str, to: &str) -> bool {
+    let (from, to) = (release_numbers(from), release_numbers(to));
+    let (Some(from_major), Some(to_major)) = (from.first(), to.first()) else {
+        return false;
+    };
+    if from_major != to_major {
+        return true;
+    }
+    *from_major == 0
+        && matches!(scheme, VersionScheme::Semver | VersionScheme::Go)
+        && from.get(1) != to.get(1)
+}
+
+/// 先頭のリリース番号（"v1.2.3-beta" -> [1, 2, 3]）
+fn release_numbers(version: &str) -> Vec<u64> {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    let end = version
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(version.len());
+    version[..end]
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::osv::{OsvEvent, OsvRange};
+
+    fn range(events: &[(&str, &str)]) -> OsvAffected {
+        OsvAffected {
+            package: None,
+            ranges: vec![OsvRange {
+                range_type: "ECOSYSTEM".to_string(),
+                events: events
+                    .iter()
+                    .map(|(kind, version)| OsvEvent {
+                        introduced: (*kind == "introduced").then(|| version.to_string()),
+                        fixed: (*kind == "fixed").then(|| version.to_string()),
+                        last_affected: None,
+                        limit: None,
+                    })
+                    .collect(),
+            }],
+            versions: Vec::new(),
+            ecosystem_specific: None,
+            database_specific: None,
+        }
+    }
+
+    fn fix<'a>(id: &'a str, fixed: &[&str], affected: Vec<&'a OsvAffected>) -> FixInfo<'a> {
+        FixInfo {
+            vuln_id: id,
+            fixed: fixed.iter().map(|f| f.to_string()).collect(),
+            affected,
+        }
+    }
+
+    #[test]
+    fn test_lowest_version_fixing_all() {
+        let a = range(&[("introduced", "0"), ("fixed", "4.17.12")]);
+        let b = range(&[("introduced", "4.0.0"), ("fixed", "4.17.21")]);
+        let advice = advise(
+            "npm",
+            "4.17.11",
+            &[
+                fix("GHSA-a", &["4.17.12"], vec![&a]),
+                fix("GHSA-b", &["4.17.21"], vec![&b]),
+            ],
+        );
+        assert_eq!(advice.version.as_deref(), Some("4.17.21"));
+        assert_eq!(advice.fixes, vec!["GHSA-a", "GHSA-b"]);
+        assert!(advice.unfixed.is_empty());
+        assert!(!advice.major_bump);
+    }
+
+    #[test]
+    fn test_backported_fix_on_same_branch() {
+        // 1.x と 2.x の両方に修正があれば、同じメジャーの修正を選ぶ
+        let a = range(&[
+            ("introduced", "1.0.0"),
+            ("fixed", "1.2.5"),
+            ("introduced", "2.0.0"),
+            ("fixed", "2.0.3"),
+        ]);
+        let advice = advise(
+            "crates.io",
+            "1.2.0",
+            &[fix("RUSTSEC-a", &["1.2.5", "2.0.3"], vec![&a])],
+        );
+        assert_eq!(advice.version.as_deref(), Some("1.2.5"));
+
+        // 2.0.0 でしか直らない脆弱性があればメジャーをまたぐ
+        let b = range(&[("introduced", "0"), ("fixed", "2.0.0")]);
+        let advice = advise(
+            "crates.io",
+            "1.2.0",
+            &[
+                fix("RUSTSEC-a", &["1.2.5", "2.0.3"], vec![&a]),
+                fix("RUSTSEC-b", &["2.0.0"], vec![&b]),
+            ],
+        );
+        // 2.0.0 は RUSTSEC-a の 2.x 系の影響範囲なので 2.0.3 まで上げる
+        assert_eq!(advice.version.as_deref(), Some("2.0.3"));
+        assert!(advice.major_bump);
+    }
+
+    #[test]
+    fn test_unfixed_and_fallback() {
+        // 影響範囲がない場合は修正バージョン以上で解消とみなす
+        let advice = advise(
+            "PyPI",
+            "2.0",
+            &[
+                fix("PYSEC-a", &["2.0.1"], Vec::new()),
+                fix("PYSEC-b", &[], Vec::new()),
+            ],
+        );
+        assert_eq!(advice.version.as_deref(), Some("2.0.1"));
+        assert_eq!(advice.unfixed, vec!["PYSEC-b"]);
+
+        let advice = advise("npm", "1.0.0", &[fix("GHSA-x", &[], Vec::new())]);
+        assert_eq!(advice.version, None);
+        assert_eq!(advice.unfixed, vec!["GHSA-x"]);
+    }
+
+    #[test]
+    fn test_major_bump() {
+        assert!(is_major_bump(VersionScheme::Semver, "1.9.0", "2.0.0"));
+        assert!(!is_major_bump(VersionScheme::Semver, "1.2.0", "1.9.0"));
+        assert!(is_major_bump(VersionScheme::Semver, "0.7.3", "0.8.0"));
+        assert!(is_major_bump(VersionScheme::Go, "v0.1.0", "v0.2.0"));
+        assert!(!is_major_bump(VersionScheme::Pep440, "0.7", "0.8"));
+        assert!(!is_major_bump(VersionScheme::Maven, "31.1-jre", "31.2-jre"));
+        assert!(is_major_bump(
+            VersionScheme::Maven,
+            "31.1-jre",
+            "32.0.0-jre"
+        ));
+    }
+}
@@ -1,15 +1,18 @@
 import { useState, useCallback } from "react";
 import { open } from "@tauri-apps/plugin-dialog";
-import { IconFolder, IconSearch, IconWarning, IconLightbulb } from "../icons";
+import type { DevFindings } from "../../types/vuln";
+import { IconDocument, IconFolder, IconSearch, IconWarning, IconLightbulb } from "../icons";
 
 interface DirectoryScannerProps {
-  onScan: (path: string) => Promise<void>;
+  onScan: (path: string, offline: boolean, devFindings: DevFindings) => Promise<void>;
   scanning: boolean;
   error: string | null;
 }
 
 export function DirectoryScanner({ onScan, scanning, error }: DirectoryScannerProps) {
   const [selectedPath, setSelectedPath] = useState<string>("");
+  const [offline, setOffline] = useState(false);
+  const [devFindings, setDevFindings] = useState<DevFindings>("include");
 
   const handleSelectDirectory = useCallback(async () => {
     try {
@@ -27,11 +30,28 @@ export function DirectoryScanner({ onScan, scanning, error }: DirectoryScannerPr
     }
   }, []);
 
+  const handleSelectSbom = useCallback(async () => {
+    try {
+      const selected = await open({
+        directory: false,
+        multiple: false,
+        title: "スキャンする SBOM を選択",
+        filters: [{ name: "CycloneDX / SPDX (JSON)", extensions: ["json"] }],
+      });
+
+      if (selected && typeof selected === "string") {
+        setSelectedPath(selected);
+      }
+    } catch (e) {
+      console.error("Failed to open file dialog:", e);
+    }
+  }, []);
+
   const handleScan = useCallback(async () => {
     if (selectedPath) {
-      await onScan(selectedPath);
+      await onScan(selectedPath, offline, devFindings);
     }
-  }, [selectedPath, onScan]);
+  }, [selectedPath, offline, devFindings, onScan]);
 
   const handleInputChange = useCallback((e: React.ChangeEvent<HTMLInputElement>) => {
     setSelectedPath(e.target.value);
@@ -46,7 +66,9 @@ export function DirectoryScanner({ onScan, scanning, error }: DirectoryScannerPr
       
       <p className="scanner-description">
         プロジェクトディレクトリを選択して、依存関係の脆弱性をスキャンします。
-        package-lock.json、Cargo.lock、requirements.txt、go.sum などのロックファイルを自動検出します。
+        package-lock.json、Cargo.lock、requirements.txt、go.mod などのロックファイルを自動検出します。
+        サブディレクトリも再帰的に探索し（node_modules、target、vendor、.git は除外）、モノレポではサブプロジェクトごとに結果をまとめます。
+        ロックファイルがない場合は、CycloneDX / SPDX 形式の SBOM（JSON）を直接スキャンすることもできます。
       </p>
 
       <div className="scanner-input-group">
@@ -54,7 +76,7 @@ export function DirectoryScanner({ onScan, scanning, error }: DirectoryScannerPr
           type="text"
           value={selectedPath}
           onChange={handleInputChange}
-          placeholder="ディレクトリパスを入力または選択..."
+          placeholder="ディレクトリ / SBOM のパスを入力または選択..."
           className="scanner-path-input"
           disabled={scanning}
         />
@@ -68,6 +90,16 @@ export function DirectoryScanner({ onScan, scanning, error }: DirectoryScannerPr
           <IconFolder size={16} />
           選択
         </button>
+        <button
+          type="button"
+          onClick={handleSelectSbom}
+          disabled={scanning}
+          className="btn-secondary"
+          title="SBOM ファイルを選択"
+        >
+          <IconDocument size={16} />
+          SBOM
+        </button>
         <button
           type="button"
           onClick={handleScan}
@@ -88,6 +120,29 @@ export function DirectoryScanner({ onScan, scanning, error }: DirectoryScannerPr
         </button>
       </div>
 
+      <label className="scanner-option">
+        <input
+          type="checkbox"
+          checked={offline}
+          onChange={(e) => setOffline(e.target.checked)}
+          disabled={scanning}
+        />
+        オフライン（OSV API を使わず、取り込み済みの OSV データベースと照合）
+      </label>
+
+      <label className="scanner-option">
+        開発用の依存関係:
+        <select
+          value={devFindings}
+          onChange={(e) => setDevFindings(e.target.value as DevFindings)}
+          disabled={scanning}
+        >
+          <option value="include">含める</option>
+          <option value="deprioritize">後回し（本番の検出の後に表示）</option>
+          <option value="exclude">除外</option>
+        </select>
+      </label>
+
       {error && (
         <div className="scanner-error">
           <IconWarning size={16} className="inline-icon" />
@@ -99,8 +154,8 @@ export function DirectoryScanner({ onScan, scanning, error }: DirectoryScannerPr
         <h4>対応パッケージマネージャー</h4>
         <ul className="supported-list">
           <li>
-            <span className="ecosystem-tag npm">npm/yarn</span>
-            package-lock.json, pnpm-lock.yaml, yarn.lock
+            <span className="ecosystem-tag npm">npm/yarn/bun</span>
+            package-lock.json, pnpm-lock.yaml, yarn.lock (v1 / Berry), bun.lock
           </li>
           <li>
             <span className="ecosystem-tag cargo">Cargo</span>
@@ -108,7 +163,7 @@ export function DirectoryScanner({ onScan, scanning, error }: DirectoryScannerPr
           </li>
           <li>
             <span className="ecosystem-tag pip">pip</span>
-            requirements.txt, poetry.lock, Pipfile.lock
+            requirements.txt, poetry.lock, Pipfile.lock, uv.lock, pdm.lock, pyproject.toml
             <span 
               className="ecosystem-tip" 
               data-tip="requirements.txt は生成方法により間接依存が含まれない場合があります。&#10;&#10;• pip freeze: 間接依存を含む（推奨）&#10;• 手動作成: 直接依存のみ&#10;&#10;包括的なスキャンには poetry.lock や Pipfile.lock を推奨します。"
@@ -118,11 +173,11 @@ export function DirectoryScanner({ onScan, scanning, error }: DirectoryScannerPr
           </li>
           <li>
             <span className="ecosystem-tag go">Go</span>
-            go.sum
+            go.mod, go.sum
           </li>
           <li>
             <span className="ecosystem-tag maven">Maven/Gradle</span>
-            pom.xml, gradle.lockfile
+            pom.xml, gradle.lockfile, gradle/libs.versions.toml
           </li>
           <li>
             <span className="ecosystem-tag nuget">NuGet</span>
@@ -152,6 +207,10 @@ export function DirectoryScanner({ onScan, scanning, error }: DirectoryScannerPr
             <span className="ecosystem-tag swift">SwiftPM</span>
             Package.resolved
           </li>
+          <li>
+            <span className="ecosystem-tag sbom">SBOM</span>
+            CycloneDX / SPDX (JSON, purl)
+          </li>
         </ul>
       </div>
     </div>
@@ -41,8 +41,9 @@ export function ScanResultList({ result, onClear }: ScanResultListProps) {
       high: groupedBySevetity.high.length,
       medium: groupedBySevetity.medium.length,
       low: groupedBySevetity.low.length,
+      suppressed: result.vulnerabilities.filter((m) => m.suppression).length,
     };
-  }, [result.vulnerabilities.length, groupedBySevetity]);
+  }, [result.vulnerabilities, groupedBySevetity]);
 
   const formatDate = (dateStr: string) => {
     try {
@@ -132,6 +133,11 @@ export function ScanResultList({ result, onClear }: ScanResultListProps) {
                   注意: {stats.low}
                 </span>
               )}
+              {stats.suppressed > 0 && (
+                <span className="stat-badge suppressed">
+                  抑制済み: {stats.suppressed}
+                </span>
+              )}
             </div>
           </>
         ) : (
@@ -142,6 +148,77 @@ export function ScanResultList({ result, onClear }: ScanResultListProps) {
         )}
       </div>
 
+      {result.projects.length > 1 && (
+        <table className="scan-result-projects">
+          <thead>
+            <tr>
+              <th>サブプロジェクト</th>
+              <th>エコシステム</th>
+              <th>パッケージ数</th>
+              <th>脆弱性</th>
+            </tr>
+          </thead>
+          <tbody>
+            {result.projects.map((project) => (
+              <tr key={project.path}>
+                <td className="project-path">{project.path}</td>
+                <td>{project.ecosystems.join(", ")}</td>
+                <td>{project.totalPackages}</td>
+                <td>{project.vulnCount}</td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+
+      {result.remediation.length > 0 && (
+        <table className="scan-result-remediation">
+          <thead>
+            <tr>
+              <th>パッケージ</th>
+              <th>現在</th>
+              <th>アップグレード先</th>
+              <th>解消する脆弱性</th>
+            </tr>
+          </thead>
+          <tbody>
+            {result.remediation.map((r) => (
+              <tr key={`${r.project}-${r.sourceFile}-${r.packageName}-${r.installedVersion}`}>
+                <td className="package-name">
+                  {r.project !== "." && <span className="project-path">{r.project}: </span>}
+                  {r.packageName}
+                </td>
+                <td>{r.installedVersion}</td>
+                <td>
+                  {r.targetVersion ?? <span className="no-fix">修正なし</span>}
+                  {r.majorBump && <span className="major-bump">メジャー更新</span>}
+                </td>
+                <td>
+                  {r.fixes.join(", ")}
+                  {r.unfixed.length > 0 && (
+                    <span className="no-fix"> 未解消: {r.unfixed.join(", ")}</span>
+                  )}
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+
+      {result.warnings.length > 0 && (
+        <details className="scan-result-warnings">
+          <summary>
+            <IconWarning size={16} className="inline-icon" />
+            スキャン対象外: {result.warnings.length} 件
+          </summary>
+          <ul>
+            {result.warnings.map((warning) => (
+              <li key={warning}>{warning}</li>
+            ))}
+          </ul>
+        </details>
+      )}
+
       {hasVulnerabilities && (
         <div className="scan-result-groups">
           {groupedBySevetity.critical.length > 0 && (
@@ -153,9 +230,14 @@ export function ScanResultList({ result, onClear }: ScanResultListProps) {
               <div className="group-cards">
                 {groupedBySevetity.critical.map((match) => (
                   <VulnCard
-                    key={`${match.vulnerability.id}-${match.packageName}`}
+                    key={`${match.project}-${match.vulnerability.id}-${match.packageName}`}
                     vulnerability={match.vulnerability}
                     installedVersion={match.installedVersion}
+                    project={match.project}
+                    versionMatch={match.versionMatch}
+                    suppression={match.suppression}
+                    dependencyPath={match.dependencyPath}
+                    dev={match.dev}
                     onViewDetail={handleViewDetail}
                   />
                 ))}
@@ -172,9 +254,14 @@ export function ScanResultList({ result, onClear }: ScanResultListProps) {
               <div className="group-cards">
                 {groupedBySevetity.high.map((match) => (
                   <VulnCard
-                    key={`${match.vulnerability.id}-${match.packageName}`}
+                    key={`${match.project}-${match.vulnerability.id}-${match.packageName}`}
                     vulnerability={match.vulnerability}
                     installedVersion={match.installedVersion}
+                    project={match.project}
+                    versionMatch={match.versionMatch}
+                    suppression={match.suppression}
+                    dependencyPath={match.dependencyPath}
+                    dev={match.dev}
                     onViewDetail={handleViewDetail}
                   />
                 ))}
@@ -191,9 +278,14 @@ export function ScanResultList({ result, onClear }: ScanResultListProps) {
               <div className="group-cards">
                 {groupedBySevetity.medium.map((match) => (
                   <VulnCard
-                    key={`${match.vulnerability.id}-${match.packageName}`}
+                    key={`${match.project}-${match.vulnerability.id}-${match.packageName}`}
                     vulnerability={match.vulnerability}
                     installedVersion={match.installedVersion}
+                    project={match.project}
+                    versionMatch={match.versionMatch}
+                    suppression={match.suppression}
+                    dependencyPath={match.dependencyPath}
+                    dev={match.dev}
                     onViewDetail={handleViewDetail}
                   />
                 ))}
@@ -210,9 +302,14 @@ export function ScanResultList({ result, onClear }: ScanResultListProps) {
               <div className="group-cards">
                 {groupedBySevetity.low.map((match) => (
                   <VulnCard
-                    key={`${match.vulnerability.id}-${match.packageName}`}
+                    key={`${match.project}-${match.vulnerability.id}-${match.packageName}`}
                     vulnerability={match.vulnerability}
                     installedVersion={match.installedVersion}
+                    project={match.project}
+                    versionMatch={match.versionMatch}
+                    suppression={match.suppression}
+                    dependencyPath={match.dependencyPath}
+                    dev={match.dev}
                     onViewDetail={handleViewDetail}
                   />
                 ))}
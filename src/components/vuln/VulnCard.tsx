@@ -1,4 +1,4 @@
-import type { Vulnerability, Severity } from "../../types/vuln";
+import type { Vulnerability, Severity, Suppression, VersionMatch } from "../../types/vuln";
 import { getEcosystemLabel } from "../../types/vuln";
 import { SeverityBadge } from "./SeverityBadge";
 import { IconExternalLink, IconShield } from "../icons";
@@ -6,10 +6,29 @@ import { IconExternalLink, IconShield } from "../icons";
 interface VulnCardProps {
   vulnerability: Vulnerability;
   installedVersion?: string;
+  /** スキャン時のサブプロジェクト（トップレベルは "."） */
+  project?: string;
+  /** スキャン時の影響範囲の判定結果 */
+  versionMatch?: VersionMatch;
+  /** スキャン時に適用された抑制 */
+  suppression?: Suppression | null;
+  /** 直接依存から脆弱なパッケージまでの経路 */
+  dependencyPath?: string[];
+  /** 開発時のみの依存関係で見つかった検出 */
+  dev?: boolean;
   onViewDetail?: (vuln: Vulnerability) => void;
 }
 
-export function VulnCard({ vulnerability, installedVersion, onViewDetail }: VulnCardProps) {
+export function VulnCard({
+  vulnerability,
+  installedVersion,
+  project,
+  versionMatch,
+  suppression,
+  dependencyPath,
+  dev,
+  onViewDetail,
+}: VulnCardProps) {
   const handleClick = () => {
     onViewDetail?.(vulnerability);
   };
@@ -29,7 +48,7 @@ export function VulnCard({ vulnerability, installedVersion, onViewDetail }: Vuln
   };
 
   return (
-    <div className="vuln-card" onClick={handleClick}>
+    <div className={`vuln-card ${suppression ? "suppressed" : ""}`} onClick={handleClick}>
       <div className="vuln-card-header">
         <div className="vuln-card-id">
           <IconShield size={16} className="inline-icon" />
@@ -40,6 +59,37 @@ export function VulnCard({ vulnerability, installedVersion, onViewDetail }: Vuln
 
       <h3 className="vuln-card-title">{vulnerability.title}</h3>
 
+      {versionMatch === "not_affected" && (
+        <div
+          className="vuln-out-of-range"
+          title="インストール済みバージョンは OSV の影響範囲に含まれていません（誤検知の可能性）"
+        >
+          影響範囲外の可能性
+        </div>
+      )}
+
+      {versionMatch === "unpinned" && (
+        <div
+          className="vuln-unpinned"
+          title="バージョンが範囲で指定されており、範囲内のいずれかのバージョンが影響を受けます（インストールされるバージョン次第）"
+        >
+          バージョン未固定
+        </div>
+      )}
+
+      {dev && (
+        <div className="vuln-dev-only" title="開発時のみの依存関係（devDependencies など）で見つかった検出">
+          開発用の依存関係
+        </div>
+      )}
+
+      {suppression && (
+        <div className="vuln-suppressed" title={suppression.reason}>
+          抑制済み: {suppression.reason}
+          {suppression.expires && `（${suppression.expires} まで）`}
+        </div>
+      )}
+
       <div className="vuln-card-meta">
         <div className="vuln-meta-item">
           <span className="meta-label">パッケージ:</span>
@@ -57,6 +107,12 @@ export function VulnCard({ vulnerability, installedVersion, onViewDetail }: Vuln
             <span className="meta-value version-installed">{installedVersion}</span>
           </div>
         )}
+        {project && project !== "." && (
+          <div className="vuln-meta-item">
+            <span className="meta-label">プロジェクト:</span>
+            <span className="meta-value">{project}</span>
+          </div>
+        )}
         {vulnerability.cvssScore && (
           <div className="vuln-meta-item">
             <span className="meta-label">CVSS:</span>
@@ -65,6 +121,13 @@ export function VulnCard({ vulnerability, installedVersion, onViewDetail }: Vuln
         )}
       </div>
 
+      {dependencyPath && dependencyPath.length > 1 && (
+        <div className="vuln-dependency-path" title="直接依存からの経路">
+          <span className="path-label">依存経路:</span>
+          <span className="path-value">{dependencyPath.join(" › ")}</span>
+        </div>
+      )}
+
       {vulnerability.affectedVersions && (
         <div className="vuln-versions">
           <span className="versions-label">影響バージョン:</span>
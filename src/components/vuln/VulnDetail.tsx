@@ -1,6 +1,6 @@
 import { useEffect, useCallback } from "react";
 import type { Vulnerability, Severity } from "../../types/vuln";
-import { getEcosystemLabel } from "../../types/vuln";
+import { getEcosystemLabel, getSeveritySourceLabel } from "../../types/vuln";
 import { SeverityBadge } from "./SeverityBadge";
 import { IconClose, IconExternalLink, IconShield } from "../icons";
 
@@ -72,6 +72,17 @@ export function VulnDetail({ vulnerability, onClose }: VulnDetailProps) {
         <div className="vuln-detail-content">
           <h3 className="vuln-detail-title">{vulnerability.title}</h3>
 
+          {vulnerability.aliases?.length > 0 && (
+            <p className="vuln-detail-aliases">
+              <span className="meta-label">別名</span>
+              {vulnerability.aliases.map((alias) => (
+                <code key={alias} className="vuln-alias">
+                  {alias}
+                </code>
+              ))}
+            </p>
+          )}
+
           <div className="vuln-detail-meta">
             <div className="vuln-detail-meta-item">
               <span className="meta-label">パッケージ</span>
@@ -83,14 +94,28 @@ export function VulnDetail({ vulnerability, onClose }: VulnDetailProps) {
                 {getEcosystemLabel(vulnerability.affectedEcosystem)}
               </span>
             </div>
+            <div className="vuln-detail-meta-item">
+              <span className="meta-label">深刻度の算出元</span>
+              <span className="meta-value">
+                {getSeveritySourceLabel(vulnerability.severitySource)}
+              </span>
+            </div>
             {vulnerability.cvssScore && (
               <div className="vuln-detail-meta-item">
-                <span className="meta-label">CVSS スコア</span>
+                <span className="meta-label">
+                  CVSS {vulnerability.cvssVersion ? `v${vulnerability.cvssVersion} ` : ""}スコア
+                </span>
                 <span className="meta-value cvss-score">
                   {vulnerability.cvssScore.toFixed(1)}
                 </span>
               </div>
             )}
+            {vulnerability.cvssVector && (
+              <div className="vuln-detail-meta-item">
+                <span className="meta-label">CVSS ベクター</span>
+                <code className="meta-value cvss-vector">{vulnerability.cvssVector}</code>
+              </div>
+            )}
             <div className="vuln-detail-meta-item">
               <span className="meta-label">公開日</span>
               <span className="meta-value">
@@ -112,6 +137,26 @@ export function VulnDetail({ vulnerability, onClose }: VulnDetailProps) {
             </div>
           )}
 
+          {vulnerability.affectedPackages?.length > 1 && (
+            <div className="vuln-detail-section">
+              <h4>影響を受けるパッケージ</h4>
+              <ul className="vuln-affected-packages">
+                {vulnerability.affectedPackages.map((pkg) => (
+                  <li key={`${pkg.ecosystem}:${pkg.name}`}>
+                    <span className="affected-package-name">{pkg.name}</span>
+                    <span className="affected-package-ecosystem">
+                      {getEcosystemLabel(pkg.ecosystem)}
+                    </span>
+                    <span className="affected-package-versions">
+                      {pkg.affectedVersions || "情報なし"}
+                      {pkg.fixedVersions && ` → ${pkg.fixedVersions}`}
+                    </span>
+                  </li>
+                ))}
+              </ul>
+            </div>
+          )}
+
           <div className="vuln-detail-section">
             <h4>影響を受けるバージョン</h4>
             <p className="vuln-versions">
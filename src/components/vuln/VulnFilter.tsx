@@ -1,12 +1,14 @@
 import { useState, useCallback } from "react";
-import type { VulnFilter as VulnFilterType } from "../../types/vuln";
+import { open } from "@tauri-apps/plugin-dialog";
+import type { OsvImportSummary, VulnFilter as VulnFilterType } from "../../types/vuln";
 import { ECOSYSTEMS, SEVERITIES } from "../../types/vuln";
-import { IconRefresh, IconSearch } from "../icons";
+import { IconDocument, IconRefresh, IconSearch } from "../icons";
 
 interface VulnFilterProps {
   filter: VulnFilterType;
   onFilterChange: (filter: VulnFilterType) => void;
   onRefresh: (ecosystems: string[]) => Promise<number>;
+  onImport: (path: string) => Promise<OsvImportSummary | null>;
   vulnCount: number;
   loading: boolean;
 }
@@ -15,11 +17,13 @@ export function VulnFilter({
   filter,
   onFilterChange,
   onRefresh,
+  onImport,
   vulnCount,
   loading,
 }: VulnFilterProps) {
   const [searchInput, setSearchInput] = useState(filter.search || "");
   const [refreshing, setRefreshing] = useState(false);
+  const [importSummary, setImportSummary] = useState<OsvImportSummary | null>(null);
 
   const handleEcosystemChange = useCallback(
     (e: React.ChangeEvent<HTMLSelectElement>) => {
@@ -65,6 +69,27 @@ export function VulnFilter({
     }
   }, [filter.ecosystem, onRefresh]);
 
+  const handleImport = useCallback(async () => {
+    try {
+      const selected = await open({
+        directory: false,
+        multiple: false,
+        title: "OSV データベース（all.zip）を選択",
+        filters: [{ name: "OSV export", extensions: ["zip"] }],
+      });
+      if (!selected || typeof selected !== "string") {
+        return;
+      }
+
+      setRefreshing(true);
+      setImportSummary(await onImport(selected));
+    } catch (e) {
+      console.error("Failed to open file dialog:", e);
+    } finally {
+      setRefreshing(false);
+    }
+  }, [onImport]);
+
   const isLoading = loading || refreshing;
 
   return (
@@ -127,12 +152,30 @@ export function VulnFilter({
           <IconRefresh size={16} className={isLoading ? "spinning" : ""} />
           更新
         </button>
+
+        <button
+          type="button"
+          onClick={handleImport}
+          disabled={isLoading}
+          className="refresh-btn"
+          title="OSV のデータベースエクスポート（all.zip）を取り込み、オフラインスキャンに使用"
+        >
+          <IconDocument size={16} />
+          取り込み
+        </button>
       </div>
 
       <div className="vuln-filter-info">
         <span className="vuln-count">
           {vulnCount.toLocaleString()} 件の脆弱性
         </span>
+        {importSummary && (
+          <span className="vuln-import-summary">
+            {importSummary.imported.toLocaleString()} 件を取り込みました
+            {importSummary.ecosystems.length > 0 && `（${importSummary.ecosystems.join(", ")}）`}
+            {importSummary.skipped > 0 && ` / ${importSummary.skipped} 件は解析できませんでした`}
+          </span>
+        )}
       </div>
     </div>
   );
@@ -1,5 +1,5 @@
 import { useState, useCallback } from "react";
-import type { Vulnerability } from "../../types/vuln";
+import type { DevFindings, Vulnerability } from "../../types/vuln";
 import { useVulnerabilities } from "../../hooks/useVulnerabilities";
 import { useVulnScanner } from "../../hooks/useVulnScanner";
 import { VulnFilter } from "./VulnFilter";
@@ -7,6 +7,7 @@ import { VulnCard } from "./VulnCard";
 import { VulnDetail } from "./VulnDetail";
 import { DirectoryScanner } from "./DirectoryScanner";
 import { ScanResultList } from "./ScanResultList";
+import { WatchedProjects } from "./WatchedProjects";
 import { IconWarning, IconShield, IconSearch } from "../icons";
 
 type VulnSubTab = "list" | "scan";
@@ -26,6 +27,7 @@ export function VulnList() {
     setFilter,
     setPage,
     fetchFromApi,
+    importOsvArchive,
   } = useVulnerabilities(20);
 
   const {
@@ -44,8 +46,8 @@ export function VulnList() {
     setSelectedVuln(null);
   }, []);
 
-  const handleScan = useCallback(async (path: string) => {
-    await scanDirectory(path);
+  const handleScan = useCallback(async (path: string, offline: boolean, devFindings: DevFindings) => {
+    await scanDirectory(path, offline, devFindings);
   }, [scanDirectory]);
 
   const totalPages = Math.ceil(total / limit);
@@ -79,6 +81,7 @@ export function VulnList() {
             filter={filter}
             onFilterChange={setFilter}
             onRefresh={fetchFromApi}
+            onImport={importOsvArchive}
             vulnCount={total}
             loading={loading}
           />
@@ -148,11 +151,14 @@ export function VulnList() {
       {subTab === "scan" && (
         <>
           {!scanResult ? (
-            <DirectoryScanner
-              onScan={handleScan}
-              scanning={scanning}
-              error={scanError}
-            />
+            <>
+              <DirectoryScanner
+                onScan={handleScan}
+                scanning={scanning}
+                error={scanError}
+              />
+              <WatchedProjects />
+            </>
           ) : (
             <ScanResultList result={scanResult} onClear={clearScanResult} />
           )}
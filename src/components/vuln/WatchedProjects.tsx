@@ -0,0 +1,109 @@
+import { useState, useCallback } from "react";
+import { open } from "@tauri-apps/plugin-dialog";
+import { useWatchedProjects } from "../../hooks/useWatchedProjects";
+import { IconFolder, IconRefresh, IconTrash, IconWarning } from "../icons";
+
+const INTERVAL_OPTIONS = [
+  { hours: 6, label: "6 時間ごと" },
+  { hours: 24, label: "毎日" },
+  { hours: 168, label: "毎週" },
+];
+
+export function WatchedProjects() {
+  const { projects, error, addProject, setEnabled, removeProject } = useWatchedProjects();
+  const [intervalHours, setIntervalHours] = useState(24);
+
+  const handleAdd = useCallback(async () => {
+    try {
+      const selected = await open({
+        directory: true,
+        multiple: false,
+        title: "定期スキャンするディレクトリを選択",
+      });
+
+      if (selected && typeof selected === "string") {
+        await addProject(selected, intervalHours);
+      }
+    } catch (e) {
+      console.error("Failed to open directory dialog:", e);
+    }
+  }, [addProject, intervalHours]);
+
+  const intervalLabel = (hours: number) =>
+    INTERVAL_OPTIONS.find((o) => o.hours === hours)?.label ?? `${hours} 時間ごと`;
+
+  return (
+    <div className="watched-projects">
+      <h3 className="scanner-title">
+        <IconRefresh size={20} className="inline-icon" />
+        定期スキャン
+      </h3>
+
+      <p className="scanner-description">
+        登録したディレクトリをバックグラウンドで定期的に再スキャンし、前回より新しい High / Critical の脆弱性が見つかるとデスクトップ通知でお知らせします。
+      </p>
+
+      <div className="scanner-input-group">
+        <select
+          value={intervalHours}
+          onChange={(e) => setIntervalHours(Number(e.target.value))}
+          className="watched-interval"
+        >
+          {INTERVAL_OPTIONS.map((o) => (
+            <option key={o.hours} value={o.hours}>
+              {o.label}
+            </option>
+          ))}
+        </select>
+        <button type="button" onClick={handleAdd} className="btn-secondary">
+          <IconFolder size={16} />
+          ディレクトリを追加
+        </button>
+      </div>
+
+      {error && (
+        <div className="scanner-error">
+          <IconWarning size={16} className="inline-icon" />
+          {error}
+        </div>
+      )}
+
+      {projects.length > 0 && (
+        <ul className="watched-list">
+          {projects.map((project) => (
+            <li key={project.id} className={`watched-item ${project.enabled ? "" : "disabled"}`}>
+              <label className="watched-toggle">
+                <input
+                  type="checkbox"
+                  checked={project.enabled}
+                  onChange={(e) => setEnabled(project.id, e.target.checked)}
+                />
+                <span className="project-path">{project.directory}</span>
+              </label>
+              <span className="watched-meta">
+                {intervalLabel(project.intervalHours)}
+                {project.lastScannedAt
+                  ? ` ・ 最終スキャン: ${project.lastScannedAt}`
+                  : " ・ 未スキャン"}
+              </span>
+              {project.lastError && (
+                <span className="watched-error">
+                  <IconWarning size={14} className="inline-icon" />
+                  {project.lastError}
+                </span>
+              )}
+              <button
+                type="button"
+                className="btn-secondary watched-remove"
+                onClick={() => removeProject(project.id)}
+                title="定期スキャンから外す"
+              >
+                <IconTrash size={14} />
+              </button>
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
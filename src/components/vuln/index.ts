@@ -4,4 +4,5 @@ export { VulnDetail } from "./VulnDetail";
 export { VulnFilter } from "./VulnFilter";
 export { DirectoryScanner } from "./DirectoryScanner";
 export { ScanResultList } from "./ScanResultList";
+export { WatchedProjects } from "./WatchedProjects";
 export { SeverityBadge } from "./SeverityBadge";
@@ -1,6 +1,6 @@
 import { useState, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { ScanResult, ScanHistory, Vulnerability } from "../types/vuln";
+import type { DevFindings, ScanResult, ScanHistory, Vulnerability } from "../types/vuln";
 
 export interface UseVulnScannerResult {
   scanResult: ScanResult | null;
@@ -8,7 +8,7 @@ export interface UseVulnScannerResult {
   scanning: boolean;
   loadingHistory: boolean;
   error: string | null;
-  scanDirectory: (path: string) => Promise<ScanResult | null>;
+  scanDirectory: (path: string, offline?: boolean, devFindings?: DevFindings) => Promise<ScanResult | null>;
   loadScanHistory: (limit?: number) => Promise<void>;
   getVulnerabilityDetail: (vulnId: string) => Promise<Vulnerability | null>;
   clearScanResult: () => void;
@@ -21,12 +21,16 @@ export function useVulnScanner(): UseVulnScannerResult {
   const [loadingHistory, setLoadingHistory] = useState(false);
   const [error, setError] = useState<string | null>(null);
 
-  const scanDirectory = useCallback(async (path: string): Promise<ScanResult | null> => {
+  const scanDirectory = useCallback(async (
+    path: string,
+    offline = false,
+    devFindings: DevFindings = "include",
+  ): Promise<ScanResult | null> => {
     setScanning(true);
     setError(null);
 
     try {
-      const result = await invoke<ScanResult>("scan_directory", { path });
+      const result = await invoke<ScanResult>("scan_directory", { path, offline, devFindings });
       setScanResult(result);
       return result;
     } catch (e) {
@@ -1,6 +1,7 @@
 import { useState, useCallback, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  OsvImportSummary,
   Vulnerability,
   VulnFilter,
   VulnListResponse,
@@ -18,6 +19,7 @@ export interface UseVulnerabilitiesResult {
   setPage: (page: number) => void;
   refresh: () => Promise<void>;
   fetchFromApi: (ecosystems: string[]) => Promise<number>;
+  importOsvArchive: (path: string) => Promise<OsvImportSummary | null>;
 }
 
 export function useVulnerabilities(initialLimit = 20): UseVulnerabilitiesResult {
@@ -74,6 +76,27 @@ export function useVulnerabilities(initialLimit = 20): UseVulnerabilitiesResult
     }
   }, [loadVulnerabilities]);
 
+  const importOsvArchive = useCallback(async (path: string): Promise<OsvImportSummary | null> => {
+    setLoading(true);
+    setError(null);
+
+    try {
+      const summary = await invoke<OsvImportSummary>("import_osv_archive", { path });
+
+      // Reload after importing
+      await loadVulnerabilities();
+
+      return summary;
+    } catch (e) {
+      const errorMessage = e instanceof Error ? e.message : String(e);
+      setError(errorMessage);
+      console.error("Failed to import OSV archive:", e);
+      return null;
+    } finally {
+      setLoading(false);
+    }
+  }, [loadVulnerabilities]);
+
   const handleSetFilter = useCallback((newFilter: VulnFilter) => {
     setFilter(newFilter);
     setPage(1); // Reset to first page when filter changes
@@ -100,5 +123,6 @@ export function useVulnerabilities(initialLimit = 20): UseVulnerabilitiesResult
     setPage: handleSetPage,
     refresh: loadVulnerabilities,
     fetchFromApi,
+    importOsvArchive,
   };
 }
@@ -0,0 +1,77 @@
+import { useState, useCallback, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { WatchedProject } from "../types/vuln";
+
+export interface UseWatchedProjectsResult {
+  projects: WatchedProject[];
+  loading: boolean;
+  error: string | null;
+  addProject: (directory: string, intervalHours: number) => Promise<boolean>;
+  setEnabled: (id: number, enabled: boolean) => Promise<void>;
+  removeProject: (id: number) => Promise<void>;
+  refresh: () => Promise<void>;
+}
+
+export function useWatchedProjects(): UseWatchedProjectsResult {
+  const [projects, setProjects] = useState<WatchedProject[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    setLoading(true);
+    try {
+      const result = await invoke<WatchedProject[]>("get_watched_projects");
+      setProjects(result);
+    } catch (e) {
+      console.error("Failed to load watched projects:", e);
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  const addProject = useCallback(async (directory: string, intervalHours: number): Promise<boolean> => {
+    setError(null);
+    try {
+      await invoke<number>("add_watched_project", { directory, intervalHours });
+      await refresh();
+      return true;
+    } catch (e) {
+      const errorMessage = e instanceof Error ? e.message : String(e);
+      setError(errorMessage);
+      console.error("Failed to add watched project:", e);
+      return false;
+    }
+  }, [refresh]);
+
+  const setEnabled = useCallback(async (id: number, enabled: boolean) => {
+    try {
+      await invoke("set_watched_project_enabled", { id, enabled });
+      await refresh();
+    } catch (e) {
+      console.error("Failed to update watched project:", e);
+    }
+  }, [refresh]);
+
+  const removeProject = useCallback(async (id: number) => {
+    try {
+      await invoke("delete_watched_project", { id });
+      await refresh();
+    } catch (e) {
+      console.error("Failed to remove watched project:", e);
+    }
+  }, [refresh]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  return {
+    projects,
+    loading,
+    error,
+    addProject,
+    setEnabled,
+    removeProject,
+    refresh,
+  };
+}
@@ -1,7 +1,10 @@
 // 脆弱性関連の型定義
 
 /** 脆弱性の深刻度 */
-export type Severity = "critical" | "high" | "medium" | "low";
+export type Severity = "critical" | "high" | "medium" | "low" | "unknown";
+
+/** 深刻度の算出元 */
+export type SeveritySource = "cvss" | "database_specific" | "ecosystem_specific" | "unknown";
 
 /** データソース */
 export type VulnSource = "osv" | "nvd" | "github";
@@ -26,7 +29,13 @@ export interface Vulnerability {
   id: string;
   source: VulnSource;
   severity: Severity;
+  /** 深刻度の算出元 */
+  severitySource: SeveritySource | null;
   cvssScore: number | null;
+  /** スコアの算出元の CVSS ベクター */
+  cvssVector: string | null;
+  /** CVSS のバージョン（"2.0" / "3.0" / "3.1" / "4.0"） */
+  cvssVersion: string | null;
   title: string;
   description: string | null;
   affectedPackage: string;
@@ -34,8 +43,23 @@ export interface Vulnerability {
   affectedVersions: string | null;
   fixedVersions: string | null;
   publishedAt: string | null;
+  /** OSV レコードの最終更新日時 */
+  modified: string | null;
   references: string[];
   fetchedAt: string | null;
+  /** アドバイザリが影響するすべてのパッケージ（affectedPackage はその代表） */
+  affectedPackages: AffectedPackage[];
+  /** 同じ問題を指す別の ID（CVE / GHSA / RUSTSEC など） */
+  aliases: string[];
+}
+
+/** 脆弱性が影響するパッケージ */
+export interface AffectedPackage {
+  ecosystem: string;
+  name: string;
+  purl: string | null;
+  affectedVersions: string | null;
+  fixedVersions: string | null;
 }
 
 /** 脆弱性フィルター */
@@ -49,7 +73,52 @@ export interface VulnFilter {
 export interface VulnMatch {
   packageName: string;
   installedVersion: string;
+  sourceFile: string;
+  /** 検出したサブプロジェクト（スキャン対象からの相対パス、トップレベルは "."） */
+  project: string;
+  /** インストール済みバージョンが OSV の影響範囲に含まれるか（not_affected は誤検知の可能性、unpinned は範囲指定で未固定） */
+  versionMatch: VersionMatch;
   vulnerability: Vulnerability;
+  /** 適用された抑制（既知のリスクとして受容済み） */
+  suppression: Suppression | null;
+  /** マニフェストの直接依存から脆弱なパッケージまでの経路（"name@version"、分からなければ空） */
+  dependencyPath: string[];
+  /** 開発時のみの依存関係で見つかった検出 */
+  dev: boolean;
+  /** 省略可能な依存関係で見つかった検出 */
+  optional: boolean;
+}
+
+/** 開発時のみの依存関係の検出の扱い（含める / 後回し / 除外） */
+export type DevFindings = "include" | "deprioritize" | "exclude";
+
+/** 抑制ルール（.paperstack-ignore.toml またはデータベース） */
+export interface Suppression {
+  /** データベースの行 ID（ファイル由来は null） */
+  id: number | null;
+  /** 脆弱性 ID（別名でも一致） */
+  vulnId: string | null;
+  package: string | null;
+  ecosystem: string | null;
+  /** スキャン対象からの相対パス */
+  path: string | null;
+  /** 適用するスキャン対象ディレクトリ（null はすべて） */
+  directory: string | null;
+  /** 有効期限（YYYY-MM-DD） */
+  expires: string | null;
+  reason: string;
+  source: "file" | "database";
+}
+
+/** 影響範囲の判定結果 */
+export type VersionMatch = "affected" | "not_affected" | "unknown" | "unpinned";
+
+/** サブプロジェクトごとのスキャン集計 */
+export interface ScanProject {
+  path: string;
+  ecosystems: string[];
+  totalPackages: number;
+  vulnCount: number;
 }
 
 /** スキャン結果 */
@@ -59,6 +128,87 @@ export interface ScanResult {
   vulnerabilities: VulnMatch[];
   scannedAt: string;
   totalPackages: number;
+  /** サブプロジェクトごとの集計 */
+  projects: ScanProject[];
+  /** 照会できなかったもの（SBOM の未対応 purl など） */
+  warnings: string[];
+  /** 検出を保存したスキャン実行の ID（保存に失敗した場合は null） */
+  runId: number | null;
+  /** 依存関係ごとのアップグレード計画 */
+  remediation: Remediation[];
+  /** 開発時のみの依存関係の検出の扱い */
+  devFindings: DevFindings;
+}
+
+/** 依存関係ごとのアップグレード計画 */
+export interface Remediation {
+  project: string;
+  sourceFile: string;
+  ecosystem: string;
+  packageName: string;
+  installedVersion: string;
+  /** 該当する脆弱性のうち最も高い深刻度 */
+  severity: string;
+  /** 解消できる脆弱性をすべて解消する最小のバージョン（修正がなければ null） */
+  targetVersion: string | null;
+  /** メジャーバージョンをまたぐ */
+  majorBump: boolean;
+  fixes: string[];
+  unfixed: string[];
+}
+
+/** 検出結果を保存したスキャンの実行 */
+export interface ScanRun {
+  id: number;
+  directory: string;
+  totalPackages: number;
+  /** 抑制済みを除く検出数 */
+  vulnCount: number;
+  scannedAt: string;
+}
+
+/** スキャンの実行ごとに保存した検出 */
+export interface ScanFinding {
+  project: string;
+  sourceFile: string;
+  ecosystem: string;
+  packageName: string;
+  installedVersion: string;
+  vulnId: string;
+  /** 実行間で照合する正規 ID（CVE > GHSA > その他） */
+  canonicalId: string;
+  severity: string;
+  suppressed: boolean;
+}
+
+/** 監視対象プロジェクト（バックグラウンドで定期的に再スキャン） */
+export interface WatchedProject {
+  id: number;
+  directory: string;
+  /** 再スキャンの間隔（時間） */
+  intervalHours: number;
+  enabled: boolean;
+  lastScannedAt: string | null;
+  lastRunId: number | null;
+  /** 最後の再スキャンが失敗した場合のエラー */
+  lastError: string | null;
+}
+
+/** 2 回のスキャンの差分 */
+export interface ScanDiff {
+  base: ScanRun;
+  target: ScanRun;
+  new: ScanFinding[];
+  fixed: ScanFinding[];
+  stillPresent: ScanFinding[];
+}
+
+/** OSV データベースエクスポート（all.zip）の取り込み結果 */
+export interface OsvImportSummary {
+  imported: number;
+  /** 解析できずに読み飛ばしたエントリ数 */
+  skipped: number;
+  ecosystems: string[];
 }
 
 /** 脆弱性一覧レスポンス */
@@ -73,6 +223,8 @@ export interface VulnListResponse {
 export interface ScanHistory {
   id: number;
   directory: string;
+  /** サブプロジェクト（スキャン対象からの相対パス、トップレベルは "."） */
+  project: string;
   ecosystem: string;
   vulnCount: number;
   scannedAt: string;
@@ -110,6 +262,20 @@ export function getSeverityLabel(severity: Severity): string {
   }
 }
 
+/** 深刻度の算出元の表示名 */
+export function getSeveritySourceLabel(source: SeveritySource | null): string {
+  switch (source) {
+    case "cvss":
+      return "CVSS";
+    case "database_specific":
+      return "データベース固有の評価";
+    case "ecosystem_specific":
+      return "エコシステム固有の評価";
+    default:
+      return "情報なし";
+  }
+}
+
 /** エコシステムの表示名 */
 export function getEcosystemLabel(ecosystem: string): string {
   switch (ecosystem) {
@@ -164,4 +330,5 @@ export const SEVERITIES: { id: Severity; label: string }[] = [
   { id: "high", label: "重要" },
   { id: "medium", label: "警告" },
   { id: "low", label: "注意" },
+  { id: "unknown", label: "不明" },
 ];